@@ -2,6 +2,7 @@ pub mod bytecode;
 pub mod eofvalidation;
 pub mod evmrunner;
 pub mod format_kzg_setup;
+pub mod precompile_bench;
 pub mod statetest;
 
 use structopt::{clap::AppSettings, StructOpt};
@@ -24,6 +25,8 @@ pub enum MainCmd {
     Evm(evmrunner::Cmd),
     #[structopt(alias = "bc", about = "Prints the opcodes of an hex Bytecodes.")]
     Bytecode(bytecode::Cmd),
+    #[structopt(about = "Benchmarks the precompiles and prints the gas per microsecond.")]
+    PrecompileBench(precompile_bench::Cmd),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -54,6 +57,10 @@ impl MainCmd {
                 cmd.run();
                 Ok(())
             }
+            Self::PrecompileBench(cmd) => {
+                cmd.run();
+                Ok(())
+            }
         }
     }
 }
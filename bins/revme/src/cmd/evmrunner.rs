@@ -1,8 +1,11 @@
+pub mod tracer;
+
+use super::statetest::export::export_test_suite;
 use revm::{
     db::BenchmarkDB,
     inspector_handle_register,
-    inspectors::TracerEip3155,
-    primitives::{eof::EofDecodeError, Address, Bytecode, TxKind},
+    inspectors::{CallGraphInspector, NoOpInspector, TracerEip3155},
+    primitives::{Address, Bytecode, BytecodeDecodeError, TxKind},
     Evm,
 };
 use std::io::Error as IoError;
@@ -10,6 +13,7 @@ use std::path::PathBuf;
 use std::time::Duration;
 use std::{borrow::Cow, fs};
 use structopt::StructOpt;
+use tracer::{prestate, FourByteInspector, TracerKind};
 
 extern crate alloc;
 
@@ -26,7 +30,11 @@ pub enum Errors {
     #[error(transparent)]
     Io(#[from] IoError),
     #[error(transparent)]
-    EofError(#[from] EofDecodeError),
+    BytecodeError(#[from] BytecodeDecodeError),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("Spec can't be exported as a statetest")]
+    UnsupportedExportSpec,
 }
 
 /// Evm runner command allows running arbitrary evm bytecode.
@@ -49,9 +57,15 @@ pub struct Cmd {
     /// Print the state.
     #[structopt(long)]
     state: bool,
-    /// Print the trace.
+    /// Print the trace. Same as `--tracer struct`.
     #[structopt(long)]
     trace: bool,
+    /// Trace the execution with the given tracer and print its output.
+    #[structopt(long, possible_values = TracerKind::VARIANTS)]
+    tracer: Option<TracerKind>,
+    /// Write the executed transaction as a statetest fixture to the given path.
+    #[structopt(long)]
+    export_statetest: Option<PathBuf>,
 }
 
 impl Cmd {
@@ -98,20 +112,78 @@ impl Cmd {
             return Ok(());
         }
 
-        let out = if self.trace {
-            let mut evm = evm
-                .modify()
-                .reset_handler_with_external_context(TracerEip3155::new(
-                    Box::new(std::io::stdout()),
-                ))
-                .append_handler_register(inspector_handle_register)
-                .build();
-
-            evm.transact().map_err(|_| Errors::EVMError)?
-        } else {
+        if let Some(path) = &self.export_statetest {
             let out = evm.transact().map_err(|_| Errors::EVMError)?;
-            println!("Result: {:#?}", out.result);
-            out
+            let spec_id = evm.spec_id();
+            let inner = &mut evm.context.evm.inner;
+            let fixture = export_test_suite("evmrunner", &mut inner.db, &inner.env, spec_id, &out)
+                .map_err(|_| Errors::EVMError)?
+                .ok_or(Errors::UnsupportedExportSpec)?;
+            fs::write(path, serde_json::to_string_pretty(&fixture)?)?;
+        }
+
+        let tracer = self
+            .tracer
+            .or_else(|| self.trace.then_some(TracerKind::Struct));
+        let out = match tracer {
+            None => {
+                let out = evm.transact().map_err(|_| Errors::EVMError)?;
+                println!("Result: {:#?}", out.result);
+                out
+            }
+            Some(TracerKind::Struct) => {
+                let mut evm = evm
+                    .modify()
+                    .reset_handler_with_external_context(TracerEip3155::new(Box::new(
+                        std::io::stdout(),
+                    )))
+                    .append_handler_register(inspector_handle_register)
+                    .build();
+
+                evm.transact().map_err(|_| Errors::EVMError)?
+            }
+            Some(TracerKind::Call) => {
+                let mut evm = evm
+                    .modify()
+                    .reset_handler_with_external_context(CallGraphInspector::default())
+                    .append_handler_register(inspector_handle_register)
+                    .build();
+
+                let out = evm.transact().map_err(|_| Errors::EVMError)?;
+                println!("{}", evm.context.external.graph().to_json()?);
+                out
+            }
+            Some(TracerKind::Prestate) => {
+                let out = evm.transact().map_err(|_| Errors::EVMError)?;
+                let prestate = prestate(evm.db_mut(), &out.state).map_err(|_| Errors::EVMError)?;
+                println!("{}", serde_json::to_string_pretty(&prestate)?);
+                out
+            }
+            Some(TracerKind::FourByte) => {
+                let mut evm = evm
+                    .modify()
+                    .reset_handler_with_external_context(FourByteInspector::default())
+                    .append_handler_register(inspector_handle_register)
+                    .build();
+
+                let out = evm.transact().map_err(|_| Errors::EVMError)?;
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&evm.context.external.calls)?
+                );
+                out
+            }
+            Some(TracerKind::Noop) => {
+                let mut evm = evm
+                    .modify()
+                    .reset_handler_with_external_context(NoOpInspector)
+                    .append_handler_register(inspector_handle_register)
+                    .build();
+
+                let out = evm.transact().map_err(|_| Errors::EVMError)?;
+                println!("Result: {:#?}", out.result);
+                out
+            }
         };
 
         if self.state {
@@ -0,0 +1,112 @@
+use revm::{
+    interpreter::{CallInputs, CallOutcome},
+    primitives::{db::Database, Address, Bytes, EvmState, U256},
+    EvmContext, Inspector,
+};
+use serde::Serialize;
+use std::{collections::BTreeMap, str::FromStr};
+
+/// Tracer selected with the `--tracer` option.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TracerKind {
+    /// EIP-3155 struct logs.
+    Struct,
+    /// Call graph of the transaction.
+    Call,
+    /// State of the touched accounts before execution.
+    Prestate,
+    /// Number of calls per function selector and calldata size.
+    FourByte,
+    /// Runs the inspector machinery without collecting anything.
+    Noop,
+}
+
+impl TracerKind {
+    /// Names accepted on the command line.
+    pub const VARIANTS: &'static [&'static str] = &["struct", "call", "prestate", "4byte", "noop"];
+}
+
+impl FromStr for TracerKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "struct" => Ok(Self::Struct),
+            "call" => Ok(Self::Call),
+            "prestate" => Ok(Self::Prestate),
+            "4byte" => Ok(Self::FourByte),
+            "noop" => Ok(Self::Noop),
+            _ => Err(format!("unknown tracer {s}")),
+        }
+    }
+}
+
+/// Counts calls by `<selector>-<calldata size>` key, same as geth `4byteTracer`.
+#[derive(Debug, Default)]
+pub struct FourByteInspector {
+    pub calls: BTreeMap<String, u64>,
+}
+
+impl<DB: Database> Inspector<DB> for FourByteInspector {
+    fn call(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        // precompiles do not have selectors.
+        if inputs.input.len() >= 4 && !context.precompiles.contains(&inputs.bytecode_address) {
+            let key = format!(
+                "0x{}-{}",
+                hex::encode(&inputs.input[..4]),
+                inputs.input.len() - 4
+            );
+            *self.calls.entry(key).or_default() += 1;
+        }
+        None
+    }
+}
+
+/// Account state before the execution, same as geth `prestateTracer` output.
+#[derive(Debug, Serialize)]
+pub struct PrestateAccount {
+    pub balance: U256,
+    pub nonce: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub storage: BTreeMap<U256, U256>,
+}
+
+/// Returns the state of all accounts touched by the execution as it was before the execution.
+///
+/// Storage contains original values of the accessed slots.
+pub fn prestate<DB: Database>(
+    db: &mut DB,
+    state: &EvmState,
+) -> Result<BTreeMap<Address, PrestateAccount>, DB::Error> {
+    let mut prestate = BTreeMap::new();
+    for (address, account) in state {
+        let Some(info) = db.basic(*address)? else {
+            continue;
+        };
+        let code = match info.code {
+            Some(code) => code,
+            None => db.code_by_hash(info.code_hash)?,
+        };
+        let code = code.original_bytes();
+        prestate.insert(
+            *address,
+            PrestateAccount {
+                balance: info.balance,
+                nonce: info.nonce,
+                code: (!code.is_empty()).then_some(code),
+                storage: account
+                    .storage
+                    .iter()
+                    .map(|(slot, value)| (*slot, value.original_value()))
+                    .collect(),
+            },
+        );
+    }
+    Ok(prestate)
+}
@@ -0,0 +1,224 @@
+use revm::{
+    precompile::{
+        fuzz::{targets, FuzzTarget, InputShape},
+        u64_to_address, Bytes, PrecompileSpecId,
+    },
+    primitives::{hex, SpecId},
+};
+use std::time::{Duration, Instant};
+use structopt::StructOpt;
+
+/// Address of the modexp precompile, its input is built from the lengths instead of shaped.
+const MODEXP_ADDRESS: u64 = 5;
+
+/// Precompile benchmark command
+#[derive(StructOpt, Debug)]
+pub struct Cmd {
+    /// Spec of the benchmarked precompiles, e.g. `Cancun` or `Prague`.
+    #[structopt(long, default_value = "Prague")]
+    spec: String,
+    /// Benchmark only the precompiles with the given names, e.g. `ecrecover` or `bn254-pairing`.
+    #[structopt(long)]
+    precompile: Vec<String>,
+    /// Input sizes in bytes. Inputs of fixed size precompiles are padded or truncated, and
+    /// inputs of precompiles taking whole chunks are rounded down to a multiple of the chunk.
+    #[structopt(long, use_delimiter = true, default_value = "32,128,512,2048,8192")]
+    sizes: Vec<usize>,
+    /// Time spent measuring each input, in milliseconds.
+    #[structopt(long, default_value = "200")]
+    time_ms: u64,
+}
+
+/// Measurement of a precompile call.
+#[derive(Debug)]
+struct Measurement {
+    input_len: usize,
+    gas: u64,
+    time_per_call: Duration,
+}
+
+impl Measurement {
+    fn gas_per_micros(&self) -> f64 {
+        self.gas as f64 / (self.time_per_call.as_secs_f64() * 1_000_000.0)
+    }
+}
+
+impl Cmd {
+    /// Run precompile benchmark command.
+    pub fn run(&self) {
+        let spec = PrecompileSpecId::from_spec_id(SpecId::from(self.spec.as_str()));
+        let time = Duration::from_millis(self.time_ms);
+
+        println!(
+            "{:<24} {:>12} {:>12} {:>16} {:>12}",
+            "precompile", "input bytes", "gas", "time/call (us)", "gas/us"
+        );
+        for target in targets(spec) {
+            if !self.precompile.is_empty() && !self.precompile.iter().any(|n| n == target.name) {
+                continue;
+            }
+            let mut measured_lens = Vec::new();
+            for &size in &self.sizes {
+                let input = bench_input(&target, size);
+                if measured_lens.contains(&input.len()) {
+                    continue;
+                }
+                measured_lens.push(input.len());
+
+                match measure(&target, &input, time) {
+                    Some(m) => println!(
+                        "{:<24} {:>12} {:>12} {:>16.3} {:>12.1}",
+                        target.name,
+                        m.input_len,
+                        m.gas,
+                        m.time_per_call.as_secs_f64() * 1_000_000.0,
+                        m.gas_per_micros()
+                    ),
+                    None => println!("{:<24} {:>12} {:>12}", target.name, input.len(), "error"),
+                }
+            }
+        }
+    }
+}
+
+/// BN254 G1 generator.
+const BN254_G1: [u8; 64] = hex!(
+    "0000000000000000000000000000000000000000000000000000000000000001"
+    "0000000000000000000000000000000000000000000000000000000000000002"
+);
+
+/// BN254 G1 and G2 points of a pairing check, from the `test_alt_bn128_pair` test.
+const BN254_PAIR: [u8; 192] = hex!(
+    "1c76476f4def4bb94541d57ebba1193381ffa7aa76ada664dd31c16024c43f59"
+    "3034dd2920f673e204fee2811c678745fc819b55d3e9d294e45c9b03a76aef41"
+    "209dd15ebff5d46c4bd888e51a93cf99a7329636c63514396b4a452003a35bf7"
+    "04bf11ca01483bfa8b34b43561848d28905960114c8ac04049af4b6315a41678"
+    "2bb8324af6cfc93537a2ad1a445cfd0ca2a71acd7ac41fadbf933c2a51be344d"
+    "120a2a4cf30c1bf9845f20c6fe39e07ea2cce61f0c9bb048165fe5e4de877550"
+);
+
+/// BLS12-381 G1 generator.
+const BLS12_G1: [u8; 128] = hex!(
+    "0000000000000000000000000000000017f1d3a73197d7942695638c4fa9ac0f"
+    "c3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb"
+    "0000000000000000000000000000000008b3f481e3aaa0f1a09e30ed741d8ae4"
+    "fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1"
+);
+
+/// BLS12-381 G2 generator.
+const BLS12_G2: [u8; 256] = hex!(
+    "00000000000000000000000000000000024aa2b2f08f0a91260805272dc51051"
+    "c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8"
+    "0000000000000000000000000000000013e02b6052719f607dacd3a088274f65"
+    "596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e"
+    "000000000000000000000000000000000ce5d527727d6e118cc9cdc6da2e351a"
+    "adfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801"
+    "000000000000000000000000000000000606c4a02ea734cc32acd2b02bc28b99"
+    "cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be"
+);
+
+/// Point evaluation input, from the `basic_test` of the point evaluation precompile.
+const KZG_POINT_EVALUATION: [u8; 192] = hex!(
+    "01e798154708fe7789429634053cbf9f99b619f9f084048927333fce637f549b"
+    "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000000"
+    "1522a4a7f34e1ea350ae07c29c96c7e79655aa926122e95fe69fcbd932ca49e9"
+    "8f59a8d2a1a625a17f3fea0fe5eb8c896db3764f3185481bc22f91b4aaffcca2"
+    "5f26936857bc3a7c2539ea8ec3a952b7a62ad71d14c5719385c0686f18714304"
+    "75bf3a00f0aa3f7b8dd99a9abc2160744faf0070725e00b60ad9a026a15b1a8c"
+);
+
+/// Returns the input of about `size` bytes accepted by the precompile.
+///
+/// Precompiles that check their input is on the curve or is a valid proof get valid inputs,
+/// repeated up to `size` for the precompiles taking a list of points.
+fn bench_input(target: &FuzzTarget, size: usize) -> Bytes {
+    if target.address == u64_to_address(MODEXP_ADDRESS) {
+        return modexp_input(size / 3);
+    }
+    let Some(input) = valid_input(target.name) else {
+        return target.shape_input(&pattern(size));
+    };
+    match target.input_shape() {
+        InputShape::Multiple(len) => input.repeat((size / len).max(1)).into(),
+        _ => input.into(),
+    }
+}
+
+/// Returns the valid input of the precompile with the given name, a single element of the list
+/// for the precompiles taking a list of points.
+fn valid_input(name: &str) -> Option<Vec<u8>> {
+    // Scalar of the multiplications, a 32 byte word.
+    let scalar = pattern(32);
+    let input = match name {
+        "bn254-add" => [BN254_G1, BN254_G1].concat(),
+        "bn254-mul" => [&BN254_G1[..], &scalar].concat(),
+        "bn254-pairing" => BN254_PAIR.to_vec(),
+        "kzg-point-evaluation" => KZG_POINT_EVALUATION.to_vec(),
+        "bls12-g1-add" => [BLS12_G1, BLS12_G1].concat(),
+        "bls12-g1-mul" | "bls12-g1-msm" => [&BLS12_G1[..], &scalar].concat(),
+        "bls12-g2-add" => [BLS12_G2, BLS12_G2].concat(),
+        "bls12-g2-mul" | "bls12-g2-msm" => [&BLS12_G2[..], &scalar].concat(),
+        "bls12-pairing" => [&BLS12_G1[..], &BLS12_G2].concat(),
+        // Coordinates of the generators are valid field elements.
+        "bls12-map-fp-to-g1" => BLS12_G1[..64].to_vec(),
+        "bls12-map-fp2-to-g2" => BLS12_G2[..128].to_vec(),
+        _ => return None,
+    };
+    Some(input)
+}
+
+/// Returns the modexp input with the base, exponent and modulus of `len` bytes each.
+fn modexp_input(len: usize) -> Bytes {
+    let mut input = Vec::with_capacity(96 + 3 * len);
+    for _ in 0..3 {
+        input.extend_from_slice(&[0; 24]);
+        input.extend_from_slice(&(len as u64).to_be_bytes());
+    }
+    input.extend_from_slice(&pattern(3 * len));
+    input.into()
+}
+
+/// Returns deterministic non-trivial bytes.
+fn pattern(len: usize) -> Vec<u8> {
+    (0..len)
+        .map(|i| (i as u8).wrapping_mul(31).wrapping_add(7))
+        .collect()
+}
+
+/// Calls the precompile repeatedly for at least `time`, returns `None` if the call fails.
+fn measure(target: &FuzzTarget, input: &[u8], time: Duration) -> Option<Measurement> {
+    let gas = target.run(input, u64::MAX).ok()?.gas_used;
+
+    let mut calls = 0u32;
+    let start = Instant::now();
+    while start.elapsed() < time || calls == 0 {
+        let _ = target.run(input, u64::MAX);
+        calls += 1;
+    }
+    Some(Measurement {
+        input_len: input.len(),
+        gas,
+        time_per_call: start.elapsed() / calls,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_spec_inputs_are_valid() {
+        let spec = PrecompileSpecId::from_spec_id(SpecId::PRAGUE);
+        for target in targets(spec) {
+            for size in [32, 1024] {
+                let input = bench_input(&target, size);
+                assert!(
+                    target.run(&input, u64::MAX).is_ok(),
+                    "{} rejects its {} byte input",
+                    target.name,
+                    input.len()
+                );
+            }
+        }
+    }
+}
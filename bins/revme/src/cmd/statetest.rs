@@ -1,3 +1,5 @@
+mod coverage;
+pub mod export;
 pub mod merkle_trie;
 pub mod models;
 mod runner;
@@ -31,6 +33,11 @@ pub struct Cmd {
     json_outcome: bool,
     #[structopt(long, alias = "no-fail-fast")]
     keep_going: bool,
+    /// Print opcodes and precompiles that were not executed by any test, per spec.
+    ///
+    /// Ignored in trace mode.
+    #[structopt(long)]
+    coverage: bool,
 }
 
 impl Cmd {
@@ -45,6 +52,7 @@ impl Cmd {
                 self.json,
                 self.json_outcome,
                 self.keep_going,
+                self.coverage,
             )?
         }
         Ok(())
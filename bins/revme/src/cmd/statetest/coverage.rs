@@ -0,0 +1,105 @@
+use revm::{
+    interpreter::{opcode::OpCode, CallInputs, CallOutcome, Interpreter},
+    precompile::{PrecompileSpecId, Precompiles},
+    primitives::{db::Database, Address, HashSet, SpecId},
+    EvmContext, Inspector,
+};
+use std::collections::BTreeMap;
+
+/// Inspector that records executed opcodes and called precompiles.
+#[derive(Clone, Debug)]
+pub struct Coverage {
+    opcodes: [bool; 256],
+    precompiles: HashSet<Address>,
+}
+
+impl Default for Coverage {
+    fn default() -> Self {
+        Self {
+            opcodes: [false; 256],
+            precompiles: HashSet::default(),
+        }
+    }
+}
+
+impl Coverage {
+    /// Merges the coverage of another run into this one.
+    pub fn merge(&mut self, other: &Self) {
+        for (covered, other) in self.opcodes.iter_mut().zip(other.opcodes) {
+            *covered |= other;
+        }
+        self.precompiles.extend(other.precompiles.iter().copied());
+    }
+}
+
+impl<DB: Database> Inspector<DB> for Coverage {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        self.opcodes[interp.current_opcode() as usize] = true;
+    }
+
+    fn call(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        if context.precompiles.contains(&inputs.bytecode_address) {
+            self.precompiles.insert(inputs.bytecode_address);
+        }
+        None
+    }
+}
+
+/// Coverage of all executed tests, aggregated per spec.
+#[derive(Debug, Default)]
+pub struct CoverageReport {
+    specs: BTreeMap<SpecId, Coverage>,
+}
+
+impl CoverageReport {
+    /// Records the coverage of a test executed with the given spec.
+    pub fn record(&mut self, spec_id: SpecId, coverage: &Coverage) {
+        self.specs.entry(spec_id).or_default().merge(coverage);
+    }
+
+    /// Prints covered counts and uncovered opcodes and precompiles of every spec.
+    ///
+    /// Opcodes are checked against all opcodes known to the interpreter, not only the ones
+    /// enabled in the spec.
+    pub fn print(&self) {
+        println!("\nCoverage:");
+        for (spec_id, coverage) in &self.specs {
+            let known_opcodes: Vec<_> = (0..=u8::MAX).filter_map(OpCode::new).collect();
+            let uncovered_opcodes: Vec<_> = known_opcodes
+                .iter()
+                .copied()
+                .filter(|op| !coverage.opcodes[op.get() as usize])
+                .map(OpCode::as_str)
+                .collect();
+
+            let precompiles = Precompiles::new(PrecompileSpecId::from_spec_id(*spec_id));
+            let mut uncovered_precompiles: Vec<_> = precompiles
+                .addresses()
+                .filter(|address| !coverage.precompiles.contains(*address))
+                .collect();
+            uncovered_precompiles.sort_unstable();
+
+            println!(
+                "{spec_id:?}: {}/{} opcodes, {}/{} precompiles",
+                known_opcodes.len() - uncovered_opcodes.len(),
+                known_opcodes.len(),
+                precompiles.len() - uncovered_precompiles.len(),
+                precompiles.len(),
+            );
+            if !uncovered_opcodes.is_empty() {
+                println!("  uncovered opcodes: {}", uncovered_opcodes.join(", "));
+            }
+            if !uncovered_precompiles.is_empty() {
+                let addresses: Vec<_> = uncovered_precompiles
+                    .iter()
+                    .map(|address| address.to_string())
+                    .collect();
+                println!("  uncovered precompiles: {}", addresses.join(", "));
+            }
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use super::merkle_trie::{log_rlp_hash, state_merkle_trie_root};
+use crate::cmd::evmrunner::tracer::prestate;
+use revm::{
+    db::{CacheState, State},
+    primitives::{
+        db::{Database, DatabaseCommit},
+        keccak256, AccountInfo, Bytecode, Env, ResultAndState, SpecId, TxKind, B256,
+    },
+};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+
+/// Returns the name of the spec used in the statetest `post` section.
+///
+/// [SpecId::LATEST] is exported as the latest named spec.
+pub fn spec_name(spec_id: SpecId) -> Option<&'static str> {
+    Some(match spec_id {
+        SpecId::FRONTIER => "Frontier",
+        SpecId::HOMESTEAD => "Homestead",
+        SpecId::TANGERINE => "EIP150",
+        SpecId::SPURIOUS_DRAGON => "EIP158",
+        SpecId::BYZANTIUM => "Byzantium",
+        SpecId::PETERSBURG => "ConstantinopleFix",
+        SpecId::ISTANBUL => "Istanbul",
+        SpecId::BERLIN => "Berlin",
+        SpecId::LONDON => "London",
+        SpecId::MERGE => "Merge",
+        SpecId::SHANGHAI => "Shanghai",
+        SpecId::CANCUN => "Cancun",
+        SpecId::PRAGUE => "Prague",
+        SpecId::PRAGUE_EOF | SpecId::LATEST => "PragueEOF",
+        _ => return None,
+    })
+}
+
+/// Captures an executed transaction as a statetest fixture with a single test named `name`.
+///
+/// Pre-state contains every account touched by the transaction as it was in `db`, with the
+/// original values of the accessed storage slots, so `db` must not have the transaction
+/// committed. Post state root and logs hash are calculated the same way the statetest runner
+/// calculates them.
+///
+/// Block hashes are not part of the fixture, transactions that use `BLOCKHASH` will get
+/// different values when the fixture is executed.
+///
+/// Returns `Ok(None)` if the spec can't be expressed in a statetest.
+pub fn export_test_suite<DB: Database>(
+    name: &str,
+    db: &mut DB,
+    env: &Env,
+    spec_id: SpecId,
+    result: &ResultAndState,
+) -> Result<Option<Value>, DB::Error> {
+    let Some(spec_name) = spec_name(spec_id) else {
+        return Ok(None);
+    };
+
+    let mut cache = CacheState::new(spec_id.is_enabled_in(SpecId::SPURIOUS_DRAGON));
+    let mut pre = Map::new();
+    let mut caller_nonce = 0;
+    for (address, account) in prestate(db, &result.state)? {
+        if address == env.tx.caller {
+            caller_nonce = account.nonce;
+        }
+        let code = account.code.unwrap_or_default();
+        let storage: BTreeMap<_, _> = account
+            .storage
+            .into_iter()
+            .filter(|(_, value)| !value.is_zero())
+            .collect();
+
+        pre.insert(
+            address.to_string(),
+            json!({
+                "balance": account.balance,
+                "code": code,
+                "nonce": format!("{:#x}", account.nonce),
+                "storage": storage,
+            }),
+        );
+        let info = AccountInfo::new(
+            account.balance,
+            account.nonce,
+            keccak256(&code),
+            Bytecode::new_raw(code),
+        );
+        cache.insert_account_with_storage(address, info, storage.into_iter().collect());
+    }
+
+    let mut state = State::builder()
+        .with_cached_prestate(cache)
+        .with_bundle_update()
+        .build();
+    state.commit(result.state.clone());
+    let hash = state_merkle_trie_root(state.cache.trie_account());
+    let logs = log_rlp_hash(result.result.logs());
+
+    let block = &env.block;
+    let mut test_env = json!({
+        "currentCoinbase": block.coinbase,
+        "currentDifficulty": block.difficulty,
+        "currentGasLimit": block.gas_limit,
+        "currentNumber": block.number,
+        "currentTimestamp": block.timestamp,
+        "currentBaseFee": block.basefee,
+    });
+    if let Some(prevrandao) = block.prevrandao {
+        test_env["currentRandom"] = json!(prevrandao);
+    }
+    if let Some(blob) = &block.blob_excess_gas_and_price {
+        test_env["currentExcessBlobGas"] = json!(format!("{:#x}", blob.excess_blob_gas));
+    }
+
+    let tx = &env.tx;
+    let mut transaction = json!({
+        "data": [tx.data],
+        "gasLimit": [format!("{:#x}", tx.gas_limit)],
+        "nonce": format!("{:#x}", caller_nonce),
+        "secretKey": B256::ZERO,
+        "sender": tx.caller,
+        "to": match tx.transact_to {
+            TxKind::Call(address) => address.to_string(),
+            TxKind::Create => String::new(),
+        },
+        "value": [tx.value],
+        "accessLists": [tx.access_list],
+        "blobVersionedHashes": tx.blob_hashes,
+    });
+    match tx.gas_priority_fee {
+        Some(priority_fee) => {
+            transaction["maxFeePerGas"] = json!(tx.gas_price);
+            transaction["maxPriorityFeePerGas"] = json!(priority_fee);
+        }
+        None => transaction["gasPrice"] = json!(tx.gas_price),
+    }
+    if let Some(max_fee_per_blob_gas) = tx.max_fee_per_blob_gas {
+        transaction["maxFeePerBlobGas"] = json!(max_fee_per_blob_gas);
+    }
+
+    Ok(Some(json!({
+        name: {
+            "env": test_env,
+            "pre": pre,
+            "transaction": transaction,
+            "post": {
+                spec_name: [{
+                    "hash": hash,
+                    "logs": logs,
+                    "indexes": { "data": 0, "gas": 0, "value": 0 },
+                }],
+            },
+        }
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmd::statetest::{models::TestSuite, runner::execute_test_suite};
+    use revm::{
+        db::BenchmarkDB,
+        primitives::{Address, Bytecode},
+        Evm,
+    };
+    use std::{
+        fs,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    /// Executes the fixture with the statetest runner.
+    fn run_fixture(fixture: &Value) -> bool {
+        let path = std::env::temp_dir().join(format!("revme-export-{}.json", std::process::id()));
+        fs::write(&path, fixture.to_string()).unwrap();
+        let result = execute_test_suite(
+            &path,
+            &Arc::new(Mutex::new(Duration::ZERO)),
+            false,
+            false,
+            None,
+        );
+        fs::remove_file(&path).unwrap();
+        result.is_ok()
+    }
+
+    #[test]
+    fn exported_fixture_passes_the_runner() {
+        // PUSH1 1, PUSH1 0, SSTORE, PUSH1 0, PUSH1 0, LOG0, STOP
+        let code = Bytecode::new_raw(
+            [
+                0x60, 0x01, 0x60, 0x00, 0x55, 0x60, 0x00, 0x60, 0x00, 0xa0, 0x00,
+            ]
+            .into(),
+        );
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(code))
+            .with_spec_id(SpecId::CANCUN)
+            .modify_tx_env(|tx| {
+                tx.caller = Address::with_last_byte(1);
+                tx.transact_to = TxKind::Call(Address::ZERO);
+            })
+            .build();
+        let result = evm.transact().unwrap();
+        assert_eq!(result.result.logs().len(), 1);
+
+        let inner = &mut evm.context.evm.inner;
+        let mut fixture =
+            export_test_suite("test", &mut inner.db, &inner.env, SpecId::CANCUN, &result)
+                .unwrap()
+                .unwrap();
+
+        let suite: TestSuite = serde_json::from_value(fixture.clone()).unwrap();
+        let unit = &suite.0["test"];
+        assert_eq!(unit.pre.len(), 2);
+        assert_eq!(unit.transaction.sender, Some(Address::with_last_byte(1)));
+
+        // Post state hash and logs hash match the execution of the fixture.
+        assert!(run_fixture(&fixture));
+        fixture["test"]["post"]["Cancun"][0]["logs"] = json!(B256::ZERO);
+        assert!(!run_fixture(&fixture));
+    }
+}
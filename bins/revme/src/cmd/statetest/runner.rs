@@ -1,4 +1,5 @@
 use super::{
+    coverage::{Coverage, CoverageReport},
     merkle_trie::{log_rlp_hash, state_merkle_trie_root},
     models::{SpecName, Test, TestSuite},
     utils::recover_address,
@@ -243,6 +244,7 @@ pub fn execute_test_suite(
     elapsed: &Arc<Mutex<Duration>>,
     trace: bool,
     print_json_outcome: bool,
+    coverage: Option<&Mutex<CoverageReport>>,
 ) -> Result<(), TestError> {
     if skip_test(path) {
         return Ok(());
@@ -398,6 +400,33 @@ pub fn execute_test_suite(
                     };
                     // reset external context
                     (e, res)
+                } else if let Some(coverage) = coverage {
+                    let mut evm = evm
+                        .modify()
+                        .reset_handler_with_external_context(Coverage::default())
+                        .append_handler_register(inspector_handle_register)
+                        .build();
+
+                    let timer = Instant::now();
+                    let res = evm.transact_commit();
+                    *elapsed.lock().unwrap() += timer.elapsed();
+
+                    coverage
+                        .lock()
+                        .unwrap()
+                        .record(spec_id, &evm.context.external);
+
+                    let Err(e) = check_evm_execution(
+                        &test,
+                        unit.out.as_ref(),
+                        &name,
+                        &res,
+                        &evm,
+                        print_json_outcome,
+                    ) else {
+                        continue;
+                    };
+                    (e, res)
                 } else {
                     let timer = Instant::now();
                     let res = evm.transact_commit();
@@ -468,6 +497,7 @@ pub fn run(
     trace: bool,
     mut print_outcome: bool,
     keep_going: bool,
+    coverage: bool,
 ) -> Result<(), TestError> {
     // trace implies print_outcome
     if trace {
@@ -486,6 +516,7 @@ pub fn run(
     ));
     let queue = Arc::new(Mutex::new((0usize, test_files)));
     let elapsed = Arc::new(Mutex::new(std::time::Duration::ZERO));
+    let coverage = coverage.then(|| Arc::new(Mutex::new(CoverageReport::default())));
 
     let num_threads = match (single_thread, std::thread::available_parallelism()) {
         (true, _) | (false, Err(_)) => 1,
@@ -498,6 +529,7 @@ pub fn run(
         let n_errors = n_errors.clone();
         let console_bar = console_bar.clone();
         let elapsed = elapsed.clone();
+        let coverage = coverage.clone();
 
         let thread = std::thread::Builder::new().name(format!("runner-{i}"));
 
@@ -516,7 +548,13 @@ pub fn run(
                 (prev_idx, test_path)
             };
 
-            let result = execute_test_suite(&test_path, &elapsed, trace, print_outcome);
+            let result = execute_test_suite(
+                &test_path,
+                &elapsed,
+                trace,
+                print_outcome,
+                coverage.as_deref(),
+            );
 
             // Increment after the test is done.
             console_bar.inc(1);
@@ -550,6 +588,10 @@ pub fn run(
         elapsed.lock().unwrap().as_secs_f64()
     );
 
+    if let Some(coverage) = coverage {
+        coverage.lock().unwrap().print();
+    }
+
     let n_errors = n_errors.load(Ordering::SeqCst);
     let n_thread_errors = thread_errors.len();
     if n_errors == 0 && n_thread_errors == 0 {
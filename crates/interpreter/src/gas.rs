@@ -6,6 +6,8 @@ mod constants;
 pub use calc::*;
 pub use constants::*;
 
+use crate::InstructionResult;
+
 /// Represents the state of gas during execution.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -129,3 +131,59 @@ impl Gas {
         success
     }
 }
+
+/// Gas of a finished call or create frame, as seen by the parent frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FrameGas {
+    /// Gas limit of the frame, after the 63/64 forwarding and call stipend are applied.
+    pub limit: u64,
+    /// Gas consumed by the frame, `limit - returned`.
+    pub spent: u64,
+    /// Refund passed to the parent. Zero if the frame did not succeed.
+    pub refunded: i64,
+    /// Unused gas returned to the parent. Zero if the frame halted.
+    pub returned: u64,
+}
+
+impl FrameGas {
+    /// Creates the breakdown from the final gas and result of the frame.
+    #[inline]
+    pub const fn new(gas: &Gas, result: InstructionResult) -> Self {
+        let returned = if result.is_ok() || result.is_revert() {
+            gas.remaining()
+        } else {
+            0
+        };
+        Self {
+            limit: gas.limit(),
+            spent: gas.limit() - returned,
+            refunded: if result.is_ok() { gas.refunded() } else { 0 },
+            returned,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_gas() {
+        let mut gas = Gas::new(100);
+        assert!(gas.record_cost(40));
+        gas.record_refund(10);
+
+        let ok = FrameGas::new(&gas, InstructionResult::Stop);
+        assert_eq!((ok.spent, ok.refunded, ok.returned), (40, 10, 60));
+
+        let revert = FrameGas::new(&gas, InstructionResult::Revert);
+        assert_eq!(
+            (revert.spent, revert.refunded, revert.returned),
+            (40, 0, 60)
+        );
+
+        let halt = FrameGas::new(&gas, InstructionResult::InvalidFEOpcode);
+        assert_eq!((halt.spent, halt.refunded, halt.returned), (100, 0, 0));
+    }
+}
@@ -336,6 +336,17 @@ pub const fn warm_cold_cost(is_cold: bool) -> u64 {
     }
 }
 
+/// EIP-7702: Cost of loading the delegated account of the call target.
+///
+/// `is_delegate_account_cold` is `None` if the target does not delegate its code.
+#[inline]
+pub const fn delegation_cost(is_delegate_account_cold: Option<bool>) -> u64 {
+    match is_delegate_account_cold {
+        Some(is_cold) => warm_cold_cost(is_cold),
+        None => 0,
+    }
+}
+
 /// Memory expansion cost calculation for a given memory length.
 #[inline]
 pub const fn memory_gas_for_len(len: usize) -> u64 {
@@ -25,6 +25,14 @@ pub trait Host {
     /// Get code of `address` and if the account is cold.
     fn code(&mut self, address: Address) -> Option<(Bytes, bool)>;
 
+    /// Get code size of `address` and if the account is cold.
+    ///
+    /// Hosts that can get the size without loading the code should override this.
+    fn code_size(&mut self, address: Address) -> Option<(usize, bool)> {
+        self.code(address)
+            .map(|(code, is_cold)| (code.len(), is_cold))
+    }
+
     /// Get code hash of `address` and if the account is cold.
     fn code_hash(&mut self, address: Address) -> Option<(B256, bool)>;
 
@@ -70,6 +78,9 @@ pub struct LoadAccountResult {
     pub is_cold: bool,
     /// Is account empty, if true account is not created.
     pub is_empty: bool,
+    /// Is the delegated account cold loaded, `None` if the account code is not an EIP-7702
+    /// delegation designator.
+    pub is_delegate_account_cold: Option<bool>,
 }
 
 /// Result of a selfdestruct instruction.
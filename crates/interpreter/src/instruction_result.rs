@@ -90,6 +90,14 @@ pub enum InstructionResult {
     EofAuxDataTooSmall,
     /// `EXT*CALL` target address needs to be padded with 0s.
     InvalidEXTCALLTarget,
+    /// The cumulative memory limit of all call frames in the transaction has been exceeded.
+    CumulativeMemoryLimitOOG,
+    /// `SELFDESTRUCT` is disabled by the configuration.
+    SelfDestructDisabled,
+    /// Precompile is disabled by the chain configuration.
+    PrecompileDisabled,
+    /// Precompile is not implemented.
+    PrecompileNotImplemented,
 }
 
 impl From<SuccessReason> for InstructionResult {
@@ -111,6 +119,7 @@ impl From<HaltReason> for InstructionResult {
                 OutOfGasError::InvalidOperand => Self::InvalidOperandOOG,
                 OutOfGasError::Memory => Self::MemoryOOG,
                 OutOfGasError::MemoryLimit => Self::MemoryLimitOOG,
+                OutOfGasError::CumulativeMemoryLimit => Self::CumulativeMemoryLimitOOG,
                 OutOfGasError::Precompile => Self::PrecompileOOG,
             },
             HaltReason::OpcodeNotFound => Self::OpcodeNotFound,
@@ -134,6 +143,9 @@ impl From<HaltReason> for InstructionResult {
             HaltReason::EofAuxDataOverflow => Self::EofAuxDataOverflow,
             HaltReason::EofAuxDataTooSmall => Self::EofAuxDataTooSmall,
             HaltReason::EOFFunctionStackOverflow => Self::EOFFunctionStackOverflow,
+            HaltReason::SelfDestructDisabled => Self::SelfDestructDisabled,
+            HaltReason::PrecompileDisabled => Self::PrecompileDisabled,
+            HaltReason::PrecompileNotImplemented => Self::PrecompileNotImplemented,
             #[cfg(feature = "optimism")]
             HaltReason::FailedDeposit => Self::FatalExternalError,
         }
@@ -169,6 +181,7 @@ macro_rules! return_error {
         InstructionResult::OutOfGas
             | InstructionResult::MemoryOOG
             | InstructionResult::MemoryLimitOOG
+            | InstructionResult::CumulativeMemoryLimitOOG
             | InstructionResult::PrecompileOOG
             | InstructionResult::InvalidOperandOOG
             | InstructionResult::OpcodeNotFound
@@ -194,6 +207,9 @@ macro_rules! return_error {
             | InstructionResult::EofAuxDataTooSmall
             | InstructionResult::EofAuxDataOverflow
             | InstructionResult::InvalidEXTCALLTarget
+            | InstructionResult::SelfDestructDisabled
+            | InstructionResult::PrecompileDisabled
+            | InstructionResult::PrecompileNotImplemented
     };
 }
 
@@ -295,6 +311,9 @@ impl From<InstructionResult> for SuccessOrHalt {
             InstructionResult::MemoryLimitOOG => {
                 Self::Halt(HaltReason::OutOfGas(OutOfGasError::MemoryLimit))
             }
+            InstructionResult::CumulativeMemoryLimitOOG => {
+                Self::Halt(HaltReason::OutOfGas(OutOfGasError::CumulativeMemoryLimit))
+            }
             InstructionResult::MemoryOOG => Self::Halt(HaltReason::OutOfGas(OutOfGasError::Memory)),
             InstructionResult::PrecompileOOG => {
                 Self::Halt(HaltReason::OutOfGas(OutOfGasError::Precompile))
@@ -338,6 +357,11 @@ impl From<InstructionResult> for SuccessOrHalt {
             InstructionResult::ReturnContract => Self::Success(SuccessReason::EofReturnContract),
             InstructionResult::EofAuxDataOverflow => Self::Halt(HaltReason::EofAuxDataOverflow),
             InstructionResult::EofAuxDataTooSmall => Self::Halt(HaltReason::EofAuxDataTooSmall),
+            InstructionResult::SelfDestructDisabled => Self::Halt(HaltReason::SelfDestructDisabled),
+            InstructionResult::PrecompileDisabled => Self::Halt(HaltReason::PrecompileDisabled),
+            InstructionResult::PrecompileNotImplemented => {
+                Self::Halt(HaltReason::PrecompileNotImplemented)
+            }
             InstructionResult::InvalidEXTCALLTarget => {
                 Self::Internal(InternalResult::InvalidEXTCALLTarget)
             }
@@ -393,6 +417,7 @@ mod tests {
             InstructionResult::OutOfGas,
             InstructionResult::MemoryOOG,
             InstructionResult::MemoryLimitOOG,
+            InstructionResult::CumulativeMemoryLimitOOG,
             InstructionResult::PrecompileOOG,
             InstructionResult::InvalidOperandOOG,
             InstructionResult::OpcodeNotFound,
@@ -412,6 +437,9 @@ mod tests {
             InstructionResult::CreateContractStartingWithEF,
             InstructionResult::CreateInitCodeSizeLimit,
             InstructionResult::FatalExternalError,
+            InstructionResult::SelfDestructDisabled,
+            InstructionResult::PrecompileDisabled,
+            InstructionResult::PrecompileNotImplemented,
         ];
 
         for result in error_results {
@@ -9,7 +9,7 @@ use crate::{
         eof::EofHeader, keccak256, Address, BerlinSpec, Bytes, Eof, Spec, SpecId::*, B256, U256,
     },
     CallInputs, CallScheme, CallValue, CreateInputs, CreateScheme, EOFCreateInputs, Host,
-    InstructionResult, InterpreterAction, InterpreterResult, LoadAccountResult, MAX_INITCODE_SIZE,
+    InstructionResult, InterpreterAction, InterpreterResult,
 };
 use core::cmp::max;
 use std::boxed::Box;
@@ -136,11 +136,7 @@ pub fn return_contract<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &
     let result = InstructionResult::ReturnContract;
     interpreter.instruction_result = result;
     interpreter.next_action = crate::InterpreterAction::Return {
-        result: InterpreterResult {
-            output,
-            gas: interpreter.gas,
-            result,
-        },
+        result: InterpreterResult::new(result, output, interpreter.gas),
     };
 }
 
@@ -176,7 +172,7 @@ pub fn extcall_gas_calc<H: Host + ?Sized>(
         transfers_value,
         load_result.is_cold,
         load_result.is_empty,
-    );
+    ) + gas::delegation_cost(load_result.is_delegate_account_cold);
     gas!(interpreter, call_cost, None);
 
     // 7. Calculate the gas available to callee as caller’s
@@ -348,14 +344,7 @@ pub fn create<const IS_CREATE2: bool, H: Host + ?Sized, SPEC: Spec>(
     if len != 0 {
         // EIP-3860: Limit and meter initcode
         if SPEC::enabled(SHANGHAI) {
-            // Limit is set as double of max contract bytecode size
-            let max_initcode_size = host
-                .env()
-                .cfg
-                .limit_contract_code_size
-                .map(|limit| limit.saturating_mul(2))
-                .unwrap_or(MAX_INITCODE_SIZE);
-            if len > max_initcode_size {
+            if len > host.env().cfg.max_initcode_size() {
                 interpreter.instruction_result = InstructionResult::CreateInitCodeSizeLimit;
                 return;
             }
@@ -417,13 +406,14 @@ pub fn call<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &
         return;
     };
 
-    let Some(LoadAccountResult { is_cold, is_empty }) = host.load_account(to) else {
+    let Some(account_load) = host.load_account(to) else {
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
     };
+    let is_empty = account_load.is_empty;
     let Some(mut gas_limit) = calc_call_gas::<SPEC>(
         interpreter,
-        is_cold,
+        account_load,
         has_transfer,
         is_empty,
         local_gas_limit,
@@ -467,14 +457,14 @@ pub fn call_code<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, ho
         return;
     };
 
-    let Some(LoadAccountResult { is_cold, .. }) = host.load_account(to) else {
+    let Some(account_load) = host.load_account(to) else {
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
     };
 
     let Some(mut gas_limit) = calc_call_gas::<SPEC>(
         interpreter,
-        is_cold,
+        account_load,
         !value.is_zero(),
         false,
         local_gas_limit,
@@ -518,12 +508,12 @@ pub fn delegate_call<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter
         return;
     };
 
-    let Some(LoadAccountResult { is_cold, .. }) = host.load_account(to) else {
+    let Some(account_load) = host.load_account(to) else {
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
     };
     let Some(gas_limit) =
-        calc_call_gas::<SPEC>(interpreter, is_cold, false, false, local_gas_limit)
+        calc_call_gas::<SPEC>(interpreter, account_load, false, false, local_gas_limit)
     else {
         return;
     };
@@ -559,13 +549,13 @@ pub fn static_call<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter,
         return;
     };
 
-    let Some(LoadAccountResult { is_cold, .. }) = host.load_account(to) else {
+    let Some(account_load) = host.load_account(to) else {
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
     };
 
     let Some(gas_limit) =
-        calc_call_gas::<SPEC>(interpreter, is_cold, false, false, local_gas_limit)
+        calc_call_gas::<SPEC>(interpreter, account_load, false, false, local_gas_limit)
     else {
         return;
     };
@@ -169,11 +169,7 @@ fn return_inner(interpreter: &mut Interpreter, instruction_result: InstructionRe
     }
     interpreter.instruction_result = instruction_result;
     interpreter.next_action = crate::InterpreterAction::Return {
-        result: InterpreterResult {
-            output,
-            gas: interpreter.gas,
-            result: instruction_result,
-        },
+        result: InterpreterResult::new(instruction_result, output, interpreter.gas),
     };
 }
 
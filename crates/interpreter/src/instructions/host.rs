@@ -42,7 +42,7 @@ pub fn selfbalance<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter,
 
 pub fn extcodesize<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
     pop_address!(interpreter, address);
-    let Some((code, is_cold)) = host.code(address) else {
+    let Some((code_size, is_cold)) = host.code_size(address) else {
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
     };
@@ -54,7 +54,7 @@ pub fn extcodesize<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter,
         gas!(interpreter, 20);
     }
 
-    push!(interpreter, U256::from(code.len()));
+    push!(interpreter, U256::from(code_size));
 }
 
 /// EIP-1052: EXTCODEHASH opcode
@@ -206,6 +206,10 @@ pub fn log<const N: usize, H: Host + ?Sized>(interpreter: &mut Interpreter, host
 
 pub fn selfdestruct<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
     require_non_staticcall!(interpreter);
+    if host.env().cfg.is_selfdestruct_disabled() {
+        interpreter.instruction_result = InstructionResult::SelfDestructDisabled;
+        return;
+    }
     pop_address!(interpreter, target);
 
     let Some(res) = host.selfdestruct(interpreter.contract.target_address, target) else {
@@ -221,3 +225,31 @@ pub fn selfdestruct<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter,
 
     interpreter.instruction_result = InstructionResult::SelfDestruct;
 }
+
+#[cfg(all(test, feature = "optional_selfdestruct"))]
+mod tests {
+    use crate::{
+        opcode::{make_instruction_table, PUSH0, SELFDESTRUCT},
+        primitives::{Bytecode, Bytes, CancunSpec},
+        DummyHost, Gas, InstructionResult, Interpreter,
+    };
+
+    #[test]
+    fn selfdestruct_disabled() {
+        let table = make_instruction_table::<_, CancunSpec>();
+        let mut host = DummyHost::default();
+        host.env.cfg.disable_selfdestruct = true;
+        let mut interp =
+            Interpreter::new_bytecode(Bytecode::LegacyRaw(Bytes::from([PUSH0, SELFDESTRUCT])));
+        interp.gas = Gas::new(10000);
+
+        interp.step(&table, &mut host);
+        interp.step(&table, &mut host);
+        assert_eq!(
+            interp.instruction_result,
+            InstructionResult::SelfDestructDisabled
+        );
+        // The target address is not popped.
+        assert_eq!(interp.stack.len(), 1);
+    }
+}
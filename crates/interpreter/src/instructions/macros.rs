@@ -93,11 +93,14 @@ macro_rules! resize_memory {
     ($interp:expr, $offset:expr, $len:expr, $ret:expr) => {
         let new_size = $offset.saturating_add($len);
         if new_size > $interp.shared_memory.len() {
-            #[cfg(feature = "memory_limit")]
             if $interp.shared_memory.limit_reached(new_size) {
                 $interp.instruction_result = $crate::InstructionResult::MemoryLimitOOG;
                 return $ret;
             }
+            if $interp.shared_memory.cumulative_limit_reached(new_size) {
+                $interp.instruction_result = $crate::InstructionResult::CumulativeMemoryLimitOOG;
+                return $ret;
+            }
 
             // Note: we can't use `Interpreter` directly here because of potential double-borrows.
             if !$crate::interpreter::resize_memory(
@@ -54,3 +54,76 @@ pub fn mcopy<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, _host:
     // copy memory in place
     interpreter.shared_memory.copy(dst, src, len);
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        opcode::{make_instruction_table, MCOPY},
+        primitives::{Bytecode, Bytes, CancunSpec, ShanghaiSpec},
+        DummyHost, Gas, InstructionResult, SharedMemory,
+    };
+    use std::vec::Vec;
+
+    fn mcopy_interpreter(dst: usize, src: usize, len: usize) -> Interpreter {
+        let mut interp = Interpreter::new_bytecode(Bytecode::LegacyRaw(Bytes::from([MCOPY])));
+        interp.gas = Gas::new(10000);
+        interp.shared_memory = SharedMemory::new();
+        interp.shared_memory.resize(64);
+        interp.shared_memory.set(0, &(0..64).collect::<Vec<u8>>());
+        interp.stack.push(U256::from(len)).unwrap();
+        interp.stack.push(U256::from(src)).unwrap();
+        interp.stack.push(U256::from(dst)).unwrap();
+        interp
+    }
+
+    #[test]
+    fn mcopy_overlapping() {
+        let table = make_instruction_table::<_, CancunSpec>();
+        let mut host = DummyHost::default();
+
+        let mut interp = mcopy_interpreter(1, 0, 33);
+        interp.step(&table, &mut host);
+        assert_eq!(interp.instruction_result, InstructionResult::Continue);
+        // Copy behaves as if the source was copied to a buffer first.
+        assert_eq!(interp.shared_memory.slice(0, 2), [0, 0]);
+        assert_eq!(
+            interp.shared_memory.slice(1, 33),
+            (0..33).collect::<Vec<u8>>()
+        );
+        assert_eq!(
+            interp.shared_memory.slice(34, 30),
+            (34..64).collect::<Vec<u8>>()
+        );
+        // 3 for the instruction and 3 per copied word, memory is not expanded.
+        assert_eq!(interp.gas.spent(), 3 + 2 * 3);
+        assert_eq!(interp.shared_memory.len(), 64);
+    }
+
+    #[test]
+    fn mcopy_expands_memory() {
+        let table = make_instruction_table::<_, CancunSpec>();
+        let mut host = DummyHost::default();
+
+        let mut interp = mcopy_interpreter(64, 0, 32);
+        interp.step(&table, &mut host);
+        assert_eq!(interp.instruction_result, InstructionResult::Continue);
+        assert_eq!(interp.shared_memory.len(), 96);
+        assert_eq!(
+            interp.shared_memory.slice(64, 32),
+            (0..32).collect::<Vec<u8>>()
+        );
+        // Copy cost and the expansion from 2 to 3 words.
+        assert_eq!(interp.gas.spent(), 3 + 3 + 3);
+    }
+
+    #[test]
+    fn mcopy_not_activated() {
+        let table = make_instruction_table::<_, ShanghaiSpec>();
+        let mut host = DummyHost::default();
+
+        let mut interp = mcopy_interpreter(1, 0, 32);
+        interp.step(&table, &mut host);
+        assert_eq!(interp.instruction_result, InstructionResult::NotActivated);
+    }
+}
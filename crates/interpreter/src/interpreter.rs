@@ -11,10 +11,10 @@ pub use stack::{Stack, STACK_LIMIT};
 
 use crate::{
     gas, primitives::Bytes, push, push_b256, return_ok, return_revert, CallOutcome, CreateOutcome,
-    FunctionStack, Gas, Host, InstructionResult, InterpreterAction,
+    FrameGas, FunctionStack, Gas, Host, InstructionResult, InterpreterAction,
 };
 use core::cmp::min;
-use revm_primitives::{Bytecode, Eof, U256};
+use revm_primitives::{Bytecode, Eof, PrecompileError, U256};
 use std::borrow::ToOwned;
 use std::sync::Arc;
 
@@ -346,18 +346,56 @@ impl Interpreter {
     /// Executes the instruction at the current instruction pointer.
     ///
     /// Internally it will increment instruction pointer by one.
+    ///
+    /// Together with [`Interpreter::take_next_action`] this allows driving the execution one
+    /// instruction at a time, inspecting or modifying the stack and memory in between. Shared
+    /// memory must be set before the first step, see [`Interpreter::run`].
+    ///
+    /// Nothing is executed if the interpreter already stopped.
     #[inline]
-    pub(crate) fn step<FN, H: Host + ?Sized>(&mut self, instruction_table: &[FN; 256], host: &mut H)
+    pub fn step<FN, H: Host + ?Sized>(
+        &mut self,
+        instruction_table: &[FN; 256],
+        host: &mut H,
+    ) -> StepResult
     where
         FN: Fn(&mut Interpreter, &mut H),
+    {
+        if self.instruction_result == InstructionResult::Continue {
+            // SAFETY: execution did not stop, so the instruction pointer is within the bytecode.
+            unsafe { self.step_unchecked(instruction_table, host) };
+        }
+
+        if self.instruction_result == InstructionResult::Continue {
+            StepResult::Continue
+        } else {
+            StepResult::Stopped(self.instruction_result)
+        }
+    }
+
+    /// Executes the instruction at the current instruction pointer.
+    ///
+    /// Internally it will increment instruction pointer by one.
+    ///
+    /// # Safety
+    ///
+    /// The instruction pointer must point into the bytecode, which is the case as long as the
+    /// instruction result is [`InstructionResult::Continue`].
+    #[inline]
+    unsafe fn step_unchecked<FN, H: Host + ?Sized>(
+        &mut self,
+        instruction_table: &[FN; 256],
+        host: &mut H,
+    ) where
+        FN: Fn(&mut Interpreter, &mut H),
     {
         // Get current opcode.
-        let opcode = unsafe { *self.instruction_pointer };
+        let opcode = *self.instruction_pointer;
 
         // SAFETY: In analysis we are doing padding of bytecode so that we are sure that last
         // byte instruction is STOP so we are safe to just increment program_counter bcs on last instruction
         // it will do noop and just stop execution of this contract
-        self.instruction_pointer = unsafe { self.instruction_pointer.offset(1) };
+        self.instruction_pointer = self.instruction_pointer.offset(1);
 
         // execute instruction.
         (instruction_table[opcode as usize])(self, host)
@@ -369,6 +407,9 @@ impl Interpreter {
     }
 
     /// Executes the interpreter until it returns or stops.
+    ///
+    /// Execution continues from the current instruction pointer, so an interpreter that was
+    /// stepped with [`Interpreter::step`] is resumed by passing back its [`Interpreter::take_memory`].
     pub fn run<FN, H: Host + ?Sized>(
         &mut self,
         shared_memory: SharedMemory,
@@ -382,21 +423,24 @@ impl Interpreter {
         self.shared_memory = shared_memory;
         // main loop
         while self.instruction_result == InstructionResult::Continue {
-            self.step(instruction_table, host);
+            // SAFETY: execution did not stop.
+            unsafe { self.step_unchecked(instruction_table, host) };
         }
+        self.take_next_action()
+    }
 
+    /// Returns the action the EVM should do after the interpreter stopped.
+    ///
+    /// This is the call, create or return action set by the last instruction, or the return
+    /// action without output if the execution halted.
+    pub fn take_next_action(&mut self) -> InterpreterAction {
         // Return next action if it is some.
         if self.next_action.is_some() {
             return core::mem::take(&mut self.next_action);
         }
         // If not, return action without output as it is a halt.
         InterpreterAction::Return {
-            result: InterpreterResult {
-                result: self.instruction_result,
-                // return empty bytecode
-                output: Bytes::new(),
-                gas: self.gas,
-            },
+            result: InterpreterResult::new(self.instruction_result, Bytes::new(), self.gas),
         }
     }
 
@@ -408,6 +452,24 @@ impl Interpreter {
     }
 }
 
+/// The result of [`Interpreter::step`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StepResult {
+    /// Instruction was executed and the interpreter can take the next step.
+    Continue,
+    /// Interpreter stopped with the given result, the action is returned by
+    /// [`Interpreter::take_next_action`].
+    Stopped(InstructionResult),
+}
+
+impl StepResult {
+    /// Returns whether the interpreter can take the next step.
+    #[inline]
+    pub const fn is_continue(&self) -> bool {
+        matches!(self, Self::Continue)
+    }
+}
+
 /// The result of an interpreter operation.
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
@@ -418,6 +480,9 @@ pub struct InterpreterResult {
     pub output: Bytes,
     /// The gas usage information.
     pub gas: Gas,
+    /// The error returned by the precompile if the result is
+    /// [InstructionResult::PrecompileError].
+    pub precompile_error: Option<PrecompileError>,
 }
 
 impl InterpreterResult {
@@ -427,6 +492,7 @@ impl InterpreterResult {
             result,
             output,
             gas,
+            precompile_error: None,
         }
     }
 
@@ -447,6 +513,12 @@ impl InterpreterResult {
     pub const fn is_error(&self) -> bool {
         self.result.is_error()
     }
+
+    /// Returns the gas breakdown of the frame as seen by the parent.
+    #[inline]
+    pub const fn frame_gas(&self) -> FrameGas {
+        FrameGas::new(&self.gas, self.result)
+    }
 }
 
 /// Resize the memory to the new size. Returns whether the gas was enough to resize the memory.
@@ -468,7 +540,10 @@ pub fn resize_memory(memory: &mut SharedMemory, gas: &mut Gas, new_size: usize)
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{opcode::InstructionTable, DummyHost};
+    use crate::{
+        opcode::{self, InstructionTable},
+        DummyHost,
+    };
     use revm_primitives::CancunSpec;
 
     #[test]
@@ -485,4 +560,43 @@ mod tests {
             &crate::opcode::make_instruction_table::<dyn Host, CancunSpec>();
         let _ = interp.run(EMPTY_SHARED_MEMORY, table, host);
     }
+
+    #[test]
+    fn step_and_resume() {
+        let mut interp = Interpreter::new_bytecode(Bytecode::LegacyRaw(Bytes::from([
+            opcode::PUSH1,
+            0x01,
+            opcode::PUSH1,
+            0x02,
+            opcode::ADD,
+            opcode::STOP,
+        ])));
+        interp.gas = Gas::new(100);
+        interp.shared_memory = SharedMemory::new();
+        let table = crate::opcode::make_instruction_table::<DummyHost, CancunSpec>();
+        let mut host = DummyHost::default();
+
+        assert_eq!(interp.step(&table, &mut host), StepResult::Continue);
+        assert_eq!(interp.program_counter(), 2);
+        assert_eq!(interp.stack.data(), &[U256::from(1)]);
+
+        // Stack can be modified between the steps.
+        interp.stack.data_mut()[0] = U256::from(40);
+        assert!(interp.step(&table, &mut host).is_continue());
+
+        // Resumes after the second push.
+        let memory = interp.take_memory();
+        let action = interp.run(memory, &table, &mut host);
+        assert_eq!(interp.stack.data(), &[U256::from(42)]);
+        // Stopped interpreter does not execute past the end of the bytecode.
+        assert_eq!(
+            interp.step(&table, &mut host),
+            StepResult::Stopped(InstructionResult::Stop)
+        );
+        assert_eq!(interp.program_counter(), 6);
+        assert!(matches!(
+            action,
+            InterpreterAction::Return { result } if result.result == InstructionResult::Stop
+        ));
+    }
 }
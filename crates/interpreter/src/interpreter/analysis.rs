@@ -21,16 +21,18 @@ use std::{borrow::Cow, fmt, sync::Arc, vec, vec::Vec};
 /// If the bytecode is already analyzed, it is returned as-is.
 #[inline]
 pub fn to_analysed(bytecode: Bytecode) -> Bytecode {
-    let (bytes, len) = match bytecode {
-        Bytecode::LegacyRaw(bytecode) => {
-            let len = bytecode.len();
-            let mut padded_bytecode = Vec::with_capacity(len + 33);
-            padded_bytecode.extend_from_slice(&bytecode);
-            padded_bytecode.resize(len + 33, 0);
-            (Bytes::from(padded_bytecode), len)
-        }
+    let bytecode = match bytecode {
+        Bytecode::LegacyRaw(bytecode) => bytecode,
+        // Delegation designator is not followed when executed, its `0xEF` byte halts the
+        // execution as an invalid opcode.
+        Bytecode::Eip7702(eip7702) => eip7702.raw,
         n => return n,
     };
+    let len = bytecode.len();
+    let mut padded_bytecode = Vec::with_capacity(len + 33);
+    padded_bytecode.extend_from_slice(&bytecode);
+    padded_bytecode.resize(len + 33, 0);
+    let bytes = Bytes::from(padded_bytecode);
     let jump_table = analyze(bytes.as_ref());
 
     Bytecode::LegacyAnalyzed(LegacyAnalyzedBytecode::new(bytes, len, jump_table))
@@ -942,4 +944,26 @@ mod test {
             ))
         );
     }
+
+    #[test]
+    fn jump_table_is_bit_per_byte() {
+        // JUMPDEST, PUSH2 with JUMPDEST in the immediate, JUMPDEST.
+        let code = [
+            opcode::JUMPDEST,
+            opcode::PUSH2,
+            opcode::JUMPDEST,
+            opcode::JUMPDEST,
+            opcode::JUMPDEST,
+        ];
+        let jump_table = analyze(&code);
+        assert!(jump_table.is_valid(0));
+        assert!(!jump_table.is_valid(2));
+        assert!(!jump_table.is_valid(3));
+        assert!(jump_table.is_valid(4));
+        assert!(!jump_table.is_valid(5));
+
+        let bytecode = to_analysed(Bytecode::new_raw(Bytes::from(vec![opcode::STOP; 1000])));
+        let jump_table = bytecode.legacy_jump_table().unwrap();
+        assert_eq!(jump_table.as_slice().len(), (1000 + 33usize).div_ceil(8));
+    }
 }
@@ -17,8 +17,11 @@ pub struct SharedMemory {
     /// Invariant: equals `self.checkpoints.last()`
     last_checkpoint: usize,
     /// Memory limit. See [`CfgEnv`](revm_primitives::CfgEnv).
-    #[cfg(feature = "memory_limit")]
     memory_limit: u64,
+    /// Limit of memory expanded by all contexts combined. See [`CfgEnv`](revm_primitives::CfgEnv).
+    cumulative_memory_limit: u64,
+    /// Memory expanded by all contexts so far, including the contexts that were freed.
+    cumulative_memory: u64,
 }
 
 /// Empty shared memory.
@@ -28,8 +31,9 @@ pub const EMPTY_SHARED_MEMORY: SharedMemory = SharedMemory {
     buffer: Vec::new(),
     checkpoints: Vec::new(),
     last_checkpoint: 0,
-    #[cfg(feature = "memory_limit")]
     memory_limit: u64::MAX,
+    cumulative_memory_limit: u64::MAX,
+    cumulative_memory: 0,
 };
 
 impl fmt::Debug for SharedMemory {
@@ -67,8 +71,9 @@ impl SharedMemory {
             buffer: Vec::with_capacity(capacity),
             checkpoints: Vec::with_capacity(32),
             last_checkpoint: 0,
-            #[cfg(feature = "memory_limit")]
             memory_limit: u64::MAX,
+            cumulative_memory_limit: u64::MAX,
+            cumulative_memory: 0,
         }
     }
 
@@ -76,7 +81,6 @@ impl SharedMemory {
     /// with `memory_limit` as upper bound for allocation size.
     ///
     /// The default initial capacity is 4KiB.
-    #[cfg(feature = "memory_limit")]
     #[inline]
     pub fn new_with_memory_limit(memory_limit: u64) -> Self {
         Self {
@@ -85,14 +89,36 @@ impl SharedMemory {
         }
     }
 
+    /// Sets the upper bound of memory expanded by all contexts combined.
+    ///
+    /// Memory of freed contexts is still counted towards this limit.
+    #[inline]
+    pub fn with_cumulative_memory_limit(mut self, cumulative_memory_limit: u64) -> Self {
+        self.cumulative_memory_limit = cumulative_memory_limit;
+        self
+    }
+
     /// Returns `true` if the `new_size` for the current context memory will
     /// make the shared buffer length exceed the `memory_limit`.
-    #[cfg(feature = "memory_limit")]
     #[inline]
     pub fn limit_reached(&self, new_size: usize) -> bool {
         self.last_checkpoint.saturating_add(new_size) as u64 > self.memory_limit
     }
 
+    /// Returns `true` if resizing the current context memory to `new_size` will
+    /// make the memory expanded by all contexts exceed the `cumulative_memory_limit`.
+    #[inline]
+    pub fn cumulative_limit_reached(&self, new_size: usize) -> bool {
+        let expansion = new_size.saturating_sub(self.len()) as u64;
+        self.cumulative_memory.saturating_add(expansion) > self.cumulative_memory_limit
+    }
+
+    /// Returns the memory expanded by all contexts so far.
+    #[inline]
+    pub fn cumulative_memory(&self) -> u64 {
+        self.cumulative_memory
+    }
+
     /// Prepares the shared memory for a new context.
     #[inline]
     pub fn new_context(&mut self) {
@@ -132,6 +158,7 @@ impl SharedMemory {
     /// Resizes the memory in-place so that `len` is equal to `new_len`.
     #[inline]
     pub fn resize(&mut self, new_size: usize) {
+        self.cumulative_memory += new_size.saturating_sub(self.len()) as u64;
         self.buffer.resize(self.last_checkpoint + new_size, 0);
     }
 
@@ -404,4 +431,22 @@ mod tests {
         assert_eq!(shared_memory.len(), 64);
         assert_eq!(shared_memory.buffer.get(0..64), Some(&[0_u8; 64] as &[u8]));
     }
+
+    #[test]
+    fn cumulative_memory_limit() {
+        let mut shared_memory = SharedMemory::new().with_cumulative_memory_limit(128);
+        shared_memory.new_context();
+        shared_memory.resize(64);
+
+        shared_memory.new_context();
+        shared_memory.resize(64);
+        shared_memory.free_context();
+        assert_eq!(shared_memory.cumulative_memory(), 128);
+
+        // memory of the freed context is still counted.
+        shared_memory.new_context();
+        assert!(!shared_memory.cumulative_limit_reached(0));
+        assert!(shared_memory.cumulative_limit_reached(32));
+        assert!(!shared_memory.limit_reached(32));
+    }
 }
@@ -444,4 +444,27 @@ mod tests {
             assert_eq!(stack.data, [U256::ZERO, U256::ZERO, U256::from(n)]);
         });
     }
+
+    #[test]
+    fn push_pop_bounds() {
+        let mut stack = Stack::new();
+        assert_eq!(stack.pop(), Err(InstructionResult::StackUnderflow));
+        for i in 0..STACK_LIMIT {
+            stack.push(U256::from(i)).unwrap();
+        }
+        assert_eq!(
+            stack.push(U256::ZERO),
+            Err(InstructionResult::StackOverflow)
+        );
+        assert_eq!(stack.dup(1), Err(InstructionResult::StackOverflow));
+        assert_eq!(stack.len(), STACK_LIMIT);
+        // Stack never reallocates.
+        assert_eq!(stack.data().capacity(), STACK_LIMIT);
+
+        assert_eq!(stack.pop(), Ok(U256::from(STACK_LIMIT - 1)));
+        assert_eq!(stack.data().len(), STACK_LIMIT - 1);
+        assert_eq!(stack.data()[..2], [U256::ZERO, U256::from(1)]);
+        stack.push(U256::MAX).unwrap();
+        assert_eq!(stack.peek(0), Ok(U256::MAX));
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{Gas, InstructionResult, InterpreterResult};
+use crate::{gas::FrameGas, Gas, InstructionResult, InterpreterResult};
 use core::ops::Range;
 use revm_primitives::Bytes;
 
@@ -56,6 +56,12 @@ impl CallOutcome {
         self.result.gas
     }
 
+    /// Returns the gas breakdown of the frame: limit, spent, refunded and gas returned to
+    /// the parent.
+    pub fn frame_gas(&self) -> FrameGas {
+        self.result.frame_gas()
+    }
+
     /// Returns a reference to the output data.
     ///
     /// Provides access to the output data generated by the executed instruction.
@@ -1,4 +1,4 @@
-use crate::{Gas, InstructionResult, InterpreterResult};
+use crate::{gas::FrameGas, Gas, InstructionResult, InterpreterResult};
 use revm_primitives::{Address, Bytes};
 
 /// Represents the outcome of a create operation in an interpreter.
@@ -67,4 +67,10 @@ impl CreateOutcome {
     pub fn gas(&self) -> &Gas {
         &self.result.gas
     }
+
+    /// Returns the gas breakdown of the frame: limit, spent, refunded and gas returned to
+    /// the parent.
+    pub fn frame_gas(&self) -> FrameGas {
+        self.result.frame_gas()
+    }
 }
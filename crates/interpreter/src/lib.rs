@@ -28,11 +28,11 @@ pub mod opcode;
 
 // Reexport primary types.
 pub use function_stack::{FunctionReturnFrame, FunctionStack};
-pub use gas::Gas;
+pub use gas::{FrameGas, Gas};
 pub use host::{DummyHost, Host, LoadAccountResult, SStoreResult, SelfDestructResult};
 pub use instruction_result::*;
 pub use interpreter::{
-    analysis, num_words, Contract, Interpreter, InterpreterResult, SharedMemory, Stack,
+    analysis, num_words, Contract, Interpreter, InterpreterResult, SharedMemory, Stack, StepResult,
     EMPTY_SHARED_MEMORY, STACK_LIMIT,
 };
 pub use interpreter_action::{
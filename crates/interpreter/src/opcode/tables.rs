@@ -1,7 +1,7 @@
 #![allow(clippy::wrong_self_convention)]
 
-use super::instruction;
-use crate::{instructions::control, primitives::Spec, Host, Interpreter};
+use super::{instruction, OpCode};
+use crate::{gas, instructions::control, primitives::Spec, Host, Interpreter};
 use std::boxed::Box;
 
 /// EVM opcode function signature.
@@ -103,6 +103,30 @@ impl<'a, H: Host + ?Sized + 'a> InstructionTables<'a, H> {
         core::mem::replace(self.get_boxed(opcode), instruction)
     }
 
+    /// Inserts the instruction of the unassigned `opcode`, e.g. an experimental L2 opcode, that
+    /// charges `static_gas` before the instruction is executed.
+    ///
+    /// The table is converted to the boxed variant. Custom opcodes are only reachable from legacy
+    /// bytecode, as EOF validation rejects unassigned opcodes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the opcode is assigned, see [`OpCode::new`].
+    #[inline]
+    pub fn insert_custom(&mut self, opcode: u8, static_gas: u64, instruction: Instruction<H>) {
+        assert!(
+            OpCode::new(opcode).is_none(),
+            "opcode 0x{opcode:02X} is already assigned"
+        );
+        self.insert_boxed(
+            opcode,
+            Box::new(move |interpreter: &mut Interpreter, host: &mut H| {
+                gas!(interpreter, static_gas);
+                instruction(interpreter, host)
+            }),
+        );
+    }
+
     /// Updates a single instruction in the table at the specified index with `f`.
     #[inline]
     pub fn update_boxed<F>(&mut self, opcode: u8, f: F)
@@ -5,6 +5,7 @@ use revm_precompile::{
         pair::{ISTANBUL_PAIR_BASE, ISTANBUL_PAIR_PER_POINT},
         run_add, run_pair,
     },
+    hash::sha256_run,
     kzg_point_evaluation::run,
     secp256k1::ec_recover_run,
     Bytes,
@@ -143,6 +144,21 @@ pub fn benchmark_crypto_precompiles(c: &mut Criterion) {
             black_box(())
         })
     });
+
+    // === SHA256 ===
+
+    for size in [32, 1024, 16 * 1024] {
+        let sha256_input = Bytes::from(vec![0x5a; size]);
+        group.bench_function(
+            group_name(&format!("sha256 precompile {size} bytes")),
+            |b| {
+                b.iter(|| {
+                    sha256_run(&sha256_input, u64::MAX).unwrap();
+                    black_box(())
+                })
+            },
+        );
+    }
 }
 
 criterion_group! {
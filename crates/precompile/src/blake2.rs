@@ -1,4 +1,4 @@
-use crate::{Error, Precompile, PrecompileResult, PrecompileWithAddress};
+use crate::{utilities::require_len, Error, Precompile, PrecompileResult, PrecompileWithAddress};
 use revm_primitives::{Bytes, PrecompileOutput};
 
 const F_ROUND: u64 = 1;
@@ -11,16 +11,15 @@ pub const FUN: PrecompileWithAddress =
 /// input format:
 /// [4 bytes for rounds][64 bytes for h][128 bytes for m][8 bytes for t_0][8 bytes for t_1][1 byte for f]
 pub fn run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
-    let input = &input[..];
-
-    if input.len() != INPUT_LENGTH {
-        return Err(Error::Blake2WrongLength.into());
-    }
+    let input = require_len::<INPUT_LENGTH>(input, |actual| Error::Blake2WrongLength {
+        expected: INPUT_LENGTH,
+        actual,
+    })?;
 
     let f = match input[212] {
         1 => true,
         0 => false,
-        _ => return Err(Error::Blake2WrongFinalIndicatorFlag.into()),
+        flag => return Err(Error::Blake2WrongFinalIndicatorFlag { flag }.into()),
     };
 
     // rounds 4 bytes
@@ -95,6 +94,21 @@ pub mod algo {
         v[b] = (v[b] ^ v[c]).rotate_right(63);
     }
 
+    /// Compression function F.
+    ///
+    /// Uses AVX2 if the `blake2-avx2` feature is enabled and the CPU supports it, otherwise
+    /// [compress_portable].
+    #[inline]
+    pub fn compress(rounds: usize, h: &mut [u64; 8], m: [u64; 16], t: [u64; 2], f: bool) {
+        #[cfg(all(feature = "blake2-avx2", target_arch = "x86_64"))]
+        if std::is_x86_feature_detected!("avx2") {
+            // SAFETY: AVX2 is supported by the CPU.
+            unsafe { avx2::compress(rounds, h, &m, t, f) };
+            return;
+        }
+        compress_portable(rounds, h, m, t, f)
+    }
+
     // Compression function F takes as an argument the state vector "h",
     // message block vector "m" (last block is padded with zeros to full
     // block size, if required), 2w-bit offset counter "t", and final block
@@ -102,7 +116,7 @@ pub mod algo {
     // returns a new state vector.  The number of rounds, "r", is 12 for
     // BLAKE2b and 10 for BLAKE2s.  Rounds are numbered from 0 to r - 1.
     #[allow(clippy::many_single_char_names)]
-    pub fn compress(rounds: usize, h: &mut [u64; 8], m: [u64; 16], t: [u64; 2], f: bool) {
+    pub fn compress_portable(rounds: usize, h: &mut [u64; 8], m: [u64; 16], t: [u64; 2], f: bool) {
         let mut v = [0u64; 16];
         v[..h.len()].copy_from_slice(h); // First half from state.
         v[h.len()..].copy_from_slice(&IV); // Second half from IV.
@@ -131,4 +145,202 @@ pub mod algo {
             h[i] ^= v[i] ^ v[i + 8];
         }
     }
+
+    /// Compression function F with the rows of the local vector in AVX2 registers.
+    ///
+    /// The four G functions of a step are computed at once, one per 64-bit lane. Rows are
+    /// rotated between the column and the diagonal steps so diagonals line up in lanes.
+    #[cfg(all(feature = "blake2-avx2", target_arch = "x86_64"))]
+    mod avx2 {
+        use super::{IV, SIGMA};
+        use core::arch::x86_64::*;
+
+        #[inline(always)]
+        unsafe fn load(src: &[u64]) -> __m256i {
+            debug_assert!(src.len() >= 4);
+            _mm256_loadu_si256(src.as_ptr().cast())
+        }
+
+        #[inline(always)]
+        unsafe fn store(dst: &mut [u64], value: __m256i) {
+            debug_assert!(dst.len() >= 4);
+            _mm256_storeu_si256(dst.as_mut_ptr().cast(), value)
+        }
+
+        /// Loads the message words of the lanes.
+        #[inline(always)]
+        unsafe fn words(m: &[u64; 16], i0: usize, i1: usize, i2: usize, i3: usize) -> __m256i {
+            _mm256_set_epi64x(m[i3] as i64, m[i2] as i64, m[i1] as i64, m[i0] as i64)
+        }
+
+        #[inline(always)]
+        unsafe fn rotate_right_32(x: __m256i) -> __m256i {
+            _mm256_shuffle_epi32(x, 0b10_11_00_01)
+        }
+
+        #[inline(always)]
+        unsafe fn rotate_right_24(x: __m256i) -> __m256i {
+            _mm256_shuffle_epi8(
+                x,
+                _mm256_setr_epi8(
+                    3, 4, 5, 6, 7, 0, 1, 2, 11, 12, 13, 14, 15, 8, 9, 10, 3, 4, 5, 6, 7, 0, 1, 2,
+                    11, 12, 13, 14, 15, 8, 9, 10,
+                ),
+            )
+        }
+
+        #[inline(always)]
+        unsafe fn rotate_right_16(x: __m256i) -> __m256i {
+            _mm256_shuffle_epi8(
+                x,
+                _mm256_setr_epi8(
+                    2, 3, 4, 5, 6, 7, 0, 1, 10, 11, 12, 13, 14, 15, 8, 9, 2, 3, 4, 5, 6, 7, 0, 1,
+                    10, 11, 12, 13, 14, 15, 8, 9,
+                ),
+            )
+        }
+
+        #[inline(always)]
+        unsafe fn rotate_right_63(x: __m256i) -> __m256i {
+            _mm256_or_si256(_mm256_srli_epi64(x, 63), _mm256_add_epi64(x, x))
+        }
+
+        /// Four G functions at once.
+        #[inline(always)]
+        #[allow(clippy::many_single_char_names)]
+        unsafe fn g(
+            a: &mut __m256i,
+            b: &mut __m256i,
+            c: &mut __m256i,
+            d: &mut __m256i,
+            x: __m256i,
+            y: __m256i,
+        ) {
+            *a = _mm256_add_epi64(*a, _mm256_add_epi64(*b, x));
+            *d = rotate_right_32(_mm256_xor_si256(*d, *a));
+            *c = _mm256_add_epi64(*c, *d);
+            *b = rotate_right_24(_mm256_xor_si256(*b, *c));
+            *a = _mm256_add_epi64(*a, _mm256_add_epi64(*b, y));
+            *d = rotate_right_16(_mm256_xor_si256(*d, *a));
+            *c = _mm256_add_epi64(*c, *d);
+            *b = rotate_right_63(_mm256_xor_si256(*b, *c));
+        }
+
+        /// # Safety
+        ///
+        /// The CPU must support AVX2.
+        #[target_feature(enable = "avx2")]
+        #[allow(clippy::many_single_char_names)]
+        pub(super) unsafe fn compress(
+            rounds: usize,
+            h: &mut [u64; 8],
+            m: &[u64; 16],
+            t: [u64; 2],
+            f: bool,
+        ) {
+            let mut a = load(&h[..4]);
+            let mut b = load(&h[4..]);
+            let mut c = load(&IV[..4]);
+            let mut d = _mm256_xor_si256(
+                load(&IV[4..]),
+                _mm256_set_epi64x(0, -(f as i64), t[1] as i64, t[0] as i64),
+            );
+
+            for i in 0..rounds {
+                let s = &SIGMA[i % 10];
+                // Columns.
+                g(
+                    &mut a,
+                    &mut b,
+                    &mut c,
+                    &mut d,
+                    words(m, s[0], s[2], s[4], s[6]),
+                    words(m, s[1], s[3], s[5], s[7]),
+                );
+                // Line up the diagonals.
+                b = _mm256_permute4x64_epi64(b, 0b00_11_10_01);
+                c = _mm256_permute4x64_epi64(c, 0b01_00_11_10);
+                d = _mm256_permute4x64_epi64(d, 0b10_01_00_11);
+                // Diagonals.
+                g(
+                    &mut a,
+                    &mut b,
+                    &mut c,
+                    &mut d,
+                    words(m, s[8], s[10], s[12], s[14]),
+                    words(m, s[9], s[11], s[13], s[15]),
+                );
+                // Back to columns.
+                b = _mm256_permute4x64_epi64(b, 0b10_01_00_11);
+                c = _mm256_permute4x64_epi64(c, 0b01_00_11_10);
+                d = _mm256_permute4x64_epi64(d, 0b00_11_10_01);
+            }
+
+            let low = _mm256_xor_si256(load(&h[..4]), _mm256_xor_si256(a, c));
+            let high = _mm256_xor_si256(load(&h[4..]), _mm256_xor_si256(b, d));
+            store(&mut h[..4], low);
+            store(&mut h[4..], high);
+        }
+
+        #[cfg(test)]
+        mod tests {
+            use super::*;
+
+            #[test]
+            fn matches_portable() {
+                if !std::is_x86_feature_detected!("avx2") {
+                    return;
+                }
+                let mut seed = 0x0123_4567_89ab_cdefu64;
+                let mut next = || {
+                    seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+                    seed
+                };
+                for rounds in 0..25 {
+                    let mut h = [0; 8].map(|_: u64| next());
+                    let m = [0; 16].map(|_: u64| next());
+                    let t = [next(), next()];
+                    let f = rounds % 2 == 0;
+
+                    let mut expected = h;
+                    super::super::compress_portable(rounds, &mut expected, m, t, f);
+                    unsafe { compress(rounds, &mut h, &m, t, f) };
+                    assert_eq!(h, expected, "rounds: {rounds}");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm_primitives::hex_literal::hex;
+
+    #[test]
+    fn blake2b_abc() {
+        // EIP-152 test vector 5: BLAKE2b-512 of "abc" in 12 rounds.
+        let mut input = std::vec::Vec::with_capacity(INPUT_LENGTH);
+        input.extend_from_slice(&12u32.to_be_bytes());
+        input.extend_from_slice(&hex!(
+            "48c9bdf267e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5"
+            "d182e6ad7f520e511f6c3e2b8c68059b6bbd41fbabd9831f79217e1319cde05b"
+        ));
+        let mut m = [0u8; 128];
+        m[..3].copy_from_slice(b"abc");
+        input.extend_from_slice(&m);
+        input.extend_from_slice(&3u64.to_le_bytes());
+        input.extend_from_slice(&0u64.to_le_bytes());
+        input.push(1);
+
+        let output = run(&input.into(), 12).unwrap();
+        assert_eq!(output.gas_used, 12);
+        assert_eq!(
+            output.bytes[..],
+            hex!(
+                "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1"
+                "7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"
+            )
+        );
+    }
 }
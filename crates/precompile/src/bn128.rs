@@ -1,10 +1,10 @@
 use crate::{
-    utilities::{bool_to_bytes32, right_pad},
+    utilities::{bool_to_bytes32, require_multiple_of, right_pad},
     Address, Error, Precompile, PrecompileResult, PrecompileWithAddress,
 };
 use bn::{AffineG1, AffineG2, Fq, Fq2, Group, Gt, G1, G2};
-use revm_primitives::PrecompileOutput;
-use std::vec::Vec;
+use revm_primitives::{Bytes, Env, PrecompileOutput, StatefulPrecompile};
+use std::{sync::Arc, vec::Vec};
 
 pub mod add {
     use super::*;
@@ -74,6 +74,41 @@ pub mod pair {
             )
         }),
     );
+
+    /// Returns the Istanbul pairing precompile that accepts at most `max_pairs` pairs per call.
+    ///
+    /// Calls with more pairs fail with [`Error::Bn128PairLimitExceeded`] before any gas is
+    /// charged or any point is read.
+    pub fn with_max_pairs(max_pairs: usize) -> PrecompileWithAddress {
+        PrecompileWithAddress(
+            ADDRESS,
+            Precompile::Stateful(Arc::new(MaxPairsPrecompile { max_pairs })),
+        )
+    }
+
+    /// Istanbul pairing precompile with a limit on the number of pairs.
+    #[derive(Clone, Copy, Debug)]
+    struct MaxPairsPrecompile {
+        max_pairs: usize,
+    }
+
+    impl StatefulPrecompile for MaxPairsPrecompile {
+        fn call(&self, input: &Bytes, gas_limit: u64, _env: &Env) -> PrecompileResult {
+            if input.len() / PAIR_ELEMENT_LEN > self.max_pairs {
+                return Err(Error::Bn128PairLimitExceeded {
+                    pairs: input.len() / PAIR_ELEMENT_LEN,
+                    max: self.max_pairs,
+                }
+                .into());
+            }
+            run_pair(
+                input,
+                ISTANBUL_PAIR_PER_POINT,
+                ISTANBUL_PAIR_BASE,
+                gas_limit,
+            )
+        }
+    }
 }
 
 /// Input length for the add operation.
@@ -166,20 +201,18 @@ pub fn run_pair(
     pair_base_cost: u64,
     gas_limit: u64,
 ) -> PrecompileResult {
-    let gas_used = (input.len() / PAIR_ELEMENT_LEN) as u64 * pair_per_point_cost + pair_base_cost;
+    let elements = require_multiple_of(input, PAIR_ELEMENT_LEN, |len| Error::Bn128PairLength {
+        len,
+    })?;
+
+    let gas_used = elements as u64 * pair_per_point_cost + pair_base_cost;
     if gas_used > gas_limit {
         return Err(Error::OutOfGas.into());
     }
 
-    if input.len() % PAIR_ELEMENT_LEN != 0 {
-        return Err(Error::Bn128PairLength.into());
-    }
-
-    let success = if input.is_empty() {
+    let success = if elements == 0 {
         true
     } else {
-        let elements = input.len() / PAIR_ELEMENT_LEN;
-
         let mut points = Vec::with_capacity(elements);
 
         // read points
@@ -502,7 +535,48 @@ mod tests {
         );
         assert!(matches!(
             res,
-            Err(PrecompileErrors::Error(Error::Bn128PairLength))
+            Err(PrecompileErrors::Error(Error::Bn128PairLength { len: 79 }))
+        ));
+    }
+
+    #[test]
+    fn test_alt_bn128_pair_with_max_pairs() {
+        // two pairs
+        let input = Bytes::from(
+            hex::decode(
+                "\
+            1c76476f4def4bb94541d57ebba1193381ffa7aa76ada664dd31c16024c43f59\
+            3034dd2920f673e204fee2811c678745fc819b55d3e9d294e45c9b03a76aef41\
+            209dd15ebff5d46c4bd888e51a93cf99a7329636c63514396b4a452003a35bf7\
+            04bf11ca01483bfa8b34b43561848d28905960114c8ac04049af4b6315a41678\
+            2bb8324af6cfc93537a2ad1a445cfd0ca2a71acd7ac41fadbf933c2a51be344d\
+            120a2a4cf30c1bf9845f20c6fe39e07ea2cce61f0c9bb048165fe5e4de877550\
+            111e129f1cf1097710d41c4ac70fcdfa5ba2023c6ff1cbeac322de49d1b6df7c\
+            2032c61a830e3c17286de9462bf242fca2883585b93870a73853face6a6bf411\
+            198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2\
+            1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed\
+            090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b\
+            12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa",
+            )
+            .unwrap(),
+        );
+        let call = |max_pairs| {
+            let Precompile::Stateful(precompile) = pair::with_max_pairs(max_pairs).1 else {
+                panic!("pairing with max pairs is stateful");
+            };
+            precompile.call(&input, 260_000, &Env::default())
+        };
+
+        let outcome = call(2).unwrap();
+        assert_eq!(outcome.bytes, bool_to_bytes32(true));
+
+        let res = call(1);
+        assert!(matches!(
+            res,
+            Err(PrecompileErrors::Error(Error::Bn128PairLimitExceeded {
+                pairs: 2,
+                max: 1
+            }))
         ));
     }
 }
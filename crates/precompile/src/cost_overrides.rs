@@ -0,0 +1,173 @@
+//! Gas cost overrides of the standard precompiles.
+//!
+//! Alternative chains can reprice the standard precompiles by applying [PrecompileCostOverrides]
+//! to the precompiles of the spec, implementations are reused with the new costs.
+use crate::{
+    bn128, hash, identity, modexp,
+    primitives::{Env, U256},
+    u64_to_address, Address, Bytes, HashMap, Precompile, PrecompileResult, PrecompileWithAddress,
+    Precompiles, StatefulPrecompile,
+};
+use std::{sync::Arc, vec::Vec};
+
+/// Calculates the modexp gas cost from the base, exponent and modulus lengths and the first 32
+/// bytes of the exponent.
+pub type ModexpGasFn = fn(u64, u64, u64, &U256) -> u64;
+
+/// Gas cost of a standard precompile.
+#[derive(Clone, Copy, Debug)]
+pub enum PrecompileCost {
+    /// SHA256 base cost and cost per word.
+    Sha256 { base: u64, per_word: u64 },
+    /// RIPEMD160 base cost and cost per word.
+    Ripemd160 { base: u64, per_word: u64 },
+    /// Identity base cost and cost per word.
+    Identity { base: u64, per_word: u64 },
+    /// Modexp minimum gas and gas calculation, e.g. [modexp::berlin_gas_calc].
+    Modexp { min_gas: u64, gas_calc: ModexpGasFn },
+    /// Bn128 pairing base cost and cost per pair.
+    Bn128Pair { base: u64, per_pair: u64 },
+}
+
+impl PrecompileCost {
+    /// Returns the address of the repriced precompile.
+    pub const fn address(&self) -> Address {
+        match self {
+            Self::Sha256 { .. } => u64_to_address(2),
+            Self::Ripemd160 { .. } => u64_to_address(3),
+            Self::Identity { .. } => u64_to_address(4),
+            Self::Modexp { .. } => u64_to_address(5),
+            Self::Bn128Pair { .. } => bn128::pair::ADDRESS,
+        }
+    }
+
+    /// Runs the precompile with this cost.
+    pub fn run(&self, input: &Bytes, gas_limit: u64) -> PrecompileResult {
+        match *self {
+            Self::Sha256 { base, per_word } => {
+                hash::sha256_run_with_cost(input, gas_limit, base, per_word)
+            }
+            Self::Ripemd160 { base, per_word } => {
+                hash::ripemd160_run_with_cost(input, gas_limit, base, per_word)
+            }
+            Self::Identity { base, per_word } => {
+                identity::identity_run_with_cost(input, gas_limit, base, per_word)
+            }
+            Self::Modexp { min_gas, gas_calc } => {
+                modexp::run_inner(input, gas_limit, min_gas, gas_calc)
+            }
+            Self::Bn128Pair { base, per_pair } => bn128::run_pair(input, per_pair, base, gas_limit),
+        }
+    }
+}
+
+impl StatefulPrecompile for PrecompileCost {
+    fn call(&self, input: &Bytes, gas_limit: u64, _env: &Env) -> PrecompileResult {
+        self.run(input, gas_limit)
+    }
+}
+
+/// Gas cost overrides of the standard precompiles, keyed by precompile address.
+#[derive(Clone, Debug, Default)]
+pub struct PrecompileCostOverrides {
+    overrides: HashMap<Address, PrecompileCost>,
+}
+
+impl PrecompileCostOverrides {
+    /// Creates empty overrides.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the cost override, replacing the previous override of the same precompile.
+    pub fn with(mut self, cost: PrecompileCost) -> Self {
+        self.insert(cost);
+        self
+    }
+
+    /// Adds the cost override, returning the previous override of the same precompile.
+    pub fn insert(&mut self, cost: PrecompileCost) -> Option<PrecompileCost> {
+        self.overrides.insert(cost.address(), cost)
+    }
+
+    /// Returns the cost override of the precompile at `address`.
+    pub fn get(&self, address: &Address) -> Option<&PrecompileCost> {
+        self.overrides.get(address)
+    }
+
+    /// Returns the repriced precompiles.
+    pub fn precompiles(&self) -> impl Iterator<Item = PrecompileWithAddress> + '_ {
+        self.overrides.iter().map(|(address, cost)| {
+            PrecompileWithAddress(*address, Precompile::Stateful(Arc::new(*cost)))
+        })
+    }
+
+    /// Replaces the precompiles with the repriced ones.
+    ///
+    /// Overrides of precompiles that are not in `precompiles` are skipped, so precompiles are
+    /// not activated before their spec.
+    pub fn apply(&self, precompiles: &mut Precompiles) {
+        let repriced = self
+            .precompiles()
+            .filter(|precompile| precompiles.contains(precompile.address()))
+            .collect::<Vec<_>>();
+        precompiles.extend(repriced);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PrecompileSpecId;
+
+    #[test]
+    fn apply_cost_overrides() {
+        let overrides = PrecompileCostOverrides::new()
+            .with(PrecompileCost::Identity {
+                base: 100,
+                per_word: 0,
+            })
+            .with(PrecompileCost::Modexp {
+                min_gas: 500,
+                gas_calc: |_, _, _, _| 1_000,
+            })
+            .with(PrecompileCost::Bn128Pair {
+                base: 1,
+                per_pair: 1,
+            });
+
+        let mut precompiles = Precompiles::new(PrecompileSpecId::HOMESTEAD).clone();
+        overrides.apply(&mut precompiles);
+        // Modexp and pairing are not active in Homestead.
+        assert!(!precompiles.contains(&modexp::BERLIN.0));
+        assert!(!precompiles.contains(&bn128::pair::ADDRESS));
+
+        let mut precompiles = Precompiles::new(PrecompileSpecId::BERLIN).clone();
+        overrides.apply(&mut precompiles);
+        let gas_used = |address: Address, input: Bytes| {
+            let Some(Precompile::Stateful(precompile)) = precompiles.get(&address) else {
+                panic!("precompile is not repriced");
+            };
+            precompile
+                .call(&input, u64::MAX, &Env::default())
+                .unwrap()
+                .gas_used
+        };
+        assert_eq!(gas_used(identity::FUN.0, Bytes::from(vec![0; 64])), 100);
+        // Empty base and modulus are charged the minimum gas.
+        assert_eq!(gas_used(modexp::BERLIN.0, Bytes::new()), 500);
+        // Base, exponent and modulus of one byte each.
+        let mut input = vec![0; 99];
+        input[31] = 1;
+        input[63] = 1;
+        input[95] = 1;
+        input[96..].fill(2);
+        assert_eq!(gas_used(modexp::BERLIN.0, input.into()), 1_000);
+        assert_eq!(gas_used(bn128::pair::ADDRESS, Bytes::new()), 1);
+        // Other precompiles are untouched.
+        assert!(matches!(
+            precompiles.get(&hash::SHA256.0),
+            Some(Precompile::Standard(_))
+        ));
+    }
+}
@@ -0,0 +1,172 @@
+//! Fuzzing harness of the precompiles.
+//!
+//! [targets] returns every precompile of the spec with an uniform [FuzzTarget::run] signature,
+//! and [FuzzTarget::shape_input] turns arbitrary fuzzer bytes into input of the length the
+//! precompile expects, so the fuzzer does not spend its time on inputs that are rejected by the
+//! length checks.
+use crate::{
+    precompile_name, primitives::Env, u64_to_address, utilities::right_pad_vec, Address, Bytes,
+    Precompile, PrecompileResult, PrecompileSpecId, Precompiles,
+};
+use std::vec::Vec;
+
+/// Length of the input expected by the precompile.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InputShape {
+    /// Input of any length.
+    Any,
+    /// Input of the exact length.
+    Fixed(usize),
+    /// Non empty input with the length that is a multiple of the given length.
+    Multiple(usize),
+}
+
+/// Precompile that can be fuzzed.
+#[derive(Clone, Debug)]
+pub struct FuzzTarget {
+    /// Address of the precompile.
+    pub address: Address,
+    /// Name of the precompile.
+    pub name: &'static str,
+    /// The precompile.
+    pub precompile: Precompile,
+}
+
+impl FuzzTarget {
+    /// Runs the precompile with the default environment.
+    pub fn run(&self, input: &[u8], gas_limit: u64) -> PrecompileResult {
+        self.precompile
+            .call_ref(&Bytes::copy_from_slice(input), gas_limit, &Env::default())
+    }
+
+    /// Returns the input length expected by the precompile.
+    pub fn input_shape(&self) -> InputShape {
+        input_shape(&self.address)
+    }
+
+    /// Shapes arbitrary bytes into the input of the precompile.
+    ///
+    /// Input is padded or truncated to the expected length and the fields that are checked
+    /// before the computation, e.g. the ecrecover `v` or the modexp lengths, are made valid.
+    pub fn shape_input(&self, data: &[u8]) -> Bytes {
+        let mut input = match self.input_shape() {
+            InputShape::Any => data.to_vec(),
+            InputShape::Fixed(len) => right_pad_vec(data, len).into_owned(),
+            InputShape::Multiple(len) => {
+                let len = (data.len() / len).max(1) * len;
+                right_pad_vec(data, len).into_owned()
+            }
+        };
+        match address_index(&self.address) {
+            // ecrecover `v` is 27 or 28.
+            Some(1) => {
+                input[32..63].fill(0);
+                input[63] = 27 + (input[63] & 1);
+            }
+            // modexp lengths are limited so the computation stays fast.
+            Some(5) => input = shape_modexp_input(data),
+            // blake2 rounds are limited so the computation stays fast, final block indicator is
+            // 0 or 1.
+            Some(9) => {
+                input[..2].fill(0);
+                input[212] &= 1;
+            }
+            _ => {}
+        }
+        input.into()
+    }
+}
+
+/// Returns the precompiles of the spec as fuzz targets, ordered by address.
+pub fn targets(spec: PrecompileSpecId) -> Vec<FuzzTarget> {
+    let precompiles = Precompiles::new(spec);
+    let mut targets = precompiles
+        .addresses()
+        .map(|address| FuzzTarget {
+            address: *address,
+            name: precompile_name(address),
+            precompile: precompiles.get(address).unwrap().clone(),
+        })
+        .collect::<Vec<_>>();
+    targets.sort_by_key(|target| target.address);
+    targets
+}
+
+/// Returns the input length expected by the precompile at the address.
+pub fn input_shape(address: &Address) -> InputShape {
+    match address_index(address) {
+        Some(0x01) => InputShape::Fixed(128),
+        Some(0x06) => InputShape::Fixed(128),
+        Some(0x07) => InputShape::Fixed(96),
+        Some(0x08) => InputShape::Multiple(192),
+        Some(0x09) => InputShape::Fixed(213),
+        Some(0x0a) => InputShape::Fixed(192),
+        Some(0x0b) => InputShape::Fixed(256),
+        Some(0x0c) => InputShape::Fixed(160),
+        Some(0x0d) => InputShape::Multiple(160),
+        Some(0x0e) => InputShape::Fixed(512),
+        Some(0x0f) => InputShape::Fixed(288),
+        Some(0x10) => InputShape::Multiple(288),
+        Some(0x11) => InputShape::Multiple(384),
+        Some(0x12) => InputShape::Fixed(64),
+        Some(0x13) => InputShape::Fixed(128),
+        Some(0x100) => InputShape::Fixed(160),
+        _ => InputShape::Any,
+    }
+}
+
+/// Returns the index of the address if it is one of the standard precompile addresses.
+fn address_index(address: &Address) -> Option<u64> {
+    let index = u64::from_be_bytes(address[12..].try_into().unwrap());
+    (*address == u64_to_address(index)).then_some(index)
+}
+
+/// Builds the modexp input with the base, exponent and modulus of at most 64 bytes each.
+fn shape_modexp_input(data: &[u8]) -> Vec<u8> {
+    let lens = right_pad_vec(data, 3)
+        .iter()
+        .map(|len| (len % 65) as usize)
+        .collect::<Vec<_>>();
+    let data = data.get(3..).unwrap_or_default();
+    let body_len: usize = lens.iter().sum();
+
+    let mut input = Vec::with_capacity(96 + body_len);
+    for len in &lens {
+        input.extend_from_slice(&[0; 24]);
+        input.extend_from_slice(&(*len as u64).to_be_bytes());
+    }
+    input.extend_from_slice(&right_pad_vec(data, body_len));
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shaped_inputs_pass_length_checks() {
+        let data = [0xab; 300];
+        for target in targets(PrecompileSpecId::LATEST) {
+            assert_ne!(target.name, "unknown", "{}", target.address);
+            let input = target.shape_input(&data);
+            match target.input_shape() {
+                InputShape::Any => {}
+                InputShape::Fixed(len) => assert_eq!(input.len(), len, "{}", target.name),
+                InputShape::Multiple(len) => {
+                    assert!(
+                        !input.is_empty() && input.len() % len == 0,
+                        "{}",
+                        target.name
+                    )
+                }
+            }
+            // Must not panic.
+            let _ = target.run(&input, 30_000_000);
+        }
+
+        let ecrecover = &targets(PrecompileSpecId::HOMESTEAD)[0];
+        assert_eq!(ecrecover.name, "ecrecover");
+        assert_eq!(ecrecover.shape_input(&[]).len(), 128);
+        assert_eq!(ecrecover.shape_input(&[])[63], 27);
+    }
+}
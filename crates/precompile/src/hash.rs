@@ -11,11 +11,33 @@ pub const RIPEMD160: PrecompileWithAddress = PrecompileWithAddress(
     Precompile::Standard(ripemd160_run),
 );
 
+/// The base cost of SHA256.
+pub const SHA256_BASE: u64 = 60;
+/// The cost of SHA256 per word.
+pub const SHA256_PER_WORD: u64 = 12;
+/// The base cost of RIPEMD160.
+pub const RIPEMD160_BASE: u64 = 600;
+/// The cost of RIPEMD160 per word.
+pub const RIPEMD160_PER_WORD: u64 = 120;
+
+/// Hashing uses the SHA extensions of the CPU when they are detected at runtime, or the
+/// assembly implementation with the `asm-sha2` feature.
+///
 /// See: <https://ethereum.github.io/yellowpaper/paper.pdf>
 /// See: <https://docs.soliditylang.org/en/develop/units-and-global-variables.html#mathematical-and-cryptographic-functions>
 /// See: <https://etherscan.io/address/0000000000000000000000000000000000000002>
 pub fn sha256_run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
-    let cost = calc_linear_cost_u32(input.len(), 60, 12);
+    sha256_run_with_cost(input, gas_limit, SHA256_BASE, SHA256_PER_WORD)
+}
+
+/// Runs SHA256 with the given base cost and cost per word.
+pub fn sha256_run_with_cost(
+    input: &Bytes,
+    gas_limit: u64,
+    base: u64,
+    per_word: u64,
+) -> PrecompileResult {
+    let cost = calc_linear_cost_u32(input.len(), base, per_word);
     if cost > gas_limit {
         Err(Error::OutOfGas.into())
     } else {
@@ -28,7 +50,17 @@ pub fn sha256_run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
 /// See: <https://docs.soliditylang.org/en/develop/units-and-global-variables.html#mathematical-and-cryptographic-functions>
 /// See: <https://etherscan.io/address/0000000000000000000000000000000000000003>
 pub fn ripemd160_run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
-    let gas_used = calc_linear_cost_u32(input.len(), 600, 120);
+    ripemd160_run_with_cost(input, gas_limit, RIPEMD160_BASE, RIPEMD160_PER_WORD)
+}
+
+/// Runs RIPEMD160 with the given base cost and cost per word.
+pub fn ripemd160_run_with_cost(
+    input: &Bytes,
+    gas_limit: u64,
+    base: u64,
+    per_word: u64,
+) -> PrecompileResult {
+    let gas_used = calc_linear_cost_u32(input.len(), base, per_word);
     if gas_used > gas_limit {
         Err(Error::OutOfGas.into())
     } else {
@@ -10,14 +10,39 @@ pub const IDENTITY_BASE: u64 = 15;
 /// The cost per word.
 pub const IDENTITY_PER_WORD: u64 = 3;
 
-/// Takes the input bytes, copies them, and returns it as the output.
+/// Returns the input bytes as the output.
+///
+/// Output is a cheap clone of the input [Bytes], the data is not copied.
 ///
 /// See: <https://ethereum.github.io/yellowpaper/paper.pdf>
 /// See: <https://etherscan.io/address/0000000000000000000000000000000000000004>
 pub fn identity_run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
-    let gas_used = calc_linear_cost_u32(input.len(), IDENTITY_BASE, IDENTITY_PER_WORD);
+    identity_run_with_cost(input, gas_limit, IDENTITY_BASE, IDENTITY_PER_WORD)
+}
+
+/// Runs the identity precompile with the given base cost and cost per word.
+pub fn identity_run_with_cost(
+    input: &Bytes,
+    gas_limit: u64,
+    base: u64,
+    per_word: u64,
+) -> PrecompileResult {
+    let gas_used = calc_linear_cost_u32(input.len(), base, per_word);
     if gas_used > gas_limit {
         return Err(Error::OutOfGas.into());
     }
     Ok(PrecompileOutput::new(gas_used, input.clone()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identity_does_not_copy() {
+        let input = Bytes::from(vec![1; 100]);
+        let output = identity_run(&input, 1_000).unwrap();
+        assert_eq!(output.gas_used, 15 + 4 * 3);
+        assert_eq!(output.bytes.as_ptr(), input.as_ptr());
+    }
+}
@@ -6,8 +6,9 @@ cfg_if::cfg_if! {
         use kzg_rs::{Bytes32, Bytes48, KzgProof, KzgSettings};
     }
 }
-use revm_primitives::{hex_literal::hex, Bytes, Env, PrecompileOutput};
+use revm_primitives::{hex_literal::hex, Bytes, Env, PrecompileOutput, StatefulPrecompile};
 use sha2::{Digest, Sha256};
+use std::sync::Arc;
 
 pub const POINT_EVALUATION: PrecompileWithAddress =
     PrecompileWithAddress(ADDRESS, Precompile::Env(run));
@@ -22,6 +23,61 @@ pub const RETURN_VALUE: &[u8; 64] = &hex!(
     "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001"
 );
 
+/// Backend that verifies KZG proofs for the point evaluation precompile.
+///
+/// It is implemented for the [`KzgSettings`] of the enabled `c-kzg` or `kzg-rs` backend, other
+/// backends can be used with [`point_evaluation_with_verifier`].
+pub trait KzgVerifier: Send + Sync {
+    /// Returns `true` if the proof shows that the polynomial of `commitment` evaluates to `y`
+    /// at `z`.
+    fn verify_kzg_proof(
+        &self,
+        commitment: &[u8; 48],
+        z: &[u8; 32],
+        y: &[u8; 32],
+        proof: &[u8; 48],
+    ) -> bool;
+}
+
+impl KzgVerifier for KzgSettings {
+    #[inline]
+    fn verify_kzg_proof(
+        &self,
+        commitment: &[u8; 48],
+        z: &[u8; 32],
+        y: &[u8; 32],
+        proof: &[u8; 48],
+    ) -> bool {
+        verify_kzg_proof(
+            as_bytes48(commitment),
+            as_bytes32(z),
+            as_bytes32(y),
+            as_bytes48(proof),
+            self,
+        )
+    }
+}
+
+/// Returns the point evaluation precompile that verifies proofs with `verifier` instead of the
+/// KZG settings of the environment.
+pub fn point_evaluation_with_verifier<V: KzgVerifier + 'static>(
+    verifier: V,
+) -> PrecompileWithAddress {
+    PrecompileWithAddress(
+        ADDRESS,
+        Precompile::Stateful(Arc::new(VerifierPrecompile(verifier))),
+    )
+}
+
+/// Point evaluation precompile with a custom [KzgVerifier].
+struct VerifierPrecompile<V>(V);
+
+impl<V: KzgVerifier> StatefulPrecompile for VerifierPrecompile<V> {
+    fn call(&self, input: &Bytes, gas_limit: u64, _env: &Env) -> PrecompileResult {
+        run_with_verifier(input, gas_limit, &self.0)
+    }
+}
+
 /// Run kzg point evaluation precompile.
 ///
 /// The Env has the KZGSettings that is needed for evaluation.
@@ -31,13 +87,28 @@ pub const RETURN_VALUE: &[u8; 64] = &hex!(
 /// |     32         | 32  | 32  |     48     |   48  |
 /// with z and y being padded 32 byte big endian values
 pub fn run(input: &Bytes, gas_limit: u64, env: &Env) -> PrecompileResult {
+    run_with_verifier(input, gas_limit, env.cfg.kzg_settings.get())
+}
+
+/// Run kzg point evaluation precompile, verifying the proof with `verifier`.
+///
+/// See [run] for the input encoding.
+pub fn run_with_verifier<V: KzgVerifier + ?Sized>(
+    input: &Bytes,
+    gas_limit: u64,
+    verifier: &V,
+) -> PrecompileResult {
     if gas_limit < GAS_COST {
         return Err(Error::OutOfGas.into());
     }
 
     // Verify input length.
     if input.len() != 192 {
-        return Err(Error::BlobInvalidInputLength.into());
+        return Err(Error::BlobInvalidInputLength {
+            expected: 192,
+            actual: input.len(),
+        }
+        .into());
     }
 
     // Verify commitment matches versioned_hash
@@ -48,11 +119,11 @@ pub fn run(input: &Bytes, gas_limit: u64, env: &Env) -> PrecompileResult {
     }
 
     // Verify KZG proof with z and y in big endian format
-    let commitment = as_bytes48(commitment);
-    let z = as_bytes32(&input[32..64]);
-    let y = as_bytes32(&input[64..96]);
-    let proof = as_bytes48(&input[144..192]);
-    if !verify_kzg_proof(commitment, z, y, proof, env.cfg.kzg_settings.get()) {
+    let commitment = as_array::<48>(commitment);
+    let z = as_array::<32>(&input[32..64]);
+    let y = as_array::<32>(&input[64..96]);
+    let proof = as_array::<48>(&input[144..192]);
+    if !verifier.verify_kzg_proof(commitment, z, y, proof) {
         return Err(Error::BlobVerifyKzgProofFailed.into());
     }
 
@@ -102,6 +173,7 @@ pub fn as_bytes48(bytes: &[u8]) -> &Bytes48 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use revm_primitives::PrecompileErrors;
 
     #[test]
     fn basic_test() {
@@ -123,4 +195,37 @@ mod tests {
         assert_eq!(output.gas_used, gas);
         assert_eq!(output.bytes[..], expected_output);
     }
+
+    #[test]
+    fn custom_verifier() {
+        struct RejectAll;
+        impl KzgVerifier for RejectAll {
+            fn verify_kzg_proof(
+                &self,
+                _: &[u8; 48],
+                _: &[u8; 32],
+                _: &[u8; 32],
+                _: &[u8; 48],
+            ) -> bool {
+                false
+            }
+        }
+
+        // valid input of `basic_test`, rejected by the verifier.
+        let commitment = hex!("8f59a8d2a1a625a17f3fea0fe5eb8c896db3764f3185481bc22f91b4aaffcca25f26936857bc3a7c2539ea8ec3a952b7").to_vec();
+        let mut versioned_hash = Sha256::digest(&commitment).to_vec();
+        versioned_hash[0] = VERSIONED_HASH_VERSION_KZG;
+        let z = hex!("73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000000").to_vec();
+        let y = hex!("1522a4a7f34e1ea350ae07c29c96c7e79655aa926122e95fe69fcbd932ca49e9").to_vec();
+        let proof = hex!("a62ad71d14c5719385c0686f1871430475bf3a00f0aa3f7b8dd99a9abc2160744faf0070725e00b60ad9a026a15b1a8c").to_vec();
+        let input = [versioned_hash, z, y, commitment, proof].concat();
+
+        let Precompile::Stateful(precompile) = point_evaluation_with_verifier(RejectAll).1 else {
+            panic!("point evaluation with verifier is stateful");
+        };
+        assert!(matches!(
+            precompile.call(&input.into(), 50000, &Env::default()),
+            Err(PrecompileErrors::Error(Error::BlobVerifyKzgProofFailed))
+        ));
+    }
 }
@@ -8,23 +8,46 @@
 #[cfg(not(feature = "std"))]
 extern crate alloc as std;
 
+#[cfg(all(
+    feature = "wasm",
+    target_arch = "wasm32",
+    any(feature = "c-kzg", feature = "secp256k1", feature = "asm-keccak")
+))]
+compile_error!(
+    "`c-kzg`, `secp256k1` and `asm-keccak` do not build for wasm, \
+     use `default-features = false` with the `wasm` feature"
+);
+
 pub mod blake2;
 #[cfg(feature = "blst")]
 pub mod bls12_381;
 pub mod bn128;
+pub mod cost_overrides;
 pub mod fatal_precompile;
+pub mod fuzz;
 pub mod hash;
 pub mod identity;
 #[cfg(any(feature = "c-kzg", feature = "kzg-rs"))]
 pub mod kzg_point_evaluation;
+#[cfg(feature = "precompile-metrics")]
+pub mod metrics;
 pub mod modexp;
 pub mod secp256k1;
 #[cfg(feature = "secp256r1")]
 pub mod secp256r1;
 pub mod utilities;
 
+pub use cost_overrides::{PrecompileCost, PrecompileCostOverrides};
 pub use fatal_precompile::fatal_precompile;
-
+#[cfg(feature = "precompile-metrics")]
+pub use metrics::{PrecompileMetrics, PrecompileStats};
+
+#[cfg(any(feature = "modexp-gmp", feature = "modexp-ibig"))]
+// silence aurora-engine-modexp lint as it is replaced by the selected modexp backend.
+use aurora_engine_modexp as _;
+#[cfg(all(feature = "modexp-gmp", feature = "modexp-ibig"))]
+// silence ibig lint as gmp will be used if both are enabled.
+use ibig as _;
 #[cfg(all(feature = "c-kzg", feature = "kzg-rs"))]
 // silence kzg-rs lint as c-kzg will be used as default if both are enabled.
 use kzg_rs as _;
@@ -44,10 +67,18 @@ pub fn calc_linear_cost_u32(len: usize, base: u64, word: u64) -> u64 {
     (len as u64 + 32 - 1) / 32 * word + base
 }
 
+/// Number of the low addresses, `0x00..=0xff`, dispatched through a table.
+const LOW_ADDRESSES: usize = 256;
+
 #[derive(Clone, Default, Debug)]
 pub struct Precompiles {
     /// Precompiles.
     inner: HashMap<Address, Precompile>,
+    /// Copies of the precompiles at the low addresses, indexed by the last address byte.
+    ///
+    /// Mutable stateful precompiles are not copied as their state is not shared, missing entries
+    /// are looked up in `inner`. Empty if there are no precompiles at the low addresses.
+    low: Vec<Option<Precompile>>,
     /// Addresses of precompile.
     addresses: HashSet<Address>,
 }
@@ -86,6 +117,11 @@ impl Precompiles {
         &self.inner
     }
 
+    /// Returns an iterator over the precompiles and their addresses.
+    pub fn entries(&self) -> impl Iterator<Item = (&Address, &Precompile)> {
+        self.inner.iter()
+    }
+
     /// Returns precompiles for Byzantium spec.
     pub fn byzantium() -> &'static Self {
         static INSTANCE: OnceBox<Precompiles> = OnceBox::new();
@@ -184,44 +220,64 @@ impl Precompiles {
         Self::prague()
     }
 
+    /// Returns the address, name and kind of every precompile, ordered by address.
+    ///
+    /// See [precompile_name] for the names.
+    pub fn iter(&self) -> impl Iterator<Item = (Address, &'static str, PrecompileKind)> {
+        let mut precompiles = self
+            .entries()
+            .map(|(address, precompile)| (*address, precompile_name(address), precompile.kind()))
+            .collect::<Vec<_>>();
+        precompiles.sort_unstable_by_key(|(address, _, _)| *address);
+        precompiles.into_iter()
+    }
+
     /// Returns an iterator over the precompiles addresses.
     #[inline]
     pub fn addresses(&self) -> impl ExactSizeIterator<Item = &Address> {
-        self.inner.keys()
+        self.addresses.iter()
     }
 
     /// Consumes the type and returns all precompile addresses.
     #[inline]
     pub fn into_addresses(self) -> impl ExactSizeIterator<Item = Address> {
-        self.inner.into_keys()
+        self.addresses.into_iter()
     }
 
     /// Is the given address a precompile.
     #[inline]
     pub fn contains(&self, address: &Address) -> bool {
-        self.inner.contains_key(address)
+        self.get(address).is_some()
     }
 
     /// Returns the precompile for the given address.
     #[inline]
     pub fn get(&self, address: &Address) -> Option<&Precompile> {
-        self.inner.get(address)
+        low_index(address)
+            .and_then(|index| self.low.get(index)?.as_ref())
+            .or_else(|| self.inner.get(address))
     }
 
     /// Returns the precompile for the given address.
+    ///
+    /// The precompile is no longer dispatched through the low address table, as it could be
+    /// replaced through the returned reference.
     #[inline]
     pub fn get_mut(&mut self, address: &Address) -> Option<&mut Precompile> {
+        if let Some(entry) = low_index(address).and_then(|index| self.low.get_mut(index)) {
+            *entry = None;
+        }
         self.inner.get_mut(address)
     }
 
     /// Is the precompiles list empty.
     pub fn is_empty(&self) -> bool {
-        self.inner.len() == 0
+        self.addresses.is_empty()
     }
 
     /// Returns the number of precompiles.
     pub fn len(&self) -> usize {
-        self.inner.len()
+        self.addresses.len()
     }
 
     /// Returns the precompiles addresses as a set.
@@ -234,12 +290,63 @@ impl Precompiles {
     /// Other precompiles with overwrite existing precompiles.
     #[inline]
     pub fn extend(&mut self, other: impl IntoIterator<Item = PrecompileWithAddress>) {
-        let items = other.into_iter().collect::<Vec<_>>();
-        self.addresses.extend(items.iter().map(|p| *p.address()));
-        self.inner.extend(items.into_iter().map(Into::into));
+        for PrecompileWithAddress(address, precompile) in other {
+            self.addresses.insert(address);
+            if let Some(index) = low_index(&address) {
+                if self.low.is_empty() {
+                    self.low.resize(LOW_ADDRESSES, None);
+                }
+                self.low[index] =
+                    (!matches!(precompile, Precompile::StatefulMut(_))).then(|| precompile.clone());
+            }
+            self.inner.insert(address, precompile);
+        }
     }
 }
 
+/// Returns the name of the precompile at the address, `unknown` if it is not a known precompile.
+pub fn precompile_name(address: &Address) -> &'static str {
+    let Some(index) = low_index(address)
+        .map(|index| index as u64)
+        .or_else(|| (*address == u64_to_address(0x100)).then_some(0x100))
+    else {
+        return "unknown";
+    };
+    match index {
+        0x01 => "ecrecover",
+        0x02 => "sha256",
+        0x03 => "ripemd160",
+        0x04 => "identity",
+        0x05 => "modexp",
+        0x06 => "bn254-add",
+        0x07 => "bn254-mul",
+        0x08 => "bn254-pairing",
+        0x09 => "blake2f",
+        0x0a => "kzg-point-evaluation",
+        0x0b => "bls12-g1-add",
+        0x0c => "bls12-g1-mul",
+        0x0d => "bls12-g1-msm",
+        0x0e => "bls12-g2-add",
+        0x0f => "bls12-g2-mul",
+        0x10 => "bls12-g2-msm",
+        0x11 => "bls12-pairing",
+        0x12 => "bls12-map-fp-to-g1",
+        0x13 => "bls12-map-fp2-to-g2",
+        0x100 => "p256verify",
+        _ => "unknown",
+    }
+}
+
+/// Returns the table index of the address if it is one of the low addresses.
+#[inline]
+fn low_index(address: &Address) -> Option<usize> {
+    let (prefix, last) = address.split_at(19);
+    prefix
+        .iter()
+        .all(|&byte| byte == 0)
+        .then_some(last[0] as usize)
+}
+
 #[derive(Clone, Debug)]
 pub struct PrecompileWithAddress(pub Address, pub Precompile);
 
@@ -313,3 +420,55 @@ pub const fn u64_to_address(x: u64) -> Address {
         0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, x[0], x[1], x[2], x[3], x[4], x[5], x[6], x[7],
     ])
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn low_and_high_address_dispatch() {
+        let high = Address::with_last_byte(1).create(0);
+        let mut precompiles = Precompiles::new(PrecompileSpecId::BERLIN).clone();
+        precompiles.extend([PrecompileWithAddress(high, identity::FUN.1)]);
+
+        assert_eq!(
+            precompiles.len(),
+            Precompiles::new(PrecompileSpecId::BERLIN).len() + 1
+        );
+        assert!(precompiles.contains(&secp256k1::ECRECOVER.0));
+        assert!(precompiles.contains(&high));
+        assert!(!precompiles.contains(&u64_to_address(0xff)));
+        assert!(!precompiles.contains(&u64_to_address(0x101)));
+        assert!(precompiles.get_mut(&high).is_some());
+        assert_eq!(precompiles.entries().count(), precompiles.len());
+        assert!(Precompiles::default().get(&identity::FUN.0).is_none());
+    }
+
+    #[test]
+    fn inner_map_and_replaced_low_address() {
+        let mut precompiles = Precompiles::new(PrecompileSpecId::BERLIN).clone();
+        assert_eq!(precompiles.inner().len(), precompiles.len());
+        assert!(precompiles.inner().contains_key(&identity::FUN.0));
+
+        // Replacing a precompile through `get_mut` is visible to the table dispatch.
+        *precompiles.get_mut(&identity::FUN.0).unwrap() = hash::SHA256.1;
+        let Some(Precompile::Standard(precompile)) = precompiles.get(&identity::FUN.0) else {
+            panic!("identity address is a standard precompile");
+        };
+        let output = precompile(&Bytes::from_static(b"abc"), u64::MAX).unwrap();
+        assert_eq!(output.bytes.len(), 32);
+    }
+
+    #[test]
+    fn iter_names() {
+        let precompiles = Precompiles::new(PrecompileSpecId::CANCUN)
+            .iter()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            precompiles[0],
+            (u64_to_address(1), "ecrecover", PrecompileKind::Standard)
+        );
+        assert_eq!(precompiles[7].1, "bn254-pairing");
+        assert!(precompiles.iter().all(|(_, name, _)| *name != "unknown"));
+    }
+}
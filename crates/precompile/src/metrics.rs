@@ -0,0 +1,165 @@
+//! Per-precompile invocation metrics.
+//!
+//! [PrecompileMetrics::wrap] wraps the precompiles so every call is counted, counters are shared
+//! by all clones of the metrics and of the wrapped precompiles.
+use crate::{
+    primitives::Env, Address, Bytes, HashMap, Precompile, PrecompileResult, PrecompileWithAddress,
+    Precompiles, StatefulPrecompile, StatefulPrecompileMut,
+};
+use std::{
+    boxed::Box,
+    sync::{Arc, Mutex},
+    vec::Vec,
+};
+
+/// Invocation counters of a single precompile.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PrecompileStats {
+    /// Number of calls.
+    pub calls: u64,
+    /// Number of calls that returned an error.
+    pub errors: u64,
+    /// Total gas used. Failed calls use all of their gas limit.
+    pub gas_used: u64,
+    /// Total size of the inputs in bytes.
+    pub input_bytes: u64,
+}
+
+/// Invocation metrics of the wrapped precompiles, keyed by precompile address.
+#[derive(Clone, Debug, Default)]
+pub struct PrecompileMetrics {
+    stats: Arc<Mutex<HashMap<Address, PrecompileStats>>>,
+}
+
+impl PrecompileMetrics {
+    /// Creates empty metrics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the precompiles wrapped to record their calls into these metrics.
+    pub fn wrap(&self, precompiles: &Precompiles) -> Precompiles {
+        let mut wrapped = Precompiles::default();
+        wrapped.extend(
+            precompiles
+                .addresses()
+                .map(|address| {
+                    let precompile = precompiles.get(address).unwrap().clone();
+                    self.wrap_precompile(PrecompileWithAddress(*address, precompile))
+                })
+                .collect::<Vec<_>>(),
+        );
+        wrapped
+    }
+
+    /// Returns the precompile wrapped to record its calls into these metrics.
+    pub fn wrap_precompile(&self, precompile: PrecompileWithAddress) -> PrecompileWithAddress {
+        let PrecompileWithAddress(address, inner) = precompile;
+        let metered = MeteredPrecompile {
+            address,
+            inner,
+            stats: self.stats.clone(),
+        };
+        let precompile = if matches!(metered.inner, Precompile::StatefulMut(_)) {
+            Precompile::StatefulMut(Box::new(metered))
+        } else {
+            Precompile::Stateful(Arc::new(metered))
+        };
+        PrecompileWithAddress(address, precompile)
+    }
+
+    /// Returns the counters recorded so far.
+    pub fn snapshot(&self) -> HashMap<Address, PrecompileStats> {
+        self.stats.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Resets all counters.
+    pub fn reset(&self) {
+        self.stats.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+}
+
+/// Precompile recording its calls.
+#[derive(Clone)]
+struct MeteredPrecompile {
+    address: Address,
+    inner: Precompile,
+    stats: Arc<Mutex<HashMap<Address, PrecompileStats>>>,
+}
+
+impl MeteredPrecompile {
+    fn record(&self, input: &Bytes, gas_limit: u64, result: &PrecompileResult) {
+        let mut stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+        let stats = stats.entry(self.address).or_default();
+        stats.calls += 1;
+        stats.input_bytes += input.len() as u64;
+        match result {
+            Ok(output) => stats.gas_used = stats.gas_used.saturating_add(output.gas_used),
+            Err(_) => {
+                stats.errors += 1;
+                stats.gas_used = stats.gas_used.saturating_add(gas_limit);
+            }
+        }
+    }
+}
+
+impl StatefulPrecompile for MeteredPrecompile {
+    fn call(&self, input: &Bytes, gas_limit: u64, env: &Env) -> PrecompileResult {
+        let result = self.inner.call_ref(input, gas_limit, env);
+        self.record(input, gas_limit, &result);
+        result
+    }
+}
+
+impl StatefulPrecompileMut for MeteredPrecompile {
+    fn call_mut(&mut self, input: &Bytes, gas_limit: u64, env: &Env) -> PrecompileResult {
+        let result = self.inner.call(input, gas_limit, env);
+        self.record(input, gas_limit, &result);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{identity, PrecompileSpecId};
+
+    #[test]
+    fn snapshot_counts_calls() {
+        let metrics = PrecompileMetrics::new();
+        let precompiles = metrics.wrap(Precompiles::new(PrecompileSpecId::BERLIN));
+        assert_eq!(
+            precompiles.len(),
+            Precompiles::new(PrecompileSpecId::BERLIN).len()
+        );
+
+        let Some(Precompile::Stateful(precompile)) = precompiles.get(&identity::FUN.0) else {
+            panic!("identity is not wrapped");
+        };
+        let env = Env::default();
+        precompile
+            .call(&Bytes::from(vec![0; 32]), 100, &env)
+            .unwrap();
+        precompile
+            .call(&Bytes::from(vec![0; 64]), 100, &env)
+            .unwrap();
+        precompile
+            .call(&Bytes::from(vec![0; 64]), 1, &env)
+            .unwrap_err();
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(
+            snapshot[&identity::FUN.0],
+            PrecompileStats {
+                calls: 3,
+                errors: 1,
+                gas_used: 18 + 21 + 1,
+                input_bytes: 160,
+            }
+        );
+
+        metrics.reset();
+        assert!(metrics.snapshot().is_empty());
+    }
+}
@@ -1,11 +1,11 @@
 use crate::{
     primitives::U256,
-    utilities::{left_pad, left_pad_vec, right_pad_vec, right_pad_with_offset},
+    utilities::{left_pad, left_pad_vec, right_pad_u256, right_pad_vec, right_pad_with_offset},
     Error, Precompile, PrecompileResult, PrecompileWithAddress,
 };
-use aurora_engine_modexp::modexp;
 use core::cmp::{max, min};
-use revm_primitives::{Bytes, PrecompileOutput};
+use revm_primitives::{Bytes, Env, PrecompileOutput, StatefulPrecompile};
+use std::sync::Arc;
 
 pub const BYZANTIUM: PrecompileWithAddress = PrecompileWithAddress(
     crate::u64_to_address(5),
@@ -29,6 +29,102 @@ pub fn berlin_run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
     })
 }
 
+cfg_if::cfg_if! {
+    if #[cfg(feature = "modexp-gmp")] {
+        use std::vec::Vec;
+
+        /// Computes `base^exponent % modulus` with GMP.
+        ///
+        /// Returns the big-endian result without leading zeros, zero modulus results in zero.
+        pub fn modexp(base: &[u8], exponent: &[u8], modulus: &[u8]) -> Vec<u8> {
+            use rug::{integer::Order, Integer};
+
+            let modulus = Integer::from_digits(modulus, Order::Msf);
+            if modulus == 0 {
+                return Vec::new();
+            }
+            let base = Integer::from_digits(base, Order::Msf);
+            let exponent = Integer::from_digits(exponent, Order::Msf);
+            base.pow_mod(&exponent, &modulus)
+                .expect("exponent is not negative")
+                .to_digits(Order::Msf)
+        }
+    } else if #[cfg(feature = "modexp-ibig")] {
+        use std::vec::Vec;
+
+        /// Computes `base^exponent % modulus` with `ibig`.
+        ///
+        /// Returns the big-endian result without leading zeros, zero modulus results in zero.
+        pub fn modexp(base: &[u8], exponent: &[u8], modulus: &[u8]) -> Vec<u8> {
+            use ibig::{modular::ModuloRing, UBig};
+
+            let modulus = UBig::from_be_bytes(modulus);
+            if modulus == UBig::from(0u8) {
+                return Vec::new();
+            }
+            let ring = ModuloRing::new(&modulus);
+            ring.from(UBig::from_be_bytes(base))
+                .pow(&UBig::from_be_bytes(exponent))
+                .residue()
+                .to_be_bytes()
+        }
+    } else {
+        pub use aurora_engine_modexp::modexp;
+    }
+}
+
+/// Maximum lengths of the modexp inputs, in bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ModexpLimits {
+    /// Maximum length of the base.
+    pub max_base_len: usize,
+    /// Maximum length of the exponent.
+    pub max_exp_len: usize,
+    /// Maximum length of the modulus.
+    pub max_mod_len: usize,
+}
+
+/// Returns the Berlin modexp precompile that rejects inputs longer than `limits`.
+///
+/// Lengths are checked against the limits before any gas is charged or input is copied, calls
+/// exceeding them fail with [`Error::ModexpLimitExceeded`].
+pub fn bounded(limits: ModexpLimits) -> PrecompileWithAddress {
+    PrecompileWithAddress(
+        crate::u64_to_address(5),
+        Precompile::Stateful(Arc::new(BoundedModexp(limits))),
+    )
+}
+
+/// Berlin modexp precompile with limits on the input lengths.
+#[derive(Clone, Copy, Debug)]
+struct BoundedModexp(ModexpLimits);
+
+impl StatefulPrecompile for BoundedModexp {
+    fn call(&self, input: &Bytes, gas_limit: u64, _env: &Env) -> PrecompileResult {
+        bounded_run(input, gas_limit, &self.0)
+    }
+}
+
+/// Runs the Berlin modexp if the input lengths are within `limits`.
+pub fn bounded_run(input: &Bytes, gas_limit: u64, limits: &ModexpLimits) -> PrecompileResult {
+    for (name, offset, max) in [
+        ("base", 0, limits.max_base_len),
+        ("exponent", 32, limits.max_exp_len),
+        ("modulus", 64, limits.max_mod_len),
+    ] {
+        let len = right_pad_u256(input, offset);
+        if len > U256::from(max) {
+            return Err(Error::ModexpLimitExceeded {
+                input: name.into(),
+                len,
+                max,
+            }
+            .into());
+        }
+    }
+    berlin_run(input, gas_limit)
+}
+
 pub fn calculate_iteration_count(exp_length: u64, exp_highp: &U256) -> u64 {
     let mut iteration_count: u64 = 0;
 
@@ -60,9 +156,9 @@ where
     const HEADER_LENGTH: usize = 96;
 
     // Extract the header.
-    let base_len = U256::from_be_bytes(right_pad_with_offset::<32>(input, 0).into_owned());
-    let exp_len = U256::from_be_bytes(right_pad_with_offset::<32>(input, 32).into_owned());
-    let mod_len = U256::from_be_bytes(right_pad_with_offset::<32>(input, 64).into_owned());
+    let base_len = right_pad_u256(input, 0);
+    let exp_len = right_pad_u256(input, 32);
+    let mod_len = right_pad_u256(input, 64);
 
     // cast base and modulus to usize, it does not make sense to handle larger values
     let Ok(base_len) = usize::try_from(base_len) else {
@@ -168,7 +264,7 @@ pub fn berlin_gas_calc(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use revm_primitives::hex;
+    use revm_primitives::{hex, PrecompileErrors};
     use std::vec::Vec;
 
     struct Test {
@@ -382,4 +478,51 @@ mod tests {
         let expected: Vec<u8> = Vec::new();
         assert_eq!(res.bytes, expected)
     }
+
+    #[test]
+    fn test_bounded_modexp() {
+        let limits = ModexpLimits {
+            max_base_len: 32,
+            max_exp_len: 32,
+            max_mod_len: 32,
+        };
+        for test in TESTS.iter() {
+            let input = Bytes::from(hex::decode(test.input).unwrap());
+            let lengths_within_limits = [0, 32, 64]
+                .iter()
+                .all(|&offset| right_pad_u256(&input, offset) <= U256::from(32));
+            let res = bounded_run(&input, 100_000_000, &limits);
+            if lengths_within_limits {
+                let expected = hex::decode(test.expected).unwrap();
+                assert_eq!(res.unwrap().bytes, expected, "test:{}", test.name);
+            } else {
+                assert!(
+                    matches!(
+                        res,
+                        Err(PrecompileErrors::Error(Error::ModexpLimitExceeded { .. }))
+                    ),
+                    "test:{}",
+                    test.name
+                );
+            }
+        }
+    }
+
+    #[cfg(any(feature = "modexp-gmp", feature = "modexp-ibig"))]
+    #[test]
+    fn test_modexp_backend_matches_aurora() {
+        let cases: [(&[u8], &[u8], &[u8]); 5] = [
+            (&[3], &[0xff, 0xfe], &[0xff, 0xff, 0xff, 0xff]),
+            (&[0, 0, 2], &[10], &[0x10, 0]),
+            (&[3], &[0], &[1]),
+            (&[3], &[1], &[]),
+            (&[0xff; 64], &[0xff; 32], &[0xfe; 64]),
+        ];
+        for (base, exponent, modulus) in cases {
+            let expected = aurora_engine_modexp::modexp(base, exponent, modulus);
+            // aurora returns leading zeros for some inputs, compare as numbers.
+            let leading = expected.iter().take_while(|&&b| b == 0).count();
+            assert_eq!(modexp(base, exponent, modulus), &expected[leading..]);
+        }
+    }
 }
@@ -1,16 +1,22 @@
-use crate::{utilities::right_pad, Error, Precompile, PrecompileResult, PrecompileWithAddress};
-use revm_primitives::{alloy_primitives::B512, Bytes, PrecompileOutput, B256};
+use crate::{
+    utilities::right_pad, Error, Precompile, PrecompileErrors, PrecompileResult,
+    PrecompileWithAddress, StatefulPrecompile,
+};
+use revm_primitives::{alloy_primitives::B512, Bytes, Env, PrecompileOutput, B256};
+use std::sync::Arc;
 
 pub const ECRECOVER: PrecompileWithAddress = PrecompileWithAddress(
     crate::u64_to_address(1),
     Precompile::Standard(ec_recover_run),
 );
 
-pub use self::secp256k1::ecrecover;
-
 #[cfg(not(feature = "secp256k1"))]
-#[allow(clippy::module_inception)]
-mod secp256k1 {
+pub use self::k256_backend::ecrecover;
+#[cfg(feature = "secp256k1")]
+pub use self::libsecp256k1_backend::ecrecover;
+
+/// Pure Rust `k256` implementation.
+pub mod k256_backend {
     use k256::ecdsa::{Error, RecoveryId, Signature, VerifyingKey};
     use revm_primitives::{alloy_primitives::B512, keccak256, B256};
 
@@ -40,18 +46,15 @@ mod secp256k1 {
     }
 }
 
+/// `libsecp256k1` implementation through the `secp256k1` bindings.
 #[cfg(feature = "secp256k1")]
-#[allow(clippy::module_inception)]
-mod secp256k1 {
+pub mod libsecp256k1_backend {
     use revm_primitives::{alloy_primitives::B512, keccak256, B256};
     use secp256k1::{
         ecdsa::{RecoverableSignature, RecoveryId},
         Message, Secp256k1,
     };
 
-    // Silence the unused crate dependency warning.
-    use k256 as _;
-
     pub fn ecrecover(sig: &B512, recid: u8, msg: &B256) -> Result<B256, secp256k1::Error> {
         let recid = RecoveryId::from_i32(recid as i32).expect("recovery ID is valid");
         let sig = RecoverableSignature::from_compact(sig.as_slice(), recid)?;
@@ -67,6 +70,78 @@ mod secp256k1 {
 }
 
 pub fn ec_recover_run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
+    ec_recover_run_with(input, gas_limit, |sig, recid, msg| {
+        Ok(ecrecover(sig, recid, msg).ok())
+    })
+}
+
+/// Implementation of the signer recovery.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Secp256k1Backend {
+    /// Pure Rust `k256`.
+    K256,
+    /// `libsecp256k1`.
+    #[cfg(feature = "secp256k1")]
+    Libsecp256k1,
+    /// Runs both `k256` and `libsecp256k1` and fails with a fatal error if the recovered
+    /// addresses differ.
+    #[cfg(feature = "secp256k1")]
+    Differential,
+    /// Backend selected at compile time, `libsecp256k1` if the `secp256k1` feature is enabled.
+    #[default]
+    Default,
+}
+
+impl Secp256k1Backend {
+    /// Recovers the signer address, `None` if the signature is invalid.
+    pub fn recover(
+        &self,
+        sig: &B512,
+        recid: u8,
+        msg: &B256,
+    ) -> Result<Option<B256>, PrecompileErrors> {
+        Ok(match self {
+            Self::K256 => k256_backend::ecrecover(sig, recid, msg).ok(),
+            #[cfg(feature = "secp256k1")]
+            Self::Libsecp256k1 => libsecp256k1_backend::ecrecover(sig, recid, msg).ok(),
+            #[cfg(feature = "secp256k1")]
+            Self::Differential => {
+                let k256 = k256_backend::ecrecover(sig, recid, msg).ok();
+                let libsecp256k1 = libsecp256k1_backend::ecrecover(sig, recid, msg).ok();
+                if k256 != libsecp256k1 {
+                    return Err(PrecompileErrors::Fatal {
+                        msg: format!(
+                            "ecrecover mismatch for sig {sig}, recid {recid}, msg {msg}: \
+                             k256 {k256:?}, libsecp256k1 {libsecp256k1:?}"
+                        ),
+                    });
+                }
+                k256
+            }
+            Self::Default => ecrecover(sig, recid, msg).ok(),
+        })
+    }
+
+    /// Returns the ecrecover precompile using this backend.
+    pub fn precompile(self) -> PrecompileWithAddress {
+        PrecompileWithAddress(ECRECOVER.0, Precompile::Stateful(Arc::new(self)))
+    }
+}
+
+impl StatefulPrecompile for Secp256k1Backend {
+    fn call(&self, input: &Bytes, gas_limit: u64, _env: &Env) -> PrecompileResult {
+        ec_recover_run_with(input, gas_limit, |sig, recid, msg| {
+            self.recover(sig, recid, msg)
+        })
+    }
+}
+
+/// Runs ecrecover with the given recovery of the signer address.
+fn ec_recover_run_with(
+    input: &Bytes,
+    gas_limit: u64,
+    recover: impl FnOnce(&B512, u8, &B256) -> Result<Option<B256>, PrecompileErrors>,
+) -> PrecompileResult {
     const ECRECOVER_BASE: u64 = 3_000;
 
     if ECRECOVER_BASE > gas_limit {
@@ -84,8 +159,169 @@ pub fn ec_recover_run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
     let recid = input[63] - 27;
     let sig = <&B512>::try_from(&input[64..128]).unwrap();
 
-    let out = secp256k1::ecrecover(sig, recid, msg)
+    let out = recover(sig, recid, msg)?
         .map(|o| o.to_vec().into())
         .unwrap_or_default();
     Ok(PrecompileOutput::new(ECRECOVER_BASE, out))
 }
+
+#[cfg(feature = "ecrecover-cache")]
+pub use cache::{ecrecover_cached, EcrecoverCache};
+
+#[cfg(feature = "ecrecover-cache")]
+mod cache {
+    use super::{ec_recover_run_with, ecrecover};
+    use crate::{Precompile, PrecompileResult, PrecompileWithAddress, StatefulPrecompile};
+    use revm_primitives::{alloy_primitives::B512, Bytes, Env, LruCache, B256};
+    use std::sync::{Arc, Mutex, MutexGuard};
+
+    type CacheKey = (B256, u8, B512);
+
+    /// Bounded least recently used cache of the recovered ecrecover addresses, keyed by the
+    /// message hash, recovery id and signature.
+    ///
+    /// Failed recoveries are cached as well.
+    #[derive(Debug)]
+    pub struct EcrecoverCache {
+        entries: LruCache<CacheKey, Option<B256>>,
+    }
+
+    impl EcrecoverCache {
+        /// Creates an empty cache holding at most `capacity` entries.
+        pub fn new(capacity: usize) -> Self {
+            Self {
+                entries: LruCache::new(capacity),
+            }
+        }
+
+        /// Returns the number of cached entries.
+        pub fn len(&self) -> usize {
+            self.entries.len()
+        }
+
+        /// Returns `true` if the cache is empty.
+        pub fn is_empty(&self) -> bool {
+            self.entries.is_empty()
+        }
+
+        /// Removes all entries.
+        pub fn clear(&mut self) {
+            self.entries.clear();
+        }
+
+        /// Returns the recovered address, recovering and caching it on a miss.
+        pub fn recover(&mut self, sig: &B512, recid: u8, msg: &B256) -> Option<B256> {
+            let key = (*msg, recid, *sig);
+            if let Some(address) = self.entries.get(&key) {
+                return *address;
+            }
+            let address = ecrecover(sig, recid, msg).ok();
+            self.entries.insert(key, address);
+            address
+        }
+    }
+
+    /// Ecrecover precompile sharing the cache between its clones.
+    #[derive(Clone, Debug)]
+    struct CachedEcrecover(Arc<Mutex<EcrecoverCache>>);
+
+    impl CachedEcrecover {
+        fn lock(&self) -> MutexGuard<'_, EcrecoverCache> {
+            self.0.lock().unwrap_or_else(|e| e.into_inner())
+        }
+    }
+
+    impl StatefulPrecompile for CachedEcrecover {
+        fn call(&self, input: &Bytes, gas_limit: u64, _env: &Env) -> PrecompileResult {
+            ec_recover_run_with(input, gas_limit, |sig, recid, msg| {
+                let key = (*msg, recid, *sig);
+                if let Some(address) = self.lock().entries.get(&key) {
+                    return Ok(*address);
+                }
+                // Recovery is done without holding the lock.
+                let address = ecrecover(sig, recid, msg).ok();
+                self.lock().entries.insert(key, address);
+                Ok(address)
+            })
+        }
+    }
+
+    /// Returns the ecrecover precompile that memoizes up to `capacity` recovered addresses.
+    ///
+    /// Useful when the same transactions are executed repeatedly, e.g. while building a block.
+    /// The cache is shared by all copies of the returned precompile.
+    pub fn ecrecover_cached(capacity: usize) -> PrecompileWithAddress {
+        PrecompileWithAddress(
+            super::ECRECOVER.0,
+            Precompile::Stateful(Arc::new(CachedEcrecover(Arc::new(Mutex::new(
+                EcrecoverCache::new(capacity),
+            ))))),
+        )
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use revm_primitives::hex;
+
+        #[test]
+        fn cached_matches_uncached() {
+            // Transaction signature from the ethereum tests.
+            let input = Bytes::from(hex!("18c547e4f7b0f325ad1e56f57e26c745b09a3e503d86e00e5255ff7f715d3d1c000000000000000000000000000000000000000000000000000000000000001c73b1693892219d736caba55bdb67216e485557ea6b6af75f37096c9aa6a5a75feeb940b1d03b21e36b0e47e79769f095fe2ab855bd91e3a38756b7d75a9c4549"));
+            let expected = super::super::ec_recover_run(&input, 3_000).unwrap();
+            assert!(!expected.bytes.is_empty());
+
+            let PrecompileWithAddress(_, Precompile::Stateful(precompile)) = ecrecover_cached(1)
+            else {
+                unreachable!()
+            };
+            for _ in 0..2 {
+                let res = precompile.call(&input, 3_000, &Env::default()).unwrap();
+                assert_eq!(res, expected);
+            }
+        }
+
+        #[test]
+        fn evicts_least_recently_used() {
+            let mut cache = EcrecoverCache::new(2);
+            let sig = B512::ZERO;
+            let (a, b, c) = (B256::with_last_byte(1), B256::with_last_byte(2), B256::ZERO);
+            cache.recover(&sig, 0, &a);
+            cache.recover(&sig, 0, &b);
+            cache.recover(&sig, 0, &a);
+            cache.recover(&sig, 0, &c);
+            assert_eq!(cache.len(), 2);
+            assert!(cache.entries.contains_key(&(a, 0, sig)));
+            assert!(!cache.entries.contains_key(&(b, 0, sig)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm_primitives::hex;
+
+    #[test]
+    fn backends_agree() {
+        let valid = Bytes::from(hex!("18c547e4f7b0f325ad1e56f57e26c745b09a3e503d86e00e5255ff7f715d3d1c000000000000000000000000000000000000000000000000000000000000001c73b1693892219d736caba55bdb67216e485557ea6b6af75f37096c9aa6a5a75feeb940b1d03b21e36b0e47e79769f095fe2ab855bd91e3a38756b7d75a9c4549"));
+        // Signature with `r` and `s` equal to zero.
+        let mut invalid = valid.to_vec();
+        invalid[64..].fill(0);
+        let invalid = Bytes::from(invalid);
+
+        let mut backends = vec![Secp256k1Backend::K256, Secp256k1Backend::Default];
+        #[cfg(feature = "secp256k1")]
+        backends.extend([
+            Secp256k1Backend::Libsecp256k1,
+            Secp256k1Backend::Differential,
+        ]);
+        for input in [valid, invalid] {
+            let expected = ec_recover_run(&input, 3_000).unwrap();
+            for backend in &backends {
+                let res = backend.call(&input, 3_000, &Env::default()).unwrap();
+                assert_eq!(res, expected, "{backend:?}");
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use revm_primitives::{b256, Bytes, B256};
+use revm_primitives::{b256, Bytes, PrecompileError, B256, U256};
 use std::borrow::Cow;
 
 /// Right-pads the given slice at `offset` with zeroes until `LEN`.
@@ -45,6 +45,38 @@ pub fn right_pad_vec(data: &[u8], len: usize) -> Cow<'_, [u8]> {
     }
 }
 
+/// Reads the big-endian `U256` at `offset`, right-padding the given slice with zeroes.
+#[inline]
+pub fn right_pad_u256(data: &[u8], offset: usize) -> U256 {
+    U256::from_be_bytes(right_pad_with_offset::<32>(data, offset).into_owned())
+}
+
+/// Returns the input as an array if it is exactly `LEN` bytes long.
+///
+/// Otherwise returns the error built by `err` from the input length.
+#[inline]
+pub fn require_len<const LEN: usize>(
+    input: &[u8],
+    err: impl FnOnce(usize) -> PrecompileError,
+) -> Result<&[u8; LEN], PrecompileError> {
+    input.try_into().map_err(|_| err(input.len()))
+}
+
+/// Returns the number of `len` byte chunks in the input if its length is a multiple of `len`.
+///
+/// Otherwise returns the error built by `err` from the input length. Empty input has zero chunks.
+#[inline]
+pub fn require_multiple_of(
+    input: &[u8],
+    len: usize,
+    err: impl FnOnce(usize) -> PrecompileError,
+) -> Result<usize, PrecompileError> {
+    if !input.len().is_multiple_of(len) {
+        return Err(err(input.len()));
+    }
+    Ok(input.len() / len)
+}
+
 /// Left-pads the given slice with zeroes until `LEN`.
 ///
 /// Returns the first `LEN` bytes if it does not need padding.
@@ -164,6 +196,26 @@ mod tests {
         assert_eq!(padded[..], [1, 2, 3, 4, 5, 6, 7, 8]);
     }
 
+    #[test]
+    fn input_shape_requirements() {
+        let err = |len| PrecompileError::Bn128PairLength { len };
+        assert_eq!(require_len::<4>(&[1, 2, 3, 4], err), Ok(&[1, 2, 3, 4]));
+        assert_eq!(
+            require_len::<4>(&[1, 2, 3], err),
+            Err(PrecompileError::Bn128PairLength { len: 3 })
+        );
+
+        assert_eq!(require_multiple_of(&[], 2, err), Ok(0));
+        assert_eq!(require_multiple_of(&[1, 2, 3, 4], 2, err), Ok(2));
+        assert_eq!(
+            require_multiple_of(&[1, 2, 3], 2, err),
+            Err(PrecompileError::Bn128PairLength { len: 3 })
+        );
+
+        assert_eq!(right_pad_u256(&[1; 33], 1), U256::from_be_bytes([1; 32]));
+        assert_eq!(right_pad_u256(&[1, 2], 1), U256::from(2) << 248);
+    }
+
     #[test]
     fn bool2bytes() {
         let f = bool_to_bytes32(false);
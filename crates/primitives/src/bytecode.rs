@@ -1,12 +1,17 @@
+pub mod eip7702_bytecode;
 pub mod eof;
 pub mod legacy;
 
+pub use eip7702_bytecode::{
+    Eip7702Bytecode, Eip7702DecodeError, EIP7702_MAGIC, EIP7702_MAGIC_BYTES, EIP7702_VERSION,
+};
 use eof::EofDecodeError;
 pub use eof::{Eof, EOF_MAGIC, EOF_MAGIC_BYTES, EOF_MAGIC_HASH};
 pub use legacy::{JumpTable, LegacyAnalyzedBytecode};
 use std::sync::Arc;
 
-use crate::{keccak256, Bytes, B256, KECCAK_EMPTY};
+use crate::{keccak256, Address, Bytes, B256, KECCAK_EMPTY};
+use core::fmt;
 
 /// State of the [`Bytecode`] analysis.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -18,6 +23,8 @@ pub enum Bytecode {
     LegacyAnalyzed(LegacyAnalyzedBytecode),
     /// Ethereum Object Format
     Eof(Arc<Eof>),
+    /// EIP-7702 delegation designator.
+    Eip7702(Eip7702Bytecode),
 }
 
 impl Default for Bytecode {
@@ -68,29 +75,77 @@ impl Bytecode {
         matches!(self, Self::Eof(_))
     }
 
+    /// Return reference to the EIP-7702 delegation designator if bytecode is one.
+    #[inline]
+    pub const fn eip7702(&self) -> Option<&Eip7702Bytecode> {
+        match self {
+            Self::Eip7702(eip7702) => Some(eip7702),
+            _ => None,
+        }
+    }
+
+    /// Return true if bytecode is an EIP-7702 delegation designator.
+    #[inline]
+    pub const fn is_eip7702(&self) -> bool {
+        matches!(self, Self::Eip7702(_))
+    }
+
+    /// Creates a new EIP-7702 delegation designator of the `address`.
+    #[inline]
+    pub fn new_eip7702(address: Address) -> Self {
+        Self::Eip7702(Eip7702Bytecode::new(address))
+    }
+
     /// Creates a new legacy [`Bytecode`].
     #[inline]
     pub fn new_legacy(raw: Bytes) -> Self {
         Self::LegacyRaw(raw)
     }
 
-    /// Creates a new raw [`Bytecode`].
+    /// Creates a new legacy [`Bytecode`] from static memory.
+    ///
+    /// Bytes are not copied nor hashed, and the function can be used in constant context.
+    #[inline]
+    pub const fn new_legacy_static(raw: &'static [u8]) -> Self {
+        Self::LegacyRaw(Bytes::from_static(raw))
+    }
+
+    /// Creates a new raw [`Bytecode`] from static memory.
+    ///
+    /// Legacy bytecode is not copied. Bytecode hash is not calculated, use
+    /// [`AccountInfo::from_bytecode_and_hash`](crate::AccountInfo::from_bytecode_and_hash)
+    /// to supply a known hash.
     ///
     /// # Panics
     ///
     /// Panics if bytecode is EOF and has incorrect format.
     #[inline]
+    pub fn new_raw_static(bytecode: &'static [u8]) -> Self {
+        Self::new_raw(Bytes::from_static(bytecode))
+    }
+
+    /// Creates a new raw [`Bytecode`].
+    ///
+    /// Legacy bytecode is not copied, so [`Bytes`] backed by shared or memory-mapped buffers
+    /// (e.g. converted from `bytes::Bytes`) can be used without duplicating the code.
+    ///
+    /// # Panics
+    ///
+    /// Panics if bytecode is EOF or EIP-7702 and has incorrect format.
+    #[inline]
     pub fn new_raw(bytecode: Bytes) -> Self {
-        Self::new_raw_checked(bytecode).expect("Expect correct EOF bytecode")
+        Self::new_raw_checked(bytecode).expect("Expect correct EOF or EIP-7702 bytecode")
     }
 
     /// Creates a new raw [`Bytecode`].
     ///
-    /// Returns an error on incorrect EOF format.
+    /// Returns an error on incorrect EOF or EIP-7702 format.
     #[inline]
-    pub fn new_raw_checked(bytecode: Bytes) -> Result<Self, EofDecodeError> {
+    pub fn new_raw_checked(bytecode: Bytes) -> Result<Self, BytecodeDecodeError> {
         if bytecode.starts_with(&EOF_MAGIC_BYTES) {
             Ok(Self::Eof(Arc::new(Eof::decode(bytecode)?)))
+        } else if bytecode.starts_with(&EIP7702_MAGIC_BYTES) {
+            Ok(Self::Eip7702(Eip7702Bytecode::new_raw(bytecode)?))
         } else {
             Ok(Self::LegacyRaw(bytecode))
         }
@@ -126,6 +181,7 @@ impl Bytecode {
                 .body
                 .code(0)
                 .expect("Valid EOF has at least one code section"),
+            Self::Eip7702(eip7702) => eip7702.raw(),
         }
     }
 
@@ -141,6 +197,7 @@ impl Bytecode {
             Self::LegacyRaw(bytes) => bytes.clone(),
             Self::LegacyAnalyzed(analyzed) => analyzed.bytecode().clone(),
             Self::Eof(eof) => eof.raw().clone(),
+            Self::Eip7702(eip7702) => eip7702.raw().clone(),
         }
     }
 
@@ -151,6 +208,7 @@ impl Bytecode {
             Self::LegacyRaw(bytes) => bytes,
             Self::LegacyAnalyzed(analyzed) => analyzed.bytecode(),
             Self::Eof(eof) => eof.raw(),
+            Self::Eip7702(eip7702) => eip7702.raw(),
         }
     }
 
@@ -161,6 +219,7 @@ impl Bytecode {
             Self::LegacyRaw(bytes) => bytes.clone(),
             Self::LegacyAnalyzed(analyzed) => analyzed.original_bytes(),
             Self::Eof(eof) => eof.raw().clone(),
+            Self::Eip7702(eip7702) => eip7702.raw().clone(),
         }
     }
 
@@ -171,6 +230,7 @@ impl Bytecode {
             Self::LegacyRaw(bytes) => bytes,
             Self::LegacyAnalyzed(analyzed) => analyzed.original_byte_slice(),
             Self::Eof(eof) => eof.raw(),
+            Self::Eip7702(eip7702) => eip7702.raw(),
         }
     }
 
@@ -181,6 +241,7 @@ impl Bytecode {
             Self::LegacyRaw(bytes) => bytes.len(),
             Self::LegacyAnalyzed(analyzed) => analyzed.original_len(),
             Self::Eof(eof) => eof.size(),
+            Self::Eip7702(eip7702) => eip7702.raw().len(),
         }
     }
 
@@ -191,6 +252,39 @@ impl Bytecode {
     }
 }
 
+/// Bytecode decode errors.
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum BytecodeDecodeError {
+    /// EOF decode error.
+    Eof(EofDecodeError),
+    /// EIP-7702 delegation designator decode error.
+    Eip7702(Eip7702DecodeError),
+}
+
+impl From<EofDecodeError> for BytecodeDecodeError {
+    fn from(error: EofDecodeError) -> Self {
+        Self::Eof(error)
+    }
+}
+
+impl From<Eip7702DecodeError> for BytecodeDecodeError {
+    fn from(error: Eip7702DecodeError) -> Self {
+        Self::Eip7702(error)
+    }
+}
+
+impl fmt::Display for BytecodeDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Eof(e) => fmt::Display::fmt(e, f),
+            Self::Eip7702(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BytecodeDecodeError {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -213,4 +307,31 @@ mod tests {
             panic!("Original bytecode is not Eof");
         }
     }
+
+    #[test]
+    fn eip7702_raw_bytecode() {
+        let address = Address::with_last_byte(1);
+        let bytecode = Bytecode::new_raw(Bytecode::new_eip7702(address).original_bytes());
+        assert_eq!(bytecode.eip7702().map(|code| code.address()), Some(address));
+        assert_eq!(bytecode.len(), 23);
+
+        let truncated = bytecode.original_bytes().slice(..22);
+        assert_eq!(
+            Bytecode::new_raw_checked(truncated),
+            Err(BytecodeDecodeError::Eip7702(
+                Eip7702DecodeError::InvalidLength
+            ))
+        );
+    }
+
+    #[test]
+    fn static_bytecode_is_not_copied() {
+        static CODE: &[u8] = &[0x60, 0x00, 0x00];
+        const BYTECODE: Bytecode = Bytecode::new_legacy_static(CODE);
+
+        assert_eq!(BYTECODE.original_byte_slice().as_ptr(), CODE.as_ptr());
+        let bytecode = Bytecode::new_raw_static(CODE);
+        assert_eq!(bytecode.original_byte_slice().as_ptr(), CODE.as_ptr());
+        assert_eq!(bytecode, BYTECODE);
+    }
 }
@@ -0,0 +1,135 @@
+use crate::{bytes, Address, Bytes};
+use core::fmt;
+
+/// EIP-7702 delegation designator magic number.
+pub const EIP7702_MAGIC: u16 = 0xEF01;
+
+/// EIP-7702 delegation designator magic number in array form.
+pub static EIP7702_MAGIC_BYTES: Bytes = bytes!("ef01");
+
+/// EIP-7702 delegation designator version.
+pub const EIP7702_VERSION: u8 = 0;
+
+/// Length of the EIP-7702 delegation designator, magic, version and address.
+pub const EIP7702_BYTECODE_LEN: usize = 23;
+
+/// EIP-7702 delegation designator, `0xEF0100 || address`.
+///
+/// Calls to the account with this code execute the code of the delegated address.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Eip7702Bytecode {
+    /// Address of the account whose code is executed.
+    pub delegated_address: Address,
+    /// Designator version.
+    pub version: u8,
+    /// Raw designator bytes.
+    pub raw: Bytes,
+}
+
+impl Eip7702Bytecode {
+    /// Decodes the delegation designator from raw bytes.
+    #[inline]
+    pub fn new_raw(raw: Bytes) -> Result<Self, Eip7702DecodeError> {
+        if raw.len() != EIP7702_BYTECODE_LEN {
+            return Err(Eip7702DecodeError::InvalidLength);
+        }
+        if !raw.starts_with(&EIP7702_MAGIC_BYTES) {
+            return Err(Eip7702DecodeError::InvalidMagic);
+        }
+        if raw[2] != EIP7702_VERSION {
+            return Err(Eip7702DecodeError::UnsupportedVersion);
+        }
+        Ok(Self {
+            delegated_address: Address::from_slice(&raw[3..]),
+            version: raw[2],
+            raw,
+        })
+    }
+
+    /// Creates the delegation designator of the address.
+    #[inline]
+    pub fn new(address: Address) -> Self {
+        let mut raw = EIP7702_MAGIC_BYTES.to_vec();
+        raw.push(EIP7702_VERSION);
+        raw.extend_from_slice(address.as_slice());
+        Self {
+            delegated_address: address,
+            version: EIP7702_VERSION,
+            raw: raw.into(),
+        }
+    }
+
+    /// Returns the raw designator bytes.
+    #[inline]
+    pub fn raw(&self) -> &Bytes {
+        &self.raw
+    }
+
+    /// Returns the delegated address.
+    #[inline]
+    pub fn address(&self) -> Address {
+        self.delegated_address
+    }
+}
+
+/// EIP-7702 delegation designator decode errors.
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Eip7702DecodeError {
+    /// Designator is not 23 bytes long.
+    InvalidLength,
+    /// Designator does not start with `0xEF01`.
+    InvalidMagic,
+    /// Designator version is not supported.
+    UnsupportedVersion,
+}
+
+impl fmt::Display for Eip7702DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::InvalidLength => "Invalid EIP-7702 bytecode length",
+            Self::InvalidMagic => "Invalid EIP-7702 magic number",
+            Self::UnsupportedVersion => "Unsupported EIP-7702 version",
+        };
+        f.write_str(s)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Eip7702DecodeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn designator_roundtrip() {
+        let address = Address::with_last_byte(0x42);
+        let bytecode = Eip7702Bytecode::new(address);
+        assert_eq!(bytecode.raw().len(), EIP7702_BYTECODE_LEN);
+        assert_eq!(bytecode.raw()[..3], [0xef, 0x01, 0x00]);
+        assert_eq!(
+            Eip7702Bytecode::new_raw(bytecode.raw().clone()),
+            Ok(bytecode)
+        );
+    }
+
+    #[test]
+    fn invalid_designator() {
+        assert_eq!(
+            Eip7702Bytecode::new_raw(bytes!("ef0100")),
+            Err(Eip7702DecodeError::InvalidLength)
+        );
+        let mut raw = Eip7702Bytecode::new(Address::ZERO).raw().to_vec();
+        raw[2] = 1;
+        assert_eq!(
+            Eip7702Bytecode::new_raw(raw.clone().into()),
+            Err(Eip7702DecodeError::UnsupportedVersion)
+        );
+        raw[1] = 0;
+        assert_eq!(
+            Eip7702Bytecode::new_raw(raw.into()),
+            Err(Eip7702DecodeError::InvalidMagic)
+        );
+    }
+}
@@ -1,11 +1,35 @@
 use crate::{Account, AccountInfo, Address, Bytecode, HashMap, B256, U256};
 use auto_impl::auto_impl;
+use std::vec::Vec;
 
+pub mod commit_summary;
 pub mod components;
+pub use commit_summary::{CommitSummary, SlotWrite};
 pub use components::{
     BlockHash, BlockHashRef, DatabaseComponentError, DatabaseComponents, State, StateRef,
 };
 
+/// Metadata of the account code, available without loading the code bytes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CodeMetadata {
+    /// Length of the original code.
+    pub len: usize,
+    /// Whether the code is EOF.
+    pub is_eof: bool,
+}
+
+impl CodeMetadata {
+    /// Returns the metadata of the given bytecode.
+    #[inline]
+    pub fn from_bytecode(code: &Bytecode) -> Self {
+        Self {
+            len: code.len(),
+            is_eof: code.is_eof(),
+        }
+    }
+}
+
 /// EVM database interface.
 #[auto_impl(&mut, Box)]
 pub trait Database {
@@ -18,9 +42,34 @@ pub trait Database {
     /// Get account code by its hash.
     fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error>;
 
+    /// Get account code metadata by its hash.
+    ///
+    /// Used when only the size of the code is needed, e.g. by `EXTCODESIZE` and `EXTCODEHASH`.
+    /// Databases that store the code size separately should override this to avoid reading
+    /// the code bytes.
+    fn code_metadata_by_hash(&mut self, code_hash: B256) -> Result<CodeMetadata, Self::Error> {
+        self.code_by_hash(code_hash)
+            .map(|code| CodeMetadata::from_bytecode(&code))
+    }
+
     /// Get storage value of address at index.
     fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error>;
 
+    /// Get storage values of address at the given indices, in the same order.
+    ///
+    /// Databases that can fetch multiple slots in one request, e.g. over RPC, should override
+    /// this.
+    fn storage_multi(
+        &mut self,
+        address: Address,
+        indices: &[U256],
+    ) -> Result<Vec<U256>, Self::Error> {
+        indices
+            .iter()
+            .map(|index| self.storage(address, *index))
+            .collect()
+    }
+
     /// Get block hash by block number.
     fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error>;
 }
@@ -30,6 +79,13 @@ pub trait Database {
 pub trait DatabaseCommit {
     /// Commit changes to the database.
     fn commit(&mut self, changes: HashMap<Address, Account>);
+
+    /// Commit changes to the database and return the summary of what was written.
+    fn commit_with_summary(&mut self, changes: HashMap<Address, Account>) -> CommitSummary {
+        let summary = CommitSummary::from_changes(&changes);
+        self.commit(changes);
+        summary
+    }
 }
 
 /// EVM database interface.
@@ -49,9 +105,31 @@ pub trait DatabaseRef {
     /// Get account code by its hash.
     fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error>;
 
+    /// Get account code metadata by its hash.
+    ///
+    /// See [`Database::code_metadata_by_hash`].
+    fn code_metadata_by_hash_ref(&self, code_hash: B256) -> Result<CodeMetadata, Self::Error> {
+        self.code_by_hash_ref(code_hash)
+            .map(|code| CodeMetadata::from_bytecode(&code))
+    }
+
     /// Get storage value of address at index.
     fn storage_ref(&self, address: Address, index: U256) -> Result<U256, Self::Error>;
 
+    /// Get storage values of address at the given indices, in the same order.
+    ///
+    /// See [`Database::storage_multi`].
+    fn storage_multi_ref(
+        &self,
+        address: Address,
+        indices: &[U256],
+    ) -> Result<Vec<U256>, Self::Error> {
+        indices
+            .iter()
+            .map(|index| self.storage_ref(address, *index))
+            .collect()
+    }
+
     /// Get block hash by block number.
     fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error>;
 }
@@ -80,11 +158,25 @@ impl<T: DatabaseRef> Database for WrapDatabaseRef<T> {
         self.0.code_by_hash_ref(code_hash)
     }
 
+    #[inline]
+    fn code_metadata_by_hash(&mut self, code_hash: B256) -> Result<CodeMetadata, Self::Error> {
+        self.0.code_metadata_by_hash_ref(code_hash)
+    }
+
     #[inline]
     fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
         self.0.storage_ref(address, index)
     }
 
+    #[inline]
+    fn storage_multi(
+        &mut self,
+        address: Address,
+        indices: &[U256],
+    ) -> Result<Vec<U256>, Self::Error> {
+        self.0.storage_multi_ref(address, indices)
+    }
+
     #[inline]
     fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
         self.0.block_hash_ref(number)
@@ -96,4 +188,290 @@ impl<T: DatabaseRef + DatabaseCommit> DatabaseCommit for WrapDatabaseRef<T> {
     fn commit(&mut self, changes: HashMap<Address, Account>) {
         self.0.commit(changes)
     }
+
+    #[inline]
+    fn commit_with_summary(&mut self, changes: HashMap<Address, Account>) -> CommitSummary {
+        self.0.commit_with_summary(changes)
+    }
+}
+
+/// Boxed database error.
+///
+/// Can be used as a common error type when composing databases with different error types.
+#[cfg(feature = "std")]
+pub type BoxedDatabaseError = std::boxed::Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// Wraps a database and maps its errors with the given function.
+///
+/// Useful when composing databases with different error types, e.g. an overlay on top of a
+/// remote database, as all of them can be mapped into a single error type.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MapDatabaseErr<DB, F> {
+    /// Wrapped database.
+    pub db: DB,
+    /// Function that maps the database error.
+    pub map_err: F,
+}
+
+impl<DB, F> MapDatabaseErr<DB, F> {
+    /// Creates a new wrapper that maps the errors of `db` with `map_err`.
+    #[inline]
+    pub fn new(db: DB, map_err: F) -> Self {
+        Self { db, map_err }
+    }
+
+    /// Consumes the wrapper and returns the wrapped database.
+    #[inline]
+    pub fn into_inner(self) -> DB {
+        self.db
+    }
+}
+
+#[cfg(feature = "std")]
+impl<DB> MapDatabaseErr<DB, fn(DB::Error) -> BoxedDatabaseError>
+where
+    DB: Database,
+    DB::Error: std::error::Error + Send + Sync + 'static,
+{
+    /// Creates a new wrapper that boxes the errors of `db` into [`BoxedDatabaseError`].
+    #[inline]
+    pub fn boxed(db: DB) -> Self {
+        Self::new(db, |e| std::boxed::Box::new(e) as BoxedDatabaseError)
+    }
+}
+
+impl<DB: Database, E, F: FnMut(DB::Error) -> E> Database for MapDatabaseErr<DB, F> {
+    type Error = E;
+
+    #[inline]
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.db.basic(address).map_err(&mut self.map_err)
+    }
+
+    #[inline]
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.db.code_by_hash(code_hash).map_err(&mut self.map_err)
+    }
+
+    #[inline]
+    fn code_metadata_by_hash(&mut self, code_hash: B256) -> Result<CodeMetadata, Self::Error> {
+        self.db
+            .code_metadata_by_hash(code_hash)
+            .map_err(&mut self.map_err)
+    }
+
+    #[inline]
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.db.storage(address, index).map_err(&mut self.map_err)
+    }
+
+    #[inline]
+    fn storage_multi(
+        &mut self,
+        address: Address,
+        indices: &[U256],
+    ) -> Result<Vec<U256>, Self::Error> {
+        self.db
+            .storage_multi(address, indices)
+            .map_err(&mut self.map_err)
+    }
+
+    #[inline]
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        self.db.block_hash(number).map_err(&mut self.map_err)
+    }
+}
+
+impl<DB: DatabaseRef, E, F: Fn(DB::Error) -> E> DatabaseRef for MapDatabaseErr<DB, F> {
+    type Error = E;
+
+    #[inline]
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.db.basic_ref(address).map_err(&self.map_err)
+    }
+
+    #[inline]
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.db.code_by_hash_ref(code_hash).map_err(&self.map_err)
+    }
+
+    #[inline]
+    fn code_metadata_by_hash_ref(&self, code_hash: B256) -> Result<CodeMetadata, Self::Error> {
+        self.db
+            .code_metadata_by_hash_ref(code_hash)
+            .map_err(&self.map_err)
+    }
+
+    #[inline]
+    fn storage_ref(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.db.storage_ref(address, index).map_err(&self.map_err)
+    }
+
+    #[inline]
+    fn storage_multi_ref(
+        &self,
+        address: Address,
+        indices: &[U256],
+    ) -> Result<Vec<U256>, Self::Error> {
+        self.db
+            .storage_multi_ref(address, indices)
+            .map_err(&self.map_err)
+    }
+
+    #[inline]
+    fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+        self.db.block_hash_ref(number).map_err(&self.map_err)
+    }
+}
+
+impl<DB: DatabaseCommit, F> DatabaseCommit for MapDatabaseErr<DB, F> {
+    #[inline]
+    fn commit(&mut self, changes: HashMap<Address, Account>) {
+        self.db.commit(changes)
+    }
+
+    #[inline]
+    fn commit_with_summary(&mut self, changes: HashMap<Address, Account>) -> CommitSummary {
+        self.db.commit_with_summary(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec;
+
+    /// Database that fails unless the overridden batch and metadata methods are used.
+    struct OverridingDB;
+
+    impl Database for OverridingDB {
+        type Error = &'static str;
+
+        fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+            self.basic_ref(address)
+        }
+
+        fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+            self.code_by_hash_ref(code_hash)
+        }
+
+        fn code_metadata_by_hash(&mut self, code_hash: B256) -> Result<CodeMetadata, Self::Error> {
+            self.code_metadata_by_hash_ref(code_hash)
+        }
+
+        fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+            self.storage_ref(address, index)
+        }
+
+        fn storage_multi(
+            &mut self,
+            address: Address,
+            indices: &[U256],
+        ) -> Result<Vec<U256>, Self::Error> {
+            self.storage_multi_ref(address, indices)
+        }
+
+        fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+            self.block_hash_ref(number)
+        }
+    }
+
+    impl DatabaseRef for OverridingDB {
+        type Error = &'static str;
+
+        fn basic_ref(&self, _address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+            Err("basic")
+        }
+
+        fn code_by_hash_ref(&self, _code_hash: B256) -> Result<Bytecode, Self::Error> {
+            Err("code_by_hash")
+        }
+
+        fn code_metadata_by_hash_ref(&self, _code_hash: B256) -> Result<CodeMetadata, Self::Error> {
+            Ok(CodeMetadata {
+                len: 42,
+                is_eof: false,
+            })
+        }
+
+        fn storage_ref(&self, _address: Address, _index: U256) -> Result<U256, Self::Error> {
+            Err("storage")
+        }
+
+        fn storage_multi_ref(
+            &self,
+            _address: Address,
+            indices: &[U256],
+        ) -> Result<Vec<U256>, Self::Error> {
+            Ok(vec![U256::from(7); indices.len()])
+        }
+
+        fn block_hash_ref(&self, _number: u64) -> Result<B256, Self::Error> {
+            Ok(B256::ZERO)
+        }
+    }
+
+    impl DatabaseCommit for OverridingDB {
+        fn commit(&mut self, _changes: HashMap<Address, Account>) {}
+
+        fn commit_with_summary(&mut self, _changes: HashMap<Address, Account>) -> CommitSummary {
+            CommitSummary {
+                destroyed: vec![Address::ZERO],
+                ..Default::default()
+            }
+        }
+    }
+
+    #[test]
+    fn map_database_err() {
+        let metadata = CodeMetadata {
+            len: 42,
+            is_eof: false,
+        };
+        let slots = [U256::from(1), U256::from(2)];
+        let mut db = MapDatabaseErr::new(OverridingDB, str::len);
+
+        // Errors are mapped.
+        assert_eq!(db.basic(Address::ZERO), Err(5));
+        assert_eq!(db.storage(Address::ZERO, U256::ZERO), Err(7));
+        assert_eq!(db.basic_ref(Address::ZERO), Err(5));
+        assert_eq!(db.code_by_hash_ref(B256::ZERO), Err(12));
+
+        // Overridden methods are forwarded instead of using the trait defaults, which would call
+        // the failing `storage` and `code_by_hash`.
+        assert_eq!(
+            db.storage_multi(Address::ZERO, &slots),
+            Ok(vec![U256::from(7); 2])
+        );
+        assert_eq!(db.code_metadata_by_hash(B256::ZERO), Ok(metadata));
+        assert_eq!(
+            db.storage_multi_ref(Address::ZERO, &slots),
+            Ok(vec![U256::from(7); 2])
+        );
+        assert_eq!(db.code_metadata_by_hash_ref(B256::ZERO), Ok(metadata));
+        assert_eq!(
+            db.commit_with_summary(HashMap::default()).destroyed,
+            vec![Address::ZERO]
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn boxed_database_error() {
+        #[derive(Debug)]
+        struct Error;
+
+        impl core::fmt::Display for Error {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str("database error")
+            }
+        }
+
+        impl std::error::Error for Error {}
+
+        let db = WrapDatabaseRef(MapDatabaseErr::new(OverridingDB, |_| Error));
+        let mut db = MapDatabaseErr::boxed(db);
+        let err = db.basic(Address::ZERO).unwrap_err();
+        assert_eq!(std::string::ToString::to_string(&err), "database error");
+    }
 }
@@ -0,0 +1,139 @@
+use crate::{Account, Address, B256, U256};
+use std::vec::Vec;
+
+/// Storage slot written by a commit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SlotWrite {
+    /// Address of the account that owns the storage.
+    pub address: Address,
+    /// Storage slot.
+    pub slot: U256,
+    /// Value of the slot before the transaction.
+    pub original_value: U256,
+    /// Value of the slot after the transaction.
+    pub present_value: U256,
+}
+
+/// Summary of the changes written by [`DatabaseCommit::commit`](super::DatabaseCommit::commit).
+///
+/// Accounts that are not touched are not written to the database and are not part of the summary.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CommitSummary {
+    /// Accounts created by the transaction.
+    pub created: Vec<Address>,
+    /// Created accounts that were selfdestructed earlier in the block.
+    ///
+    /// Storage of these accounts must be cleared before new storage is written. The changes of
+    /// a single transaction do not show earlier selfdestructs, so this is only filled by
+    /// databases that track the accounts over the block, e.g. `State`.
+    pub recreated: Vec<Address>,
+    /// Existing accounts that were modified.
+    pub updated: Vec<Address>,
+    /// Accounts that were selfdestructed.
+    pub destroyed: Vec<Address>,
+    /// Storage slots written to created or updated accounts.
+    pub slots_written: Vec<SlotWrite>,
+    /// Hashes of the code of the created accounts.
+    pub code_inserted: Vec<B256>,
+}
+
+impl CommitSummary {
+    /// Creates the summary of the changes that are going to be committed.
+    pub fn from_changes<'a>(changes: impl IntoIterator<Item = (&'a Address, &'a Account)>) -> Self {
+        let mut summary = Self::default();
+        for (address, account) in changes {
+            if !account.is_touched() {
+                continue;
+            }
+            if account.is_selfdestructed() {
+                summary.destroyed.push(*address);
+                continue;
+            }
+            if account.is_created() {
+                summary.created.push(*address);
+                if !account.info.is_empty_code_hash() {
+                    summary.code_inserted.push(account.info.code_hash);
+                }
+            } else {
+                summary.updated.push(*address);
+            }
+            summary
+                .slots_written
+                .extend(
+                    account
+                        .changed_storage_slots()
+                        .map(|(slot, value)| SlotWrite {
+                            address: *address,
+                            slot: *slot,
+                            original_value: value.original_value(),
+                            present_value: value.present_value(),
+                        }),
+                );
+        }
+        summary
+    }
+
+    /// Returns `true` if nothing was written.
+    pub fn is_empty(&self) -> bool {
+        self.created.is_empty() && self.updated.is_empty() && self.destroyed.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AccountInfo, Bytecode, Bytes, EvmStorageSlot, HashMap};
+
+    #[test]
+    fn summary_of_changes() {
+        let created = Address::with_last_byte(1);
+        let updated = Address::with_last_byte(2);
+        let destroyed = Address::with_last_byte(3);
+        let untouched = Address::with_last_byte(4);
+
+        let code = Bytecode::new_raw(Bytes::from_static(&[0x00]));
+        let mut created_account = Account {
+            info: AccountInfo::from_bytecode(code.clone()),
+            ..Default::default()
+        };
+        created_account.mark_touch();
+        created_account.mark_created();
+        let mut updated_account = Account::default();
+        updated_account.mark_touch();
+        updated_account.storage = HashMap::from([
+            (
+                U256::from(1),
+                EvmStorageSlot::new_changed(U256::ZERO, U256::from(2)),
+            ),
+            (U256::from(3), EvmStorageSlot::new(U256::from(4))),
+        ]);
+        let mut destroyed_account = Account::default();
+        destroyed_account.mark_touch();
+        destroyed_account.mark_selfdestruct();
+
+        let changes = HashMap::from([
+            (created, created_account),
+            (updated, updated_account),
+            (destroyed, destroyed_account),
+            (untouched, Account::default()),
+        ]);
+        let summary = CommitSummary::from_changes(&changes);
+
+        assert_eq!(summary.created, vec![created]);
+        assert!(summary.recreated.is_empty());
+        assert_eq!(summary.updated, vec![updated]);
+        assert_eq!(summary.destroyed, vec![destroyed]);
+        assert_eq!(summary.code_inserted, vec![code.hash_slow()]);
+        assert_eq!(
+            summary.slots_written,
+            vec![SlotWrite {
+                address: updated,
+                slot: U256::from(1),
+                original_value: U256::ZERO,
+                present_value: U256::from(2),
+            }]
+        );
+    }
+}
@@ -1,15 +1,19 @@
 pub mod eip7702;
+#[cfg(feature = "alloy-consensus")]
+pub mod envelope;
 pub mod handler_cfg;
 
 pub use eip7702::{
     Authorization, AuthorizationList, RecoveredAuthorization, Signature, SignedAuthorization,
 };
+#[cfg(feature = "alloy-consensus")]
+pub use envelope::{EnvelopeError, SenderCache, DEFAULT_SENDER_CACHE_CAPACITY};
 pub use handler_cfg::{CfgEnvWithHandlerCfg, EnvWithHandlerCfg, HandlerCfg};
 
 use crate::{
-    calc_blob_gasprice, AccessListItem, Account, Address, Bytes, InvalidHeader, InvalidTransaction,
-    Spec, SpecId, B256, GAS_PER_BLOB, KECCAK_EMPTY, MAX_BLOB_NUMBER_PER_BLOCK, MAX_CODE_SIZE,
-    MAX_INITCODE_SIZE, U256, VERSIONED_HASH_VERSION_KZG,
+    calc_blob_gasprice, AccessListItem, Account, Address, BaseFeeParams, Bytes, InvalidHeader,
+    InvalidTransaction, Spec, SpecId, B256, GAS_PER_BLOB, KECCAK_EMPTY, MAX_BLOB_NUMBER_PER_BLOCK,
+    MAX_CODE_SIZE, MAX_INITCODE_SIZE, U256, VERSIONED_HASH_VERSION_KZG,
 };
 use alloy_primitives::TxKind;
 use core::cmp::{min, Ordering};
@@ -124,24 +128,21 @@ impl Env {
                 }
             }
 
-            // check minimal cost against basefee
+            // check minimal cost against basefee and the chain minimum base fee
             if !self.cfg.is_base_fee_check_disabled()
-                && self.effective_gas_price() < self.block.basefee
+                && (self.effective_gas_price() < self.block.basefee
+                    || self.tx.gas_price < U256::from(self.cfg.min_base_fee))
             {
                 return Err(InvalidTransaction::GasPriceLessThanBasefee);
             }
         }
 
         // EIP-3860: Limit and meter initcode
-        if SPEC::enabled(SpecId::SHANGHAI) && self.tx.transact_to.is_create() {
-            let max_initcode_size = self
-                .cfg
-                .limit_contract_code_size
-                .map(|limit| limit.saturating_mul(2))
-                .unwrap_or(MAX_INITCODE_SIZE);
-            if self.tx.data.len() > max_initcode_size {
-                return Err(InvalidTransaction::CreateInitCodeSizeLimit);
-            }
+        if SPEC::enabled(SpecId::SHANGHAI)
+            && self.tx.transact_to.is_create()
+            && self.tx.data.len() > self.cfg.max_initcode_size()
+        {
+            return Err(InvalidTransaction::CreateInitCodeSizeLimit);
         }
 
         // - For before CANCUN, check that `blob_hashes` and `max_fee_per_blob_gas` are empty / not set
@@ -219,7 +220,15 @@ impl Env {
         // EIP-3607: Reject transactions from senders with deployed code
         // This EIP is introduced after london but there was no collision in past
         // so we can leave it enabled always
-        if !self.cfg.is_eip3607_disabled() && account.info.code_hash != KECCAK_EMPTY {
+        // EIP-7702: Senders whose code is a delegation designator are allowed.
+        if !self.cfg.is_eip3607_disabled()
+            && account.info.code_hash != KECCAK_EMPTY
+            && !account
+                .info
+                .code
+                .as_ref()
+                .is_some_and(|code| code.is_eip7702())
+        {
             return Err(InvalidTransaction::RejectCallerWithCode);
         }
 
@@ -288,13 +297,36 @@ pub struct CfgEnv {
     /// If some it will effects EIP-170: Contract code size limit. Useful to increase this because of tests.
     /// By default it is 0x6000 (~25kb).
     pub limit_contract_code_size: Option<usize>,
-    /// A hard memory limit in bytes beyond which [crate::result::OutOfGasError::Memory] cannot be resized.
-    ///
-    /// In cases where the gas limit may be extraordinarily high, it is recommended to set this to
-    /// a sane value to prevent memory allocation panics. Defaults to `2^32 - 1` bytes per
-    /// EIP-1985.
-    #[cfg(feature = "memory_limit")]
+    /// If some it will effects EIP-3860: Limit and meter initcode.
+    /// By default, it is double of the max contract code size, see [`Self::max_code_size`].
+    pub limit_initcode_size: Option<usize>,
+    /// EIP-1559 parameters used to calculate the base fee of the next block.
+    ///
+    /// By default, it is set to the Ethereum mainnet parameters.
+    pub base_fee_params: BaseFeeParams,
+    /// Minimum base fee of the chain.
+    ///
+    /// Transactions with a max fee per gas lower than this value are rejected, and the next block
+    /// base fee calculated by [`CfgEnv::next_block_base_fee`] never goes below it.
+    /// By default, it is set to `0`.
+    pub min_base_fee: u64,
+    /// Destination of the base fee after London.
+    ///
+    /// By default, it is set to [`BaseFeePolicy::Burn`].
+    pub base_fee_policy: BaseFeePolicy,
+    /// A hard memory limit in bytes beyond which the memory of a call frame cannot be resized.
+    ///
+    /// Memory expansion beyond it halts with [crate::result::OutOfGasError::MemoryLimit]. In cases
+    /// where the gas limit may be extraordinarily high, e.g. in RPC simulations, it is recommended
+    /// to set this to a sane value to prevent memory allocation panics. Defaults to `2^32 - 1`
+    /// bytes per EIP-1985.
     pub memory_limit: u64,
+    /// A limit in bytes of memory expanded by all call frames of the transaction combined.
+    ///
+    /// Memory of call frames that already returned is still counted, which bounds the total memory
+    /// allocated by the transaction. Exceeding it halts with
+    /// [crate::result::OutOfGasError::CumulativeMemoryLimit]. Defaults to `u64::MAX`.
+    pub cumulative_memory_limit: u64,
     /// Skip balance checks if true. Adds transaction cost to balance to ensure execution doesn't fail.
     #[cfg(feature = "optional_balance_check")]
     pub disable_balance_check: bool,
@@ -322,6 +354,11 @@ pub struct CfgEnv {
     /// By default, it is set to `false`.
     #[cfg(feature = "optional_beneficiary_reward")]
     pub disable_beneficiary_reward: bool,
+    /// Disables the `SELFDESTRUCT` opcode, executing it halts with
+    /// [`crate::HaltReason::SelfDestructDisabled`]. Useful for chains that removed the opcode.
+    /// By default, it is set to `false`.
+    #[cfg(feature = "optional_selfdestruct")]
+    pub disable_selfdestruct: bool,
 }
 
 impl CfgEnv {
@@ -331,11 +368,36 @@ impl CfgEnv {
         self.limit_contract_code_size.unwrap_or(MAX_CODE_SIZE)
     }
 
+    /// Returns max initcode size from [`Self::limit_initcode_size`] if set,
+    /// otherwise double of [`Self::limit_contract_code_size`] if set
+    /// or default [`MAX_INITCODE_SIZE`] value.
+    pub fn max_initcode_size(&self) -> usize {
+        self.limit_initcode_size.unwrap_or_else(|| {
+            self.limit_contract_code_size
+                .map(|limit| limit.saturating_mul(2))
+                .unwrap_or(MAX_INITCODE_SIZE)
+        })
+    }
+
     pub fn with_chain_id(mut self, chain_id: u64) -> Self {
         self.chain_id = chain_id;
         self
     }
 
+    /// Calculates the base fee of the next block from the parent block gas usage and base fee.
+    ///
+    /// Uses [`Self::base_fee_params`] and is floored to [`Self::min_base_fee`].
+    pub fn next_block_base_fee(&self, gas_used: u64, gas_limit: u64, base_fee: u64) -> u64 {
+        let next = self.base_fee_params.next_block_base_fee(
+            gas_used as u128,
+            gas_limit as u128,
+            base_fee as u128,
+        );
+        u64::try_from(next)
+            .unwrap_or(u64::MAX)
+            .max(self.min_base_fee)
+    }
+
     #[cfg(feature = "optional_eip3607")]
     pub fn is_eip3607_disabled(&self) -> bool {
         self.disable_eip3607
@@ -395,6 +457,16 @@ impl CfgEnv {
     pub fn is_beneficiary_reward_disabled(&self) -> bool {
         false
     }
+
+    #[cfg(feature = "optional_selfdestruct")]
+    pub fn is_selfdestruct_disabled(&self) -> bool {
+        self.disable_selfdestruct
+    }
+
+    #[cfg(not(feature = "optional_selfdestruct"))]
+    pub fn is_selfdestruct_disabled(&self) -> bool {
+        false
+    }
 }
 
 impl Default for CfgEnv {
@@ -403,10 +475,14 @@ impl Default for CfgEnv {
             chain_id: 1,
             perf_analyse_created_bytecodes: AnalysisKind::default(),
             limit_contract_code_size: None,
+            limit_initcode_size: None,
+            base_fee_params: BaseFeeParams::ethereum(),
+            min_base_fee: 0,
+            base_fee_policy: BaseFeePolicy::Burn,
             #[cfg(any(feature = "c-kzg", feature = "kzg-rs"))]
             kzg_settings: crate::kzg::EnvKzgSettings::Default,
-            #[cfg(feature = "memory_limit")]
             memory_limit: (1 << 32) - 1,
+            cumulative_memory_limit: u64::MAX,
             #[cfg(feature = "optional_balance_check")]
             disable_balance_check: false,
             #[cfg(feature = "optional_block_gas_limit")]
@@ -419,6 +495,8 @@ impl Default for CfgEnv {
             disable_base_fee: false,
             #[cfg(feature = "optional_beneficiary_reward")]
             disable_beneficiary_reward: false,
+            #[cfg(feature = "optional_selfdestruct")]
+            disable_selfdestruct: false,
         }
     }
 }
@@ -708,6 +786,31 @@ pub enum CreateScheme {
     },
 }
 
+/// Destination of the base fee of a transaction after London.
+#[derive(Clone, Copy, Default, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BaseFeePolicy {
+    /// Base fee is burned, as specified by EIP-1559.
+    #[default]
+    Burn,
+    /// Base fee is credited to the block beneficiary together with the priority fee.
+    ToCoinbase,
+    /// Base fee is credited to the fee vault. On optimism, the L1 data fee is credited to
+    /// the vault as well. The beneficiary receives only the priority fee.
+    ToVault(Address),
+}
+
+impl BaseFeePolicy {
+    /// Returns the fee vault address if the base fee is credited to a vault.
+    #[inline]
+    pub const fn fee_vault(&self) -> Option<Address> {
+        match self {
+            Self::ToVault(address) => Some(*address),
+            _ => None,
+        }
+    }
+}
+
 /// What bytecode analysis to perform.
 #[derive(Clone, Default, Debug, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -734,6 +837,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_validate_tx_min_base_fee() {
+        let mut env = Env::default();
+        env.cfg.min_base_fee = 10;
+        env.tx.gas_price = U256::from(9);
+        assert_eq!(
+            env.validate_tx::<crate::LatestSpec>(),
+            Err(InvalidTransaction::GasPriceLessThanBasefee)
+        );
+        env.tx.gas_price = U256::from(10);
+        assert_eq!(env.validate_tx::<crate::LatestSpec>(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_tx_initcode_size_limit() {
+        let mut env = Env::default();
+        env.tx.transact_to = TxKind::Create;
+        env.tx.data = Bytes::from(vec![0; 100]);
+        env.cfg.limit_contract_code_size = Some(50);
+        assert_eq!(env.validate_tx::<crate::LatestSpec>(), Ok(()));
+        env.cfg.limit_contract_code_size = Some(49);
+        assert_eq!(
+            env.validate_tx::<crate::LatestSpec>(),
+            Err(InvalidTransaction::CreateInitCodeSizeLimit)
+        );
+        env.cfg.limit_initcode_size = Some(100);
+        assert_eq!(env.validate_tx::<crate::LatestSpec>(), Ok(()));
+    }
+
+    #[test]
+    fn test_next_block_base_fee_floor() {
+        let mut cfg = CfgEnv::default();
+        // empty block decreases the base fee by 1/8.
+        assert_eq!(cfg.next_block_base_fee(0, 30_000_000, 800), 700);
+        cfg.min_base_fee = 750;
+        assert_eq!(cfg.next_block_base_fee(0, 30_000_000, 800), 750);
+        cfg.base_fee_params = BaseFeeParams::new(4, 2);
+        cfg.min_base_fee = 0;
+        assert_eq!(cfg.next_block_base_fee(0, 30_000_000, 800), 600);
+    }
+
     #[test]
     fn test_validate_tx_access_list() {
         let mut env = Env::default();
@@ -0,0 +1,280 @@
+//! Conversion of signed [`TxEnvelope`] transactions into [`TxEnv`].
+
+use super::TxEnv;
+use crate::{Address, LruCache, B256, U256};
+use alloy_consensus::TxEnvelope;
+use alloy_primitives::{SignatureError, TxKind};
+use core::fmt;
+
+#[cfg(feature = "optimism")]
+use alloy_eips::eip2718::Encodable2718;
+
+/// Error converting a signed transaction envelope into [`TxEnv`].
+#[derive(Debug)]
+pub enum EnvelopeError {
+    /// Sender could not be recovered from the transaction signature.
+    Signature(SignatureError),
+    /// Transaction type is not supported by the conversion.
+    UnsupportedTxType(u8),
+}
+
+impl From<SignatureError> for EnvelopeError {
+    fn from(err: SignatureError) -> Self {
+        Self::Signature(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EnvelopeError {}
+
+impl fmt::Display for EnvelopeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Signature(err) => write!(f, "sender recovery failed: {err}"),
+            Self::UnsupportedTxType(ty) => write!(f, "unsupported transaction type {ty}"),
+        }
+    }
+}
+
+impl TxEnv {
+    /// Creates a new [`TxEnv`] from the signed transaction envelope.
+    ///
+    /// Sender is recovered from the transaction signature.
+    pub fn from_envelope(envelope: &TxEnvelope) -> Result<Self, EnvelopeError> {
+        let caller = envelope.recover_signer()?;
+        Self::from_recovered_envelope(envelope, caller)
+    }
+
+    /// Creates a new [`TxEnv`] from the signed transaction envelope and already recovered sender.
+    ///
+    /// With `optimism` feature the EIP-2718 encoded transaction is set as
+    /// `optimism.enveloped_tx` so the L1 cost can be calculated.
+    pub fn from_recovered_envelope(
+        envelope: &TxEnvelope,
+        caller: Address,
+    ) -> Result<Self, EnvelopeError> {
+        let mut tx_env = Self {
+            caller,
+            ..Default::default()
+        };
+        match envelope {
+            TxEnvelope::Legacy(tx) => {
+                let tx = tx.tx();
+                tx_env.gas_limit = tx.gas_limit.try_into().unwrap_or(u64::MAX);
+                tx_env.gas_price = U256::from(tx.gas_price);
+                tx_env.transact_to = tx.to;
+                tx_env.value = tx.value;
+                tx_env.data = tx.input.clone();
+                tx_env.chain_id = tx.chain_id;
+                tx_env.nonce = Some(tx.nonce);
+            }
+            TxEnvelope::Eip2930(tx) => {
+                let tx = tx.tx();
+                tx_env.gas_limit = tx.gas_limit.try_into().unwrap_or(u64::MAX);
+                tx_env.gas_price = U256::from(tx.gas_price);
+                tx_env.transact_to = tx.to;
+                tx_env.value = tx.value;
+                tx_env.data = tx.input.clone();
+                tx_env.chain_id = Some(tx.chain_id);
+                tx_env.nonce = Some(tx.nonce);
+                tx_env.access_list = tx.access_list.0.clone();
+            }
+            TxEnvelope::Eip1559(tx) => {
+                let tx = tx.tx();
+                tx_env.gas_limit = tx.gas_limit.try_into().unwrap_or(u64::MAX);
+                tx_env.gas_price = U256::from(tx.max_fee_per_gas);
+                tx_env.gas_priority_fee = Some(U256::from(tx.max_priority_fee_per_gas));
+                tx_env.transact_to = tx.to;
+                tx_env.value = tx.value;
+                tx_env.data = tx.input.clone();
+                tx_env.chain_id = Some(tx.chain_id);
+                tx_env.nonce = Some(tx.nonce);
+                tx_env.access_list = tx.access_list.0.clone();
+            }
+            TxEnvelope::Eip4844(tx) => {
+                let tx = tx.tx().tx();
+                tx_env.gas_limit = tx.gas_limit.try_into().unwrap_or(u64::MAX);
+                tx_env.gas_price = U256::from(tx.max_fee_per_gas);
+                tx_env.gas_priority_fee = Some(U256::from(tx.max_priority_fee_per_gas));
+                tx_env.transact_to = TxKind::Call(tx.to);
+                tx_env.value = tx.value;
+                tx_env.data = tx.input.clone();
+                tx_env.chain_id = Some(tx.chain_id);
+                tx_env.nonce = Some(tx.nonce);
+                tx_env.access_list = tx.access_list.0.clone();
+                tx_env.blob_hashes.clone_from(&tx.blob_versioned_hashes);
+                tx_env.max_fee_per_blob_gas = Some(U256::from(tx.max_fee_per_blob_gas));
+            }
+            // newer transaction types are not supported yet. Scroll L1 message transactions are
+            // not modelled in this tree, `TxEnv` has no fields for them.
+            _ => return Err(EnvelopeError::UnsupportedTxType(envelope.tx_type().into())),
+        }
+
+        #[cfg(feature = "optimism")]
+        {
+            tx_env.optimism.enveloped_tx = Some(envelope.encoded_2718().into());
+        }
+        Ok(tx_env)
+    }
+
+    /// Overwrites all transaction fields with the fields of the signed transaction envelope.
+    ///
+    /// The transaction is left unchanged if the conversion fails.
+    pub fn fill_from_envelope(
+        &mut self,
+        envelope: &TxEnvelope,
+        caller: Address,
+    ) -> Result<(), EnvelopeError> {
+        *self = Self::from_recovered_envelope(envelope, caller)?;
+        Ok(())
+    }
+}
+
+/// Default number of senders kept by the [SenderCache].
+pub const DEFAULT_SENDER_CACHE_CAPACITY: usize = 16384;
+
+/// Cache of recovered transaction senders, keyed by transaction hash.
+///
+/// Sender recovery is the most expensive part of the conversion, this cache allows the same
+/// transaction to be converted multiple times (e.g. when re-executing a block) without recovering
+/// the sender again. Least recently used senders are evicted when the cache is full.
+#[derive(Clone, Debug)]
+pub struct SenderCache {
+    senders: LruCache<B256, Address>,
+}
+
+impl Default for SenderCache {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_SENDER_CACHE_CAPACITY)
+    }
+}
+
+impl SenderCache {
+    /// Creates a new empty cache with [DEFAULT_SENDER_CACHE_CAPACITY].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new empty cache that keeps at most `capacity` senders.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            senders: LruCache::new(capacity),
+        }
+    }
+
+    /// Returns the sender of the transaction, recovering it if it is not cached.
+    pub fn recover(&mut self, envelope: &TxEnvelope) -> Result<Address, SignatureError> {
+        let hash = *envelope.tx_hash();
+        if let Some(sender) = self.senders.get(&hash) {
+            return Ok(*sender);
+        }
+        let sender = envelope.recover_signer()?;
+        self.senders.insert(hash, sender);
+        Ok(sender)
+    }
+
+    /// Creates a new [`TxEnv`] from the signed transaction envelope, using the cached sender.
+    pub fn tx_env(&mut self, envelope: &TxEnvelope) -> Result<TxEnv, EnvelopeError> {
+        let caller = self.recover(envelope)?;
+        TxEnv::from_recovered_envelope(envelope, caller)
+    }
+
+    /// Returns the number of cached senders.
+    pub fn len(&self) -> usize {
+        self.senders.len()
+    }
+
+    /// Returns `true` if no senders are cached.
+    pub fn is_empty(&self) -> bool {
+        self.senders.is_empty()
+    }
+
+    /// Removes all cached senders.
+    pub fn clear(&mut self) {
+        self.senders.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bytes, U256};
+    use alloy_consensus::{Signed, TxEip1559, TxLegacy};
+    use alloy_primitives::Signature;
+
+    fn legacy(nonce: u64, signature: Signature) -> TxEnvelope {
+        let tx = TxLegacy {
+            chain_id: Some(1),
+            nonce,
+            gas_price: 7,
+            gas_limit: 21_000,
+            to: TxKind::Call(Address::with_last_byte(1)),
+            value: U256::from(3),
+            input: Bytes::from_static(&[1, 2]),
+        };
+        Signed::new_unchecked(tx, signature, B256::with_last_byte(nonce as u8)).into()
+    }
+
+    #[test]
+    fn converts_envelopes() {
+        let caller = Address::with_last_byte(2);
+        let tx_env =
+            TxEnv::from_recovered_envelope(&legacy(5, Signature::test_signature()), caller)
+                .unwrap();
+        assert_eq!(tx_env.caller, caller);
+        assert_eq!(tx_env.gas_limit, 21_000);
+        assert_eq!(tx_env.gas_price, U256::from(7));
+        assert_eq!(tx_env.gas_priority_fee, None);
+        assert_eq!(tx_env.transact_to, TxKind::Call(Address::with_last_byte(1)));
+        assert_eq!(tx_env.value, U256::from(3));
+        assert_eq!(tx_env.data, Bytes::from_static(&[1, 2]));
+        assert_eq!(tx_env.chain_id, Some(1));
+        assert_eq!(tx_env.nonce, Some(5));
+
+        let tx = TxEip1559 {
+            chain_id: 1,
+            nonce: 6,
+            gas_limit: 50_000,
+            max_fee_per_gas: 10,
+            max_priority_fee_per_gas: 2,
+            to: TxKind::Create,
+            ..Default::default()
+        };
+        let envelope = Signed::new_unchecked(tx, Signature::test_signature(), B256::ZERO).into();
+        let mut tx_env = TxEnv::default();
+        tx_env.fill_from_envelope(&envelope, caller).unwrap();
+        assert_eq!(tx_env.gas_price, U256::from(10));
+        assert_eq!(tx_env.gas_priority_fee, Some(U256::from(2)));
+        assert_eq!(tx_env.transact_to, TxKind::Create);
+        assert_eq!(tx_env.nonce, Some(6));
+    }
+
+    #[test]
+    fn invalid_signature_is_an_error() {
+        // There is no curve point with `x = 5`, so no key can be recovered.
+        let signature = Signature::from_rs_and_parity(U256::from(5), U256::from(1), false).unwrap();
+        assert!(matches!(
+            TxEnv::from_envelope(&legacy(0, signature)),
+            Err(EnvelopeError::Signature(_))
+        ));
+    }
+
+    #[test]
+    fn sender_cache_is_bounded() {
+        let mut cache = SenderCache::with_capacity(1);
+        let first = legacy(0, Signature::test_signature());
+        let sender = cache.recover(&first).unwrap();
+        assert_eq!(sender, first.recover_signer().unwrap());
+        assert_eq!(cache.tx_env(&first).unwrap().caller, sender);
+        assert_eq!(cache.len(), 1);
+
+        cache
+            .recover(&legacy(1, Signature::test_signature()))
+            .unwrap();
+        assert_eq!(cache.len(), 1);
+        assert!(!cache.senders.contains_key(first.tx_hash()));
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+}
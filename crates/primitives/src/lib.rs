@@ -14,14 +14,21 @@ pub mod env;
 
 #[cfg(any(feature = "c-kzg", feature = "kzg-rs"))]
 pub mod kzg;
+mod lru;
 pub mod precompile;
+pub mod receipt;
 pub mod result;
 pub mod specification;
+#[cfg(feature = "ssz")]
+pub mod ssz;
 pub mod state;
 pub mod utilities;
-pub use alloy_eips::eip2930::{AccessList, AccessListItem};
+pub use alloy_eips::{
+    eip1559::BaseFeeParams,
+    eip2930::{AccessList, AccessListItem},
+};
 pub use alloy_primitives::{
-    self, address, b256, bytes, fixed_bytes, hex, hex_literal, ruint, uint, Address, Bytes,
+    self, address, b256, bytes, fixed_bytes, hex, hex_literal, ruint, uint, Address, Bloom, Bytes,
     FixedBytes, Log, LogData, TxKind, B256, I256, U256,
 };
 pub use bitvec;
@@ -40,7 +47,9 @@ cfg_if::cfg_if! {
 
 #[cfg(any(feature = "c-kzg", feature = "kzg-rs"))]
 pub use kzg::{EnvKzgSettings, KzgSettings};
+pub use lru::LruCache;
 pub use precompile::*;
+pub use receipt::*;
 pub use result::*;
 pub use specification::*;
 pub use state::*;
@@ -0,0 +1,128 @@
+//! Bounded least recently used cache.
+
+use crate::HashMap;
+use core::hash::Hash;
+use std::collections::BTreeMap;
+
+/// Map holding at most `capacity` entries, the least recently used entry is evicted when a new
+/// entry is inserted into a full cache.
+///
+/// Lookups, inserts and evictions are `O(log n)`.
+#[derive(Clone, Debug)]
+pub struct LruCache<K, V> {
+    capacity: usize,
+    /// Values with the tick of their last use.
+    entries: HashMap<K, (V, u64)>,
+    /// Keys ordered by the tick of their last use.
+    lru: BTreeMap<u64, K>,
+    /// Incremented on every use.
+    tick: u64,
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    /// Creates an empty cache holding at most `capacity` entries.
+    ///
+    /// Cache with zero capacity does not store anything.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::default(),
+            lru: BTreeMap::new(),
+            tick: 0,
+        }
+    }
+
+    /// Returns the maximum number of entries.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of cached entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns `true` if the key is cached, without marking it as used.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// Returns the cached value and marks it as the most recently used.
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        let (value, last_used) = self.entries.get_mut(key)?;
+        self.tick += 1;
+        let key = self
+            .lru
+            .remove(last_used)
+            .expect("cached keys are ordered by use");
+        *last_used = self.tick;
+        self.lru.insert(self.tick, key);
+        Some(value)
+    }
+
+    /// Inserts the value as the most recently used, evicting the least recently used entry if
+    /// the cache is full.
+    pub fn insert(&mut self, key: K, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.tick += 1;
+        if let Some((_, last_used)) = self.entries.get(&key) {
+            self.lru.remove(last_used);
+        } else if self.entries.len() >= self.capacity {
+            if let Some((_, evicted)) = self.lru.pop_first() {
+                self.entries.remove(&evicted);
+            }
+        }
+        self.lru.insert(self.tick, key.clone());
+        self.entries.insert(key, (value, self.tick));
+    }
+
+    /// Removes all entries.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.lru.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = LruCache::new(2);
+        cache.insert(1, "a");
+        cache.insert(2, "b");
+        assert_eq!(cache.get(&1), Some(&"a"));
+        cache.insert(3, "c");
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.contains_key(&1));
+        assert!(!cache.contains_key(&2));
+        assert!(cache.contains_key(&3));
+
+        // Replacing a value does not evict.
+        cache.insert(3, "d");
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&3), Some(&"d"));
+        cache.insert(4, "e");
+        assert!(!cache.contains_key(&1));
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let mut cache = LruCache::new(0);
+        cache.insert(1, ());
+        assert!(cache.is_empty());
+        assert_eq!(cache.get(&1), None);
+    }
+}
@@ -1,7 +1,7 @@
-use crate::{Bytes, Env};
+use crate::{Bytes, Env, Log, U256};
 use core::fmt::{self};
 use dyn_clone::DynClone;
-use std::{boxed::Box, string::String, sync::Arc};
+use std::{borrow::Cow, boxed::Box, string::String, sync::Arc, vec::Vec};
 
 /// A precompile operation result.
 ///
@@ -13,14 +13,53 @@ pub type PrecompileResult = Result<PrecompileOutput, PrecompileErrors>;
 pub struct PrecompileOutput {
     /// Gas used by the precompile.
     pub gas_used: u64,
+    /// Gas refunded by the precompile.
+    ///
+    /// It is added to the refund counter of the call frame the same way as `SSTORE` refunds.
+    pub gas_refunded: i64,
+    /// Whether the precompile reverted.
+    ///
+    /// State changes of the call are reverted and `bytes` are returned as the revert data.
+    pub reverted: bool,
     /// Output bytes.
     pub bytes: Bytes,
+    /// Logs emitted by the precompile.
+    ///
+    /// They are appended to the journal after the logs of the calling frame, unless the
+    /// precompile reverted.
+    pub logs: Vec<Log>,
 }
 
 impl PrecompileOutput {
     /// Returns new precompile output with the given gas used and output bytes.
     pub fn new(gas_used: u64, bytes: Bytes) -> Self {
-        Self { gas_used, bytes }
+        Self {
+            gas_used,
+            gas_refunded: 0,
+            reverted: false,
+            bytes,
+            logs: Vec::new(),
+        }
+    }
+
+    /// Returns new reverted precompile output with the given gas used and revert data.
+    pub fn new_reverted(gas_used: u64, bytes: Bytes) -> Self {
+        Self {
+            reverted: true,
+            ..Self::new(gas_used, bytes)
+        }
+    }
+
+    /// Sets the gas refund of the output.
+    pub fn with_gas_refund(mut self, gas_refunded: i64) -> Self {
+        self.gas_refunded = gas_refunded;
+        self
+    }
+
+    /// Sets the logs emitted by the precompile.
+    pub fn with_logs(mut self, logs: Vec<Log>) -> Self {
+        self.logs = logs;
+        self
     }
 }
 
@@ -97,7 +136,30 @@ impl fmt::Debug for Precompile {
     }
 }
 
+/// Kind of the [Precompile].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PrecompileKind {
+    /// [Precompile::Standard].
+    Standard,
+    /// [Precompile::Env].
+    Env,
+    /// [Precompile::Stateful].
+    Stateful,
+    /// [Precompile::StatefulMut].
+    StatefulMut,
+}
+
 impl Precompile {
+    /// Returns the kind of the precompile.
+    pub fn kind(&self) -> PrecompileKind {
+        match self {
+            Precompile::Standard(_) => PrecompileKind::Standard,
+            Precompile::Env(_) => PrecompileKind::Env,
+            Precompile::Stateful(_) => PrecompileKind::Stateful,
+            Precompile::StatefulMut(_) => PrecompileKind::StatefulMut,
+        }
+    }
+
     /// Create a new stateful precompile.
     pub fn new_stateful<P: StatefulPrecompile + 'static>(p: P) -> Self {
         Self::Stateful(Arc::new(p))
@@ -153,27 +215,67 @@ impl fmt::Display for PrecompileErrors {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PrecompileError {
     /// out of gas is the main error. Others are here just for completeness
     OutOfGas,
     // Blake2 errors
-    Blake2WrongLength,
-    Blake2WrongFinalIndicatorFlag,
+    /// The input length is not 213 bytes.
+    Blake2WrongLength {
+        /// Required input length.
+        expected: usize,
+        /// Length of the input.
+        actual: usize,
+    },
+    /// The final block indicator flag is neither 0 nor 1.
+    Blake2WrongFinalIndicatorFlag {
+        /// Value of the flag byte.
+        flag: u8,
+    },
     // Modexp errors
     ModexpExpOverflow,
     ModexpBaseOverflow,
     ModexpModOverflow,
+    /// Base, exponent or modulus length exceeds the limit of the bounded modexp precompile.
+    ModexpLimitExceeded {
+        /// Name of the input, `base`, `exponent` or `modulus`.
+        input: Cow<'static, str>,
+        /// Length of the input.
+        len: U256,
+        /// Maximum length of the input.
+        max: usize,
+    },
     // Bn128 errors
     Bn128FieldPointNotAMember,
     Bn128AffineGFailedToCreate,
-    Bn128PairLength,
+    /// The pairing input length is not a multiple of 192 bytes or exceeds the size limit.
+    Bn128PairLength {
+        /// Length of the input.
+        len: usize,
+    },
+    /// The number of pairs exceeds the limit of the pairing precompile.
+    Bn128PairLimitExceeded {
+        /// Number of pairs in the input.
+        pairs: usize,
+        /// Maximum number of pairs.
+        max: usize,
+    },
     // Blob errors
     /// The input length is not exactly 192 bytes.
-    BlobInvalidInputLength,
+    BlobInvalidInputLength {
+        /// Required input length.
+        expected: usize,
+        /// Length of the input.
+        actual: usize,
+    },
     /// The commitment does not match the versioned hash.
     BlobMismatchedVersion,
     /// The proof verification failed.
     BlobVerifyKzgProofFailed,
+    /// The precompile is disabled by the chain configuration.
+    Disabled,
+    /// The precompile is not implemented.
+    NotImplemented,
     /// Catch-all variant for other errors.
     Other(String),
 }
@@ -201,22 +303,41 @@ impl std::error::Error for PrecompileError {}
 
 impl fmt::Display for PrecompileError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let s = match self {
-            Self::OutOfGas => "out of gas",
-            Self::Blake2WrongLength => "wrong input length for blake2",
-            Self::Blake2WrongFinalIndicatorFlag => "wrong final indicator flag for blake2",
-            Self::ModexpExpOverflow => "modexp exp overflow",
-            Self::ModexpBaseOverflow => "modexp base overflow",
-            Self::ModexpModOverflow => "modexp mod overflow",
-            Self::Bn128FieldPointNotAMember => "field point not a member of bn128 curve",
-            Self::Bn128AffineGFailedToCreate => "failed to create affine g point for bn128 curve",
-            Self::Bn128PairLength => "bn128 invalid pair length",
-            Self::BlobInvalidInputLength => "invalid blob input length",
-            Self::BlobMismatchedVersion => "mismatched blob version",
-            Self::BlobVerifyKzgProofFailed => "verifying blob kzg proof failed",
-            Self::Other(s) => s,
-        };
-        f.write_str(s)
+        match self {
+            Self::OutOfGas => f.write_str("out of gas"),
+            Self::Blake2WrongLength { expected, actual } => write!(
+                f,
+                "wrong input length for blake2: expected {expected}, got {actual}"
+            ),
+            Self::Blake2WrongFinalIndicatorFlag { flag } => {
+                write!(f, "wrong final indicator flag for blake2: {flag}")
+            }
+            Self::ModexpExpOverflow => f.write_str("modexp exp overflow"),
+            Self::ModexpBaseOverflow => f.write_str("modexp base overflow"),
+            Self::ModexpModOverflow => f.write_str("modexp mod overflow"),
+            Self::ModexpLimitExceeded { input, len, max } => {
+                write!(f, "modexp {input} length {len} exceeds the limit of {max}")
+            }
+            Self::Bn128FieldPointNotAMember => {
+                f.write_str("field point not a member of bn128 curve")
+            }
+            Self::Bn128AffineGFailedToCreate => {
+                f.write_str("failed to create affine g point for bn128 curve")
+            }
+            Self::Bn128PairLength { len } => write!(f, "bn128 invalid pair input length: {len}"),
+            Self::Bn128PairLimitExceeded { pairs, max } => {
+                write!(f, "bn128 pair count {pairs} exceeds the limit of {max}")
+            }
+            Self::BlobInvalidInputLength { expected, actual } => write!(
+                f,
+                "invalid blob input length: expected {expected}, got {actual}"
+            ),
+            Self::BlobMismatchedVersion => f.write_str("mismatched blob version"),
+            Self::BlobVerifyKzgProofFailed => f.write_str("verifying blob kzg proof failed"),
+            Self::Disabled => f.write_str("precompile is disabled"),
+            Self::NotImplemented => f.write_str("precompile is not implemented"),
+            Self::Other(s) => f.write_str(s),
+        }
     }
 }
 
@@ -0,0 +1,147 @@
+use crate::{Bloom, ExecutionResult, Log, U256};
+use std::vec::Vec;
+
+/// Receipt of the executed transaction.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Receipt {
+    /// [EIP-2718] transaction type.
+    ///
+    /// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+    pub tx_type: u8,
+    /// Whether the transaction was successful, see [EIP-658].
+    ///
+    /// [EIP-658]: https://eips.ethereum.org/EIPS/eip-658
+    pub success: bool,
+    /// Gas used by this and all previous transactions in the block.
+    pub cumulative_gas_used: u64,
+    /// Logs emitted by the transaction.
+    pub logs: Vec<Log>,
+    /// Bloom filter of the logs.
+    pub logs_bloom: Bloom,
+    /// L1 data fee paid by the transaction. Only set for L2 chains.
+    pub l1_fee: Option<U256>,
+}
+
+impl Receipt {
+    /// Returns the gas used by this transaction, given the receipt of the previous transaction.
+    pub fn gas_used(&self, previous: Option<&Receipt>) -> u64 {
+        self.cumulative_gas_used - previous.map_or(0, |r| r.cumulative_gas_used)
+    }
+}
+
+/// Builds the receipts of a block from successive execution results.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReceiptBuilder {
+    receipts: Vec<Receipt>,
+    cumulative_gas_used: u64,
+    logs_bloom: Bloom,
+}
+
+impl ReceiptBuilder {
+    /// Creates a new builder for an empty block.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates the receipt of the next transaction in the block.
+    pub fn push(&mut self, tx_type: u8, result: ExecutionResult) -> &Receipt {
+        self.push_receipt(tx_type, result, None)
+    }
+
+    /// Creates the receipt of the next transaction in the block that paid the given L1 data fee.
+    pub fn push_with_l1_fee(
+        &mut self,
+        tx_type: u8,
+        result: ExecutionResult,
+        l1_fee: U256,
+    ) -> &Receipt {
+        self.push_receipt(tx_type, result, Some(l1_fee))
+    }
+
+    fn push_receipt(
+        &mut self,
+        tx_type: u8,
+        result: ExecutionResult,
+        l1_fee: Option<U256>,
+    ) -> &Receipt {
+        self.cumulative_gas_used += result.gas_used();
+        let success = result.is_success();
+        let logs = result.into_logs();
+        let mut logs_bloom = Bloom::ZERO;
+        for log in &logs {
+            logs_bloom.accrue_log(log);
+        }
+        self.logs_bloom.accrue_bloom(&logs_bloom);
+        self.receipts.push(Receipt {
+            tx_type,
+            success,
+            cumulative_gas_used: self.cumulative_gas_used,
+            logs,
+            logs_bloom,
+            l1_fee,
+        });
+        self.receipts.last().unwrap()
+    }
+
+    /// Returns the gas used by all transactions so far.
+    pub fn cumulative_gas_used(&self) -> u64 {
+        self.cumulative_gas_used
+    }
+
+    /// Returns the bloom filter of all logs in the block so far.
+    pub fn logs_bloom(&self) -> Bloom {
+        self.logs_bloom
+    }
+
+    /// Returns the receipts created so far.
+    pub fn receipts(&self) -> &[Receipt] {
+        &self.receipts
+    }
+
+    /// Consumes the builder and returns the receipts.
+    pub fn into_receipts(self) -> Vec<Receipt> {
+        self.receipts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Address, Bytes, HaltReason, Output, SuccessReason, B256};
+
+    #[test]
+    fn cumulative_gas_and_bloom() {
+        let log = Log::new_unchecked(Address::with_last_byte(1), vec![B256::ZERO], Bytes::new());
+        let mut builder = ReceiptBuilder::new();
+        builder.push(
+            2,
+            ExecutionResult::Success {
+                reason: SuccessReason::Stop,
+                gas_used: 21_000,
+                gas_refunded: 0,
+                logs: vec![log.clone()],
+                output: Output::Call(Bytes::new()),
+            },
+        );
+        let receipt = builder.push_with_l1_fee(
+            0,
+            ExecutionResult::Halt {
+                reason: HaltReason::OutOfGas(crate::OutOfGasError::Basic),
+                gas_used: 30_000,
+            },
+            U256::from(5),
+        );
+        assert!(!receipt.success);
+        assert_eq!(receipt.cumulative_gas_used, 51_000);
+        assert_eq!(receipt.l1_fee, Some(U256::from(5)));
+        assert_eq!(receipt.logs_bloom, Bloom::ZERO);
+
+        let receipts = builder.receipts();
+        assert!(receipts[0].success);
+        assert!(receipts[0].logs_bloom.contains_log(&log));
+        assert_eq!(receipts[1].gas_used(Some(&receipts[0])), 30_000);
+        assert!(builder.logs_bloom().contains_log(&log));
+        assert_eq!(builder.cumulative_gas_used(), 51_000);
+    }
+}
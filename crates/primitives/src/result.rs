@@ -269,6 +269,11 @@ pub enum InvalidTransaction {
         fee: Box<U256>,
         balance: Box<U256>,
     },
+    /// Transaction account does not have enough amount of ether left to cover the operator fee.
+    LackOfFundForOperatorFee {
+        fee: Box<U256>,
+        balance: Box<U256>,
+    },
     /// Overflow payment in transaction.
     OverflowPaymentInTransaction,
     /// Nonce overflows in transaction.
@@ -356,6 +361,9 @@ impl fmt::Display for InvalidTransaction {
             Self::LackOfFundForMaxFee { fee, balance } => {
                 write!(f, "lack of funds ({balance}) for max fee ({fee})")
             }
+            Self::LackOfFundForOperatorFee { fee, balance } => {
+                write!(f, "lack of funds ({balance}) for operator fee ({fee})")
+            }
             Self::OverflowPaymentInTransaction => {
                 write!(f, "overflow payment in transaction")
             }
@@ -467,6 +475,12 @@ pub enum HaltReason {
     EofAuxDataTooSmall,
     /// EOF Subroutine stack overflow
     EOFFunctionStackOverflow,
+    /// `SELFDESTRUCT` is disabled by `CfgEnv::disable_selfdestruct`.
+    SelfDestructDisabled,
+    /// Precompile is disabled by the chain configuration.
+    PrecompileDisabled,
+    /// Precompile is not implemented.
+    PrecompileNotImplemented,
 
     /* Optimism errors */
     #[cfg(feature = "optimism")]
@@ -480,6 +494,8 @@ pub enum OutOfGasError {
     Basic,
     // Tried to expand past REVM limit
     MemoryLimit,
+    // Tried to expand past REVM limit of memory expanded by all call frames combined
+    CumulativeMemoryLimit,
     // Basic OOG error from memory expansion
     Memory,
     // Precompile threw OOG error
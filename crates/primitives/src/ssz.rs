@@ -0,0 +1,474 @@
+//! [SSZ](https://github.com/ethereum/consensus-specs/blob/dev/ssz/simple-serialize.md) encoding of
+//! state and result types.
+//!
+//! Encodings follow these rules:
+//! * [`AccountInfo`] is a container of `(balance, nonce, code_hash)`. Bytecode is not encoded and
+//!   is `None` after decoding.
+//! * Storage is a list of `(slot, value)` pairs sorted by slot.
+//! * [`Log`] is a container of `(address, topics, data)`.
+//! * [`ExecutionResult`] and [`Output`] are unions with variants in declaration order.
+//! * [`SuccessReason`] and [`HaltReason`] are encoded as a single byte. New halt reasons are
+//!   appended so existing tags keep their values.
+
+use crate::{
+    AccountInfo, Address, Bytes, ExecutionResult, HaltReason, HashMap, Log, OutOfGasError, Output,
+    SuccessReason, B256, U256,
+};
+use ssz::split_union_bytes;
+pub use ssz::{Decode, DecodeError, Encode};
+use std::vec::Vec;
+
+/// SSZ representation of the [`Log`].
+type SszLog = (Address, Vec<B256>, Bytes);
+
+/// Encodes storage as a list of `(slot, value)` pairs sorted by slot.
+pub fn encode_storage(storage: &HashMap<U256, U256>) -> Vec<u8> {
+    let mut slots: Vec<(U256, U256)> = storage.iter().map(|(k, v)| (*k, *v)).collect();
+    slots.sort_unstable_by_key(|(slot, _)| *slot);
+    slots.as_ssz_bytes()
+}
+
+/// Decodes storage encoded with [`encode_storage`].
+///
+/// Slots must be strictly increasing, so every storage map has exactly one encoding.
+pub fn decode_storage(bytes: &[u8]) -> Result<HashMap<U256, U256>, DecodeError> {
+    let slots = Vec::<(U256, U256)>::from_ssz_bytes(bytes)?;
+    if let Some(pair) = slots.windows(2).find(|pair| pair[0].0 >= pair[1].0) {
+        return Err(DecodeError::BytesInvalid(format!(
+            "storage slot {} is not after slot {}",
+            pair[1].0, pair[0].0
+        )));
+    }
+    Ok(slots.into_iter().collect())
+}
+
+/// Encodes the list of logs.
+pub fn encode_logs(logs: &[Log]) -> Vec<u8> {
+    to_ssz_logs(logs).as_ssz_bytes()
+}
+
+/// Decodes the list of logs encoded with [`encode_logs`].
+pub fn decode_logs(bytes: &[u8]) -> Result<Vec<Log>, DecodeError> {
+    Ok(from_ssz_logs(Vec::<SszLog>::from_ssz_bytes(bytes)?))
+}
+
+fn to_ssz_logs(logs: &[Log]) -> Vec<SszLog> {
+    logs.iter()
+        .map(|log| {
+            (
+                log.address,
+                log.data.topics().to_vec(),
+                log.data.data.clone(),
+            )
+        })
+        .collect()
+}
+
+fn from_ssz_logs(logs: Vec<SszLog>) -> Vec<Log> {
+    logs.into_iter()
+        .map(|(address, topics, data)| Log::new_unchecked(address, topics, data))
+        .collect()
+}
+
+/// Appends the union selector and the union value to the buffer.
+fn append_union<T: Encode>(buf: &mut Vec<u8>, selector: u8, value: &T) {
+    buf.push(selector);
+    value.ssz_append(buf);
+}
+
+impl Encode for AccountInfo {
+    fn is_ssz_fixed_len() -> bool {
+        true
+    }
+
+    fn ssz_fixed_len() -> usize {
+        <(U256, u64, B256) as Encode>::ssz_fixed_len()
+    }
+
+    fn ssz_bytes_len(&self) -> usize {
+        <Self as Encode>::ssz_fixed_len()
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        (self.balance, self.nonce, self.code_hash).ssz_append(buf)
+    }
+}
+
+impl Decode for AccountInfo {
+    fn is_ssz_fixed_len() -> bool {
+        true
+    }
+
+    fn ssz_fixed_len() -> usize {
+        <(U256, u64, B256) as Decode>::ssz_fixed_len()
+    }
+
+    fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let (balance, nonce, code_hash) = <(U256, u64, B256)>::from_ssz_bytes(bytes)?;
+        Ok(Self {
+            balance,
+            nonce,
+            code_hash,
+            code: None,
+        })
+    }
+}
+
+impl Encode for SuccessReason {
+    fn is_ssz_fixed_len() -> bool {
+        true
+    }
+
+    fn ssz_fixed_len() -> usize {
+        1
+    }
+
+    fn ssz_bytes_len(&self) -> usize {
+        1
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        buf.push(match self {
+            Self::Stop => 0,
+            Self::Return => 1,
+            Self::SelfDestruct => 2,
+            Self::EofReturnContract => 3,
+        })
+    }
+}
+
+impl Decode for SuccessReason {
+    fn is_ssz_fixed_len() -> bool {
+        true
+    }
+
+    fn ssz_fixed_len() -> usize {
+        1
+    }
+
+    fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        Ok(match u8::from_ssz_bytes(bytes)? {
+            0 => Self::Stop,
+            1 => Self::Return,
+            2 => Self::SelfDestruct,
+            3 => Self::EofReturnContract,
+            i => {
+                return Err(DecodeError::BytesInvalid(format!(
+                    "invalid success reason {i}"
+                )))
+            }
+        })
+    }
+}
+
+impl Encode for HaltReason {
+    fn is_ssz_fixed_len() -> bool {
+        true
+    }
+
+    fn ssz_fixed_len() -> usize {
+        1
+    }
+
+    fn ssz_bytes_len(&self) -> usize {
+        1
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        buf.push(match self {
+            Self::OutOfGas(OutOfGasError::Basic) => 0,
+            Self::OutOfGas(OutOfGasError::MemoryLimit) => 1,
+            Self::OutOfGas(OutOfGasError::Memory) => 2,
+            Self::OutOfGas(OutOfGasError::Precompile) => 3,
+            Self::OutOfGas(OutOfGasError::InvalidOperand) => 4,
+            Self::OpcodeNotFound => 5,
+            Self::InvalidFEOpcode => 6,
+            Self::InvalidJump => 7,
+            Self::NotActivated => 8,
+            Self::StackUnderflow => 9,
+            Self::StackOverflow => 10,
+            Self::OutOfOffset => 11,
+            Self::CreateCollision => 12,
+            Self::PrecompileError => 13,
+            Self::NonceOverflow => 14,
+            Self::CreateContractSizeLimit => 15,
+            Self::CreateContractStartingWithEF => 16,
+            Self::CreateInitCodeSizeLimit => 17,
+            Self::OverflowPayment => 18,
+            Self::StateChangeDuringStaticCall => 19,
+            Self::CallNotAllowedInsideStatic => 20,
+            Self::OutOfFunds => 21,
+            Self::CallTooDeep => 22,
+            Self::EofAuxDataOverflow => 23,
+            Self::EofAuxDataTooSmall => 24,
+            Self::EOFFunctionStackOverflow => 25,
+            Self::OutOfGas(OutOfGasError::CumulativeMemoryLimit) => 26,
+            #[cfg(feature = "optimism")]
+            Self::FailedDeposit => 27,
+            Self::SelfDestructDisabled => 28,
+            Self::PrecompileDisabled => 29,
+            Self::PrecompileNotImplemented => 30,
+        });
+    }
+}
+
+impl Decode for HaltReason {
+    fn is_ssz_fixed_len() -> bool {
+        true
+    }
+
+    fn ssz_fixed_len() -> usize {
+        1
+    }
+
+    fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        Ok(match u8::from_ssz_bytes(bytes)? {
+            0 => Self::OutOfGas(OutOfGasError::Basic),
+            1 => Self::OutOfGas(OutOfGasError::MemoryLimit),
+            2 => Self::OutOfGas(OutOfGasError::Memory),
+            3 => Self::OutOfGas(OutOfGasError::Precompile),
+            4 => Self::OutOfGas(OutOfGasError::InvalidOperand),
+            5 => Self::OpcodeNotFound,
+            6 => Self::InvalidFEOpcode,
+            7 => Self::InvalidJump,
+            8 => Self::NotActivated,
+            9 => Self::StackUnderflow,
+            10 => Self::StackOverflow,
+            11 => Self::OutOfOffset,
+            12 => Self::CreateCollision,
+            13 => Self::PrecompileError,
+            14 => Self::NonceOverflow,
+            15 => Self::CreateContractSizeLimit,
+            16 => Self::CreateContractStartingWithEF,
+            17 => Self::CreateInitCodeSizeLimit,
+            18 => Self::OverflowPayment,
+            19 => Self::StateChangeDuringStaticCall,
+            20 => Self::CallNotAllowedInsideStatic,
+            21 => Self::OutOfFunds,
+            22 => Self::CallTooDeep,
+            23 => Self::EofAuxDataOverflow,
+            24 => Self::EofAuxDataTooSmall,
+            25 => Self::EOFFunctionStackOverflow,
+            26 => Self::OutOfGas(OutOfGasError::CumulativeMemoryLimit),
+            #[cfg(feature = "optimism")]
+            27 => Self::FailedDeposit,
+            28 => Self::SelfDestructDisabled,
+            29 => Self::PrecompileDisabled,
+            30 => Self::PrecompileNotImplemented,
+            i => {
+                return Err(DecodeError::BytesInvalid(format!(
+                    "invalid halt reason {i}"
+                )))
+            }
+        })
+    }
+}
+
+impl Encode for Output {
+    fn is_ssz_fixed_len() -> bool {
+        false
+    }
+
+    fn ssz_bytes_len(&self) -> usize {
+        1 + match self {
+            Self::Call(data) => data.ssz_bytes_len(),
+            Self::Create(data, address) => (data, address).ssz_bytes_len(),
+        }
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        match self {
+            Self::Call(data) => append_union(buf, 0, data),
+            Self::Create(data, address) => append_union(buf, 1, &(data, address)),
+        }
+    }
+}
+
+impl Decode for Output {
+    fn is_ssz_fixed_len() -> bool {
+        false
+    }
+
+    fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let (selector, body) = split_union_bytes(bytes)?;
+        match u8::from(selector) {
+            0 => Ok(Self::Call(Bytes::from_ssz_bytes(body)?)),
+            1 => {
+                let (data, address) = <(Bytes, Option<Address>)>::from_ssz_bytes(body)?;
+                Ok(Self::Create(data, address))
+            }
+            i => Err(DecodeError::UnionSelectorInvalid(i)),
+        }
+    }
+}
+
+impl Encode for ExecutionResult {
+    fn is_ssz_fixed_len() -> bool {
+        false
+    }
+
+    fn ssz_bytes_len(&self) -> usize {
+        let mut buf = Vec::new();
+        self.ssz_append(&mut buf);
+        buf.len()
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        match self {
+            Self::Success {
+                reason,
+                gas_used,
+                gas_refunded,
+                logs,
+                output,
+            } => append_union(
+                buf,
+                0,
+                &(reason, gas_used, gas_refunded, to_ssz_logs(logs), output),
+            ),
+            Self::Revert { gas_used, output } => append_union(buf, 1, &(gas_used, output)),
+            Self::Halt { reason, gas_used } => append_union(buf, 2, &(reason, gas_used)),
+        }
+    }
+}
+
+impl Decode for ExecutionResult {
+    fn is_ssz_fixed_len() -> bool {
+        false
+    }
+
+    fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let (selector, body) = split_union_bytes(bytes)?;
+        match u8::from(selector) {
+            0 => {
+                let (reason, gas_used, gas_refunded, logs, output) =
+                    <(SuccessReason, u64, u64, Vec<SszLog>, Output)>::from_ssz_bytes(body)?;
+                Ok(Self::Success {
+                    reason,
+                    gas_used,
+                    gas_refunded,
+                    logs: from_ssz_logs(logs),
+                    output,
+                })
+            }
+            1 => {
+                let (gas_used, output) = <(u64, Bytes)>::from_ssz_bytes(body)?;
+                Ok(Self::Revert { gas_used, output })
+            }
+            2 => {
+                let (reason, gas_used) = <(HaltReason, u64)>::from_ssz_bytes(body)?;
+                Ok(Self::Halt { reason, gas_used })
+            }
+            i => Err(DecodeError::UnionSelectorInvalid(i)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{address, b256, bytes, KECCAK_EMPTY};
+
+    #[test]
+    fn account_info_roundtrip() {
+        let info = AccountInfo {
+            balance: U256::from(100),
+            nonce: 3,
+            code_hash: KECCAK_EMPTY,
+            code: None,
+        };
+        let bytes = info.as_ssz_bytes();
+        assert_eq!(bytes.len(), 32 + 8 + 32);
+        assert_eq!(AccountInfo::from_ssz_bytes(&bytes).unwrap(), info);
+    }
+
+    #[test]
+    fn execution_result_roundtrip() {
+        let results = [
+            ExecutionResult::Success {
+                reason: SuccessReason::Return,
+                gas_used: 21_000,
+                gas_refunded: 100,
+                logs: vec![Log::new_unchecked(
+                    address!("1000000000000000000000000000000000000000"),
+                    vec![b256!(
+                        "0000000000000000000000000000000000000000000000000000000000000001"
+                    )],
+                    bytes!("0102"),
+                )],
+                output: Output::Create(bytes!("03"), Some(Address::ZERO)),
+            },
+            ExecutionResult::Revert {
+                gas_used: 1,
+                output: bytes!("04"),
+            },
+            ExecutionResult::Halt {
+                reason: HaltReason::OutOfGas(OutOfGasError::Memory),
+                gas_used: 2,
+            },
+            ExecutionResult::Halt {
+                reason: HaltReason::SelfDestructDisabled,
+                gas_used: 3,
+            },
+            ExecutionResult::Halt {
+                reason: HaltReason::PrecompileNotImplemented,
+                gas_used: 4,
+            },
+        ];
+        for result in results {
+            let bytes = result.as_ssz_bytes();
+            assert_eq!(result.ssz_bytes_len(), bytes.len());
+            assert_eq!(ExecutionResult::from_ssz_bytes(&bytes).unwrap(), result);
+        }
+    }
+
+    #[test]
+    fn halt_reason_tags() {
+        // Tags are part of the wire format and must not change.
+        let tags = [
+            (HaltReason::CreateCollision, 12),
+            (HaltReason::PrecompileError, 13),
+            (
+                HaltReason::OutOfGas(OutOfGasError::CumulativeMemoryLimit),
+                26,
+            ),
+            #[cfg(feature = "optimism")]
+            (HaltReason::FailedDeposit, 27),
+            (HaltReason::SelfDestructDisabled, 28),
+            (HaltReason::PrecompileDisabled, 29),
+            (HaltReason::PrecompileNotImplemented, 30),
+        ];
+        for (reason, tag) in tags {
+            assert_eq!(reason.as_ssz_bytes(), [tag]);
+            assert_eq!(HaltReason::from_ssz_bytes(&[tag]).unwrap(), reason);
+        }
+    }
+
+    #[test]
+    fn storage_roundtrip() {
+        let storage = HashMap::from([
+            (U256::from(2), U256::from(3)),
+            (U256::from(1), U256::from(4)),
+        ]);
+        let bytes = encode_storage(&storage);
+        assert_eq!(decode_storage(&bytes).unwrap(), storage);
+    }
+
+    #[test]
+    fn storage_rejects_unordered_slots() {
+        let unsorted = [
+            (U256::from(2), U256::from(3)),
+            (U256::from(1), U256::from(4)),
+        ];
+        let duplicate = [
+            (U256::from(1), U256::from(3)),
+            (U256::from(1), U256::from(4)),
+        ];
+        for slots in [unsorted, duplicate] {
+            assert!(matches!(
+                decode_storage(&slots.to_vec().as_ssz_bytes()),
+                Err(DecodeError::BytesInvalid(_))
+            ));
+        }
+    }
+}
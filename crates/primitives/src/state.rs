@@ -313,12 +313,18 @@ impl AccountInfo {
 
     pub fn from_bytecode(bytecode: Bytecode) -> Self {
         let hash = bytecode.hash_slow();
+        Self::from_bytecode_and_hash(bytecode, hash)
+    }
 
+    /// Creates account info with the given bytecode and its already known hash.
+    ///
+    /// Skips hashing of the bytecode, `code_hash` is not checked.
+    pub fn from_bytecode_and_hash(bytecode: Bytecode, code_hash: B256) -> Self {
         AccountInfo {
             balance: U256::ZERO,
             nonce: 1,
             code: Some(bytecode),
-            code_hash: hash,
+            code_hash,
         }
     }
 }
@@ -0,0 +1,130 @@
+//! Cache of analyzed bytecode shared between transactions.
+
+use crate::{
+    interpreter::analysis::to_analysed,
+    primitives::{Bytecode, LruCache, B256},
+};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Default number of bytecodes kept by the [AnalysisCache].
+pub const DEFAULT_ANALYSIS_CACHE_CAPACITY: usize = 4096;
+
+/// Cache of analyzed legacy bytecode keyed by code hash.
+///
+/// Legacy bytecode loaded from the database is analyzed for jump destinations every time a call
+/// frame executes it. The cache keeps the analysis across call frames and transactions, and is
+/// shared by all of its clones so it can be supplied to every [`Evm`](crate::Evm) of a block or
+/// of a node. Least recently used bytecode is evicted when the cache is full.
+#[derive(Clone, Debug)]
+pub struct AnalysisCache {
+    inner: Arc<Mutex<LruCache<B256, Bytecode>>>,
+}
+
+impl Default for AnalysisCache {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_ANALYSIS_CACHE_CAPACITY)
+    }
+}
+
+impl AnalysisCache {
+    /// Creates an empty cache with [DEFAULT_ANALYSIS_CACHE_CAPACITY].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty cache that keeps at most `capacity` bytecodes.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(LruCache::new(capacity))),
+        }
+    }
+
+    /// Returns the analyzed bytecode with the given code hash, analyzing and caching it if
+    /// it is not cached yet.
+    ///
+    /// Bytecode that is not raw legacy bytecode is returned as-is.
+    pub fn get_or_analyse(&self, code_hash: B256, bytecode: Bytecode) -> Bytecode {
+        if !matches!(bytecode, Bytecode::LegacyRaw(_)) {
+            return bytecode;
+        }
+        if let Some(analysed) = self.lock().get(&code_hash) {
+            return analysed.clone();
+        }
+        // Analysis is done without holding the lock.
+        let analysed = to_analysed(bytecode);
+        self.lock().insert(code_hash, analysed.clone());
+        analysed
+    }
+
+    /// Returns the number of cached bytecodes.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns `true` if no bytecode is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all cached bytecodes.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, LruCache<B256, Bytecode>> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{interpreter::opcode, primitives::Bytes};
+
+    fn bytecode(byte: u8) -> (B256, Bytecode) {
+        let bytecode = Bytecode::new_raw(Bytes::from(vec![opcode::JUMPDEST, byte]));
+        (bytecode.hash_slow(), bytecode)
+    }
+
+    #[test]
+    fn caches_analysis() {
+        let cache = AnalysisCache::with_capacity(2);
+        let (hash, raw) = bytecode(0);
+
+        let analysed = cache.get_or_analyse(hash, raw.clone());
+        assert!(analysed.legacy_jump_table().unwrap().is_valid(0));
+        assert_eq!(cache.len(), 1);
+
+        // Clones share the cache.
+        let clone = cache.clone();
+        assert_eq!(clone.get_or_analyse(hash, raw), analysed);
+        assert_eq!(cache.len(), 1);
+
+        // Analyzed bytecode is not cached.
+        let (other_hash, other) = bytecode(1);
+        clone.get_or_analyse(other_hash, to_analysed(other));
+        assert_eq!(cache.len(), 1);
+
+        cache.clear();
+        assert!(clone.is_empty());
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let cache = AnalysisCache::with_capacity(2);
+        let (hash0, raw0) = bytecode(0);
+        let (hash1, raw1) = bytecode(1);
+        let (hash2, raw2) = bytecode(2);
+
+        cache.get_or_analyse(hash0, raw0);
+        cache.get_or_analyse(hash1, raw1);
+        assert!(cache.lock().get(&hash0).is_some());
+        cache.get_or_analyse(hash2, raw2);
+
+        let inner = cache.lock();
+        assert_eq!(inner.len(), 2);
+        assert!(inner.contains_key(&hash0));
+        assert!(!inner.contains_key(&hash1));
+        assert!(inner.contains_key(&hash2));
+    }
+}
@@ -1,13 +1,17 @@
 use crate::{
     db::{Database, DatabaseRef, EmptyDB, WrapDatabaseRef},
     handler::register,
+    interpreter::Instruction,
     primitives::{
-        BlockEnv, CfgEnv, CfgEnvWithHandlerCfg, Env, EnvWithHandlerCfg, HandlerCfg, SpecId, TxEnv,
+        Address, BlockEnv, CfgEnv, CfgEnvWithHandlerCfg, Env, EnvWithHandlerCfg, HandlerCfg,
+        SpecId, TxEnv,
     },
-    Context, ContextWithHandlerCfg, Evm, Handler,
+    Context, ContextPrecompile, ContextWithHandlerCfg, Evm, Handler,
 };
+#[cfg(feature = "optimism")]
+use core::fmt;
 use core::marker::PhantomData;
-use std::boxed::Box;
+use std::{boxed::Box, sync::Arc};
 
 /// Evm Builder allows building or modifying EVM.
 /// Note that some of the methods that changes underlying structures
@@ -20,6 +24,34 @@ pub struct EvmBuilder<'a, BuilderStage, EXT, DB: Database> {
     phantom: PhantomData<BuilderStage>,
 }
 
+/// Error returned by [`EvmBuilder::try_build`] when the configuration is inconsistent.
+#[cfg(feature = "optimism")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EvmBuilderError {
+    /// Spec exists only on optimism but the handler is not optimism, so the optimism
+    /// specific behavior of the spec would not be applied.
+    OptimismSpecWithoutOptimismHandler(SpecId),
+    /// Optimism handler is used with a spec before [`SpecId::BEDROCK`].
+    OptimismHandlerWithPreBedrockSpec(SpecId),
+}
+
+#[cfg(feature = "optimism")]
+impl fmt::Display for EvmBuilderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::OptimismSpecWithoutOptimismHandler(spec_id) => {
+                write!(f, "optimism spec {spec_id:?} used without optimism handler")
+            }
+            Self::OptimismHandlerWithPreBedrockSpec(spec_id) => {
+                write!(f, "optimism handler used with pre-bedrock spec {spec_id:?}")
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "optimism", feature = "std"))]
+impl std::error::Error for EvmBuilderError {}
+
 /// First stage of the builder allows setting generic variables.
 /// Generic variables are database and external context.
 pub struct SetGenericStage;
@@ -305,6 +337,39 @@ impl<'a, BuilderStage, EXT, DB: Database> EvmBuilder<'a, BuilderStage, EXT, DB>
         Evm::new(self.context, self.handler)
     }
 
+    /// Builds the [`Evm`], checking that the handler and spec are compatible.
+    ///
+    /// Unlike [`EvmBuilder::build`], returns an error instead of building an [`Evm`] whose
+    /// behavior does not match the configured chain.
+    #[cfg(feature = "optimism")]
+    pub fn try_build(self) -> Result<Evm<'a, EXT, DB>, EvmBuilderError> {
+        self.validate()?;
+        Ok(self.build())
+    }
+
+    /// Checks that the handler and spec are compatible.
+    #[cfg(feature = "optimism")]
+    pub fn validate(&self) -> Result<(), EvmBuilderError> {
+        let spec_id = self.handler.spec_id();
+        let is_optimism_spec = matches!(
+            spec_id,
+            SpecId::BEDROCK
+                | SpecId::REGOLITH
+                | SpecId::CANYON
+                | SpecId::ECOTONE
+                | SpecId::FJORD
+                | SpecId::GRANITE
+        );
+        if self.handler.is_optimism() {
+            if !spec_id.is_enabled_in(SpecId::BEDROCK) {
+                return Err(EvmBuilderError::OptimismHandlerWithPreBedrockSpec(spec_id));
+            }
+        } else if is_optimism_spec {
+            return Err(EvmBuilderError::OptimismSpecWithoutOptimismHandler(spec_id));
+        }
+        Ok(())
+    }
+
     /// Register Handler that modifies the behavior of EVM.
     /// Check [`Handler`] for more information.
     ///
@@ -341,6 +406,92 @@ impl<'a, BuilderStage, EXT, DB: Database> EvmBuilder<'a, BuilderStage, EXT, DB>
         }
     }
 
+    /// Appends a handle register that adds the precompile at `address` to the precompiles of
+    /// the spec, replacing the precompile that is already there.
+    ///
+    /// When called, EvmBuilder will transition from SetGenericStage to HandlerStage.
+    pub fn append_precompile(
+        self,
+        address: Address,
+        precompile: impl Into<ContextPrecompile<DB>>,
+    ) -> EvmBuilder<'a, HandlerStage, EXT, DB>
+    where
+        DB: 'a,
+    {
+        let precompile = precompile.into();
+        self.append_handler_register_box(Box::new(
+            move |handler: &mut register::EvmHandler<'_, EXT, DB>| {
+                let load_precompiles = handler.pre_execution.load_precompiles.clone();
+                let precompile = precompile.clone();
+                handler.pre_execution.load_precompiles = Arc::new(move || {
+                    let mut precompiles = load_precompiles();
+                    precompiles.extend([(address, precompile.clone())]);
+                    precompiles
+                });
+            },
+        ))
+    }
+
+    /// Appends a handle register that removes the precompile at `address` from the
+    /// precompiles of the spec.
+    ///
+    /// When called, EvmBuilder will transition from SetGenericStage to HandlerStage.
+    pub fn remove_precompile(self, address: Address) -> EvmBuilder<'a, HandlerStage, EXT, DB> {
+        self.append_handler_register_box(Box::new(
+            move |handler: &mut register::EvmHandler<'_, EXT, DB>| {
+                let load_precompiles = handler.pre_execution.load_precompiles.clone();
+                handler.pre_execution.load_precompiles = Arc::new(move || {
+                    let mut precompiles = load_precompiles();
+                    precompiles.to_mut().remove(&address);
+                    precompiles
+                });
+            },
+        ))
+    }
+
+    /// Appends a handle register that replaces the instruction of the `opcode` in the
+    /// instruction table of the spec, e.g. to override `BLOCKHASH` or `COINBASE` in simulations.
+    ///
+    /// When called, EvmBuilder will transition from SetGenericStage to HandlerStage.
+    pub fn with_instruction(
+        self,
+        opcode: u8,
+        instruction: Instruction<Context<EXT, DB>>,
+    ) -> EvmBuilder<'a, HandlerStage, EXT, DB> {
+        self.append_handler_register_box(Box::new(
+            move |handler: &mut register::EvmHandler<'_, EXT, DB>| {
+                handler.instruction_table.insert(opcode, instruction);
+            },
+        ))
+    }
+
+    /// Appends a handle register that adds the instruction of the unassigned `opcode`, charging
+    /// `static_gas` before it is executed. See [`InstructionTables::insert_custom`].
+    ///
+    /// This allows prototyping L2 specific opcodes, e.g. in the unassigned `0xB0..=0xBF` range.
+    ///
+    /// When called, EvmBuilder will transition from SetGenericStage to HandlerStage.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the opcode is assigned.
+    ///
+    /// [`InstructionTables::insert_custom`]: crate::interpreter::opcode::InstructionTables::insert_custom
+    pub fn with_custom_opcode(
+        self,
+        opcode: u8,
+        static_gas: u64,
+        instruction: Instruction<Context<EXT, DB>>,
+    ) -> EvmBuilder<'a, HandlerStage, EXT, DB> {
+        self.append_handler_register_box(Box::new(
+            move |handler: &mut register::EvmHandler<'_, EXT, DB>| {
+                handler
+                    .instruction_table
+                    .insert_custom(opcode, static_gas, instruction);
+            },
+        ))
+    }
+
     /// Sets specification Id , that will mark the version of EVM.
     /// It represent the hard fork of ethereum.
     ///
@@ -412,6 +563,25 @@ impl<'a, BuilderStage, EXT, DB: Database> EvmBuilder<'a, BuilderStage, EXT, DB>
         self
     }
 
+    /// Sets the L1 block info used by the first transaction instead of loading it from the
+    /// database.
+    ///
+    /// See [`InnerEvmContext::set_l1_block_info`](crate::InnerEvmContext::set_l1_block_info).
+    #[cfg(feature = "optimism")]
+    pub fn with_l1_block_info(mut self, l1_block_info: crate::optimism::L1BlockInfo) -> Self {
+        self.context.evm.set_l1_block_info(l1_block_info);
+        self
+    }
+
+    /// Sets the cache of analyzed bytecode, shared with the other users of the cache.
+    ///
+    /// See [`AnalysisCache`](crate::AnalysisCache).
+    #[cfg(feature = "std")]
+    pub fn with_analysis_cache(mut self, analysis_cache: crate::AnalysisCache) -> Self {
+        self.context.evm.analysis_cache = Some(analysis_cache);
+        self
+    }
+
     /// Clears Environment of EVM.
     pub fn with_clear_env(mut self) -> Self {
         self.context.evm.env.clear();
@@ -448,8 +618,8 @@ mod test {
         },
         Context, ContextPrecompile, ContextStatefulPrecompile, Evm, InMemoryDB, InnerEvmContext,
     };
-    use revm_interpreter::{gas, Host, Interpreter};
-    use revm_precompile::PrecompileOutput;
+    use revm_interpreter::{gas, opcode, push, Host, Interpreter};
+    use revm_precompile::{Precompile, PrecompileOutput};
     use std::{cell::RefCell, rc::Rc, sync::Arc};
 
     /// Custom evm context
@@ -637,4 +807,147 @@ mod test {
 
         evm.transact().unwrap();
     }
+
+    #[test]
+    fn append_and_remove_precompile() {
+        let custom = Address::with_last_byte(0xff);
+        let ecrecover = Address::with_last_byte(1);
+
+        let evm = Evm::builder()
+            .with_empty_db()
+            .append_precompile(
+                custom,
+                Precompile::Standard(|_, _| Ok(PrecompileOutput::new(10, Bytes::new()))),
+            )
+            .remove_precompile(ecrecover)
+            .modify_tx_env(|tx| tx.transact_to = TxKind::Call(custom))
+            .build();
+
+        let precompiles = evm.handler.pre_execution.load_precompiles();
+        assert!(precompiles.contains(&custom));
+        assert!(!precompiles.contains(&ecrecover));
+
+        // precompiles are kept when the spec changes.
+        let mut evm = evm.modify().with_spec_id(SpecId::BERLIN).build();
+        let precompiles = evm.handler.pre_execution.load_precompiles();
+        assert!(precompiles.contains(&custom));
+        assert!(!precompiles.contains(&ecrecover));
+
+        let result = evm.transact().unwrap().result;
+        assert_eq!(result.gas_used(), 21_010);
+    }
+
+    #[test]
+    fn with_instruction() {
+        fn coinbase(interp: &mut Interpreter, _host: &mut impl Host) {
+            gas!(interp, gas::BASE);
+            push!(interp, U256::from(42));
+        }
+
+        // Returns the coinbase.
+        let code = Bytecode::new_raw(Bytes::from(vec![
+            opcode::COINBASE,
+            opcode::PUSH1,
+            0x00,
+            opcode::MSTORE,
+            opcode::PUSH1,
+            0x20,
+            opcode::PUSH1,
+            0x00,
+            opcode::RETURN,
+        ]));
+        let to_addr = Address::with_last_byte(0xff);
+
+        let evm = Evm::builder()
+            .with_db(InMemoryDB::default())
+            .modify_db(|db| {
+                db.insert_account_info(
+                    to_addr,
+                    AccountInfo::new(U256::ZERO, 0, code.hash_slow(), code),
+                )
+            })
+            .modify_tx_env(|tx| tx.transact_to = TxKind::Call(to_addr))
+            .with_instruction(opcode::COINBASE, coinbase)
+            .build();
+
+        // instruction is kept when the spec changes.
+        let mut evm = evm.modify().with_spec_id(SpecId::BERLIN).build();
+        let output = evm.transact().unwrap().result.into_output().unwrap();
+        assert_eq!(output, Bytes::from(U256::from(42).to_be_bytes_vec()));
+    }
+
+    #[test]
+    fn with_custom_opcode() {
+        const CUSTOM_OPCODE: u8 = 0xB0;
+
+        fn custom_instruction(interp: &mut Interpreter, _host: &mut impl Host) {
+            push!(interp, U256::from(1));
+        }
+
+        let code = Bytecode::new_raw(Bytes::from(vec![CUSTOM_OPCODE, opcode::STOP]));
+        let to_addr = Address::with_last_byte(0xff);
+
+        let mut evm = Evm::builder()
+            .with_db(InMemoryDB::default())
+            .modify_db(|db| {
+                db.insert_account_info(
+                    to_addr,
+                    AccountInfo::new(U256::ZERO, 0, code.hash_slow(), code),
+                )
+            })
+            .modify_tx_env(|tx| tx.transact_to = TxKind::Call(to_addr))
+            .with_custom_opcode(CUSTOM_OPCODE, 100, custom_instruction)
+            .build();
+
+        let result = evm.transact().unwrap().result;
+        assert!(result.is_success());
+        assert_eq!(result.gas_used(), 21_100);
+    }
+
+    #[test]
+    #[should_panic = "opcode 0x54 is already assigned"]
+    fn with_custom_opcode_rejects_assigned_opcode() {
+        fn custom_instruction(_interp: &mut Interpreter, _host: &mut impl Host) {}
+
+        Evm::builder()
+            .with_empty_db()
+            .with_custom_opcode(opcode::SLOAD, 100, custom_instruction);
+    }
+
+    #[cfg(feature = "optimism")]
+    #[test]
+    fn try_build_rejects_mismatched_optimism_config() {
+        use super::EvmBuilderError;
+        use crate::primitives::HandlerCfg;
+
+        let result = Evm::builder()
+            .with_empty_db()
+            .with_handler_cfg(HandlerCfg::new_with_optimism(SpecId::ECOTONE, false))
+            .try_build();
+        assert!(matches!(
+            result,
+            Err(EvmBuilderError::OptimismSpecWithoutOptimismHandler(
+                SpecId::ECOTONE
+            ))
+        ));
+
+        let result = Evm::builder()
+            .with_empty_db()
+            .optimism()
+            .with_spec_id(SpecId::LONDON)
+            .try_build();
+        assert!(matches!(
+            result,
+            Err(EvmBuilderError::OptimismHandlerWithPreBedrockSpec(
+                SpecId::LONDON
+            ))
+        ));
+
+        assert!(Evm::builder()
+            .with_empty_db()
+            .optimism()
+            .with_spec_id(SpecId::ECOTONE)
+            .try_build()
+            .is_ok());
+    }
 }
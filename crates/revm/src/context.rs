@@ -134,7 +134,7 @@ impl<EXT, DB: Database> Host for Context<EXT, DB> {
 
     fn load_account(&mut self, address: Address) -> Option<LoadAccountResult> {
         self.evm
-            .load_account_exist(address)
+            .load_account_delegated(address)
             .map_err(|e| self.evm.error = Err(e))
             .ok()
     }
@@ -153,6 +153,13 @@ impl<EXT, DB: Database> Host for Context<EXT, DB> {
             .ok()
     }
 
+    fn code_size(&mut self, address: Address) -> Option<(usize, bool)> {
+        self.evm
+            .code_size(address)
+            .map_err(|e| self.evm.error = Err(e))
+            .ok()
+    }
+
     fn code_hash(&mut self, address: Address) -> Option<(B256, bool)> {
         self.evm
             .code_hash(address)
@@ -1,5 +1,5 @@
 use revm_interpreter::CallValue;
-use revm_precompile::PrecompileErrors;
+use revm_precompile::{PrecompileError, PrecompileErrors};
 
 use super::inner_evm_context::InnerEvmContext;
 use crate::{
@@ -122,26 +122,36 @@ impl<DB: Database> EvmContext<DB> {
             return Ok(None);
         };
 
-        let mut result = InterpreterResult {
-            result: InstructionResult::Return,
-            gas,
-            output: Bytes::new(),
-        };
+        let mut result = InterpreterResult::new(InstructionResult::Return, Bytes::new(), gas);
 
         match outcome {
             Ok(output) => {
                 if result.gas.record_cost(output.gas_used) {
-                    result.result = InstructionResult::Return;
+                    result.result = if output.reverted {
+                        InstructionResult::Revert
+                    } else {
+                        InstructionResult::Return
+                    };
+                    result.gas.record_refund(output.gas_refunded);
                     result.output = output.bytes;
+                    if !output.reverted {
+                        for log in output.logs {
+                            self.journaled_state.log(log);
+                        }
+                    }
                 } else {
                     result.result = InstructionResult::PrecompileOOG;
                 }
             }
             Err(PrecompileErrors::Error(e)) => {
-                result.result = if e.is_oog() {
-                    InstructionResult::PrecompileOOG
-                } else {
-                    InstructionResult::PrecompileError
+                result.result = match e {
+                    PrecompileError::OutOfGas => InstructionResult::PrecompileOOG,
+                    PrecompileError::Disabled => InstructionResult::PrecompileDisabled,
+                    PrecompileError::NotImplemented => InstructionResult::PrecompileNotImplemented,
+                    e => {
+                        result.precompile_error = Some(e);
+                        InstructionResult::PrecompileError
+                    }
                 };
             }
             Err(PrecompileErrors::Fatal { msg }) => return Err(EVMError::Precompile(msg)),
@@ -159,11 +169,7 @@ impl<DB: Database> EvmContext<DB> {
 
         let return_result = |instruction_result: InstructionResult| {
             Ok(FrameOrResult::new_call_result(
-                InterpreterResult {
-                    result: instruction_result,
-                    gas,
-                    output: Bytes::new(),
-                },
+                InterpreterResult::new(instruction_result, Bytes::new(), gas),
                 inputs.return_memory_offset.clone(),
             ))
         };
@@ -221,8 +227,19 @@ impl<DB: Database> EvmContext<DB> {
                 .journaled_state
                 .load_code(inputs.bytecode_address, &mut self.inner.db)?;
 
-            let code_hash = account.info.code_hash();
-            let bytecode = account.info.code.clone().unwrap_or_default();
+            let mut code_hash = account.info.code_hash();
+            let mut bytecode = account.info.code.clone().unwrap_or_default();
+
+            // EIP-7702: execute the code of the delegated account. Delegated account is already
+            // warmed when the call gas was calculated.
+            if let Bytecode::Eip7702(eip7702) = &bytecode {
+                let (account, _) = self
+                    .inner
+                    .journaled_state
+                    .load_code(eip7702.address(), &mut self.inner.db)?;
+                code_hash = account.info.code_hash();
+                bytecode = account.info.code.clone().unwrap_or_default();
+            }
 
             // ExtDelegateCall is not allowed to call non-EOF contracts.
             if inputs.scheme.is_ext_delegate_call()
@@ -236,6 +253,12 @@ impl<DB: Database> EvmContext<DB> {
                 return return_result(InstructionResult::Stop);
             }
 
+            #[cfg(feature = "std")]
+            let bytecode = match &self.analysis_cache {
+                Some(cache) => cache.get_or_analyse(code_hash, bytecode),
+                None => bytecode,
+            };
+
             let contract =
                 Contract::new_with_context(inputs.input.clone(), bytecode, Some(code_hash), inputs);
             // Create interpreter and executes call and push new CallStackFrame.
@@ -256,11 +279,7 @@ impl<DB: Database> EvmContext<DB> {
     ) -> Result<FrameOrResult, EVMError<DB::Error>> {
         let return_error = |e| {
             Ok(FrameOrResult::new_create_result(
-                InterpreterResult {
-                    result: e,
-                    gas: Gas::new(inputs.gas_limit),
-                    output: Bytes::new(),
-                },
+                InterpreterResult::new(e, Bytes::new(), Gas::new(inputs.gas_limit)),
                 None,
             ))
         };
@@ -350,11 +369,7 @@ impl<DB: Database> EvmContext<DB> {
     ) -> Result<FrameOrResult, EVMError<DB::Error>> {
         let return_error = |e| {
             Ok(FrameOrResult::new_eofcreate_result(
-                InterpreterResult {
-                    result: e,
-                    gas: Gas::new(inputs.gas_limit),
-                    output: Bytes::new(),
-                },
+                InterpreterResult::new(e, Bytes::new(), Gas::new(inputs.gas_limit)),
                 None,
             ))
         };
@@ -518,9 +533,10 @@ pub(crate) mod test_utils {
                 journaled_state: JournaledState::new(SpecId::CANCUN, HashSet::new()),
                 db,
                 error: Ok(()),
-                valid_authorizations: Vec::new(),
                 #[cfg(feature = "optimism")]
                 l1_block_info: None,
+                #[cfg(feature = "std")]
+                analysis_cache: None,
             },
             precompiles: ContextPrecompiles::default(),
         }
@@ -534,9 +550,10 @@ pub(crate) mod test_utils {
                 journaled_state: JournaledState::new(SpecId::CANCUN, HashSet::new()),
                 db,
                 error: Ok(()),
-                valid_authorizations: Default::default(),
                 #[cfg(feature = "optimism")]
                 l1_block_info: None,
+                #[cfg(feature = "std")]
+                analysis_cache: None,
             },
             precompiles: ContextPrecompiles::default(),
         }
@@ -549,10 +566,14 @@ mod tests {
     use crate::primitives::U256;
     use crate::{
         db::{CacheDB, EmptyDB},
-        primitives::{address, Bytecode},
-        Frame, JournalEntry,
+        interpreter::SuccessOrHalt,
+        primitives::{
+            address, Bytecode, HaltReason, HashMap, Log, Precompile, PrecompileOutput,
+            PrecompileResult,
+        },
+        ContextPrecompile, ContextPrecompiles, Frame, JournalEntry,
     };
-    use std::boxed::Box;
+    use std::{boxed::Box, vec::Vec};
     use test_utils::*;
 
     // Tests that the `EVMContext::make_call_frame` function returns an error if the
@@ -638,4 +659,151 @@ mod tests {
         };
         assert_eq!(call_frame.return_memory_range, 0..0,);
     }
+
+    #[test]
+    fn test_make_call_frame_uses_analysis_cache() {
+        let mut cdb = CacheDB::new(EmptyDB::default());
+        let by = Bytecode::new_raw(Bytes::from(vec![0x60, 0x00, 0x60, 0x00]));
+        let code_hash = by.hash_slow();
+        let contract = address!("dead10000000000000000000000000000001dead");
+        cdb.insert_account_info(
+            contract,
+            crate::primitives::AccountInfo::new(U256::ZERO, 0, code_hash, by),
+        );
+        let cache = crate::AnalysisCache::new();
+        let mut evm_context =
+            create_cache_db_evm_context_with_balance(Box::default(), cdb, U256::ZERO);
+        evm_context.analysis_cache = Some(cache.clone());
+
+        let call_inputs = test_utils::create_mock_call_inputs(contract);
+        let Ok(FrameOrResult::Frame(Frame::Call(call_frame))) =
+            evm_context.make_call_frame(&call_inputs)
+        else {
+            panic!("Expected FrameOrResult::Frame(Frame::Call(..))");
+        };
+        assert_eq!(cache.len(), 1);
+        assert_eq!(
+            call_frame.frame_data.interpreter.contract.bytecode,
+            cache.get_or_analyse(code_hash, Bytecode::new_raw(Bytes::new()))
+        );
+    }
+
+    #[test]
+    fn test_make_call_frame_precompile_revert() {
+        fn reverting_precompile(_input: &Bytes, _gas_limit: u64) -> PrecompileResult {
+            Ok(
+                PrecompileOutput::new_reverted(10, Bytes::from_static(b"revert"))
+                    .with_gas_refund(5),
+            )
+        }
+
+        let env = Env::default();
+        let db = EmptyDB::default();
+        let mut context = test_utils::create_empty_evm_context(Box::new(env), db);
+        let precompile = address!("0000000000000000000000000000000000000100");
+        context.precompiles = ContextPrecompiles::from_precompiles(HashMap::from([(
+            precompile,
+            ContextPrecompile::Ordinary(Precompile::Standard(reverting_precompile)),
+        )]));
+        let mut call_inputs = test_utils::create_mock_call_inputs(precompile);
+        call_inputs.gas_limit = 100;
+        let res = context.make_call_frame(&call_inputs);
+        let Ok(FrameOrResult::Result(result)) = res else {
+            panic!("Expected FrameOrResult::Result");
+        };
+        let result = result.interpreter_result();
+        assert_eq!(result.result, InstructionResult::Revert);
+        assert_eq!(result.output, Bytes::from_static(b"revert"));
+        assert_eq!(result.gas.spent(), 10);
+        assert_eq!(result.gas.refunded(), 5);
+    }
+
+    #[test]
+    fn test_make_call_frame_precompile_logs() {
+        fn logging_precompile(_input: &Bytes, _gas_limit: u64) -> PrecompileResult {
+            let log = Log::new_unchecked(
+                address!("0000000000000000000000000000000000000100"),
+                Vec::new(),
+                Bytes::from_static(b"log"),
+            );
+            Ok(PrecompileOutput::new(10, Bytes::new()).with_logs(vec![log]))
+        }
+
+        let env = Env::default();
+        let db = EmptyDB::default();
+        let mut context = test_utils::create_empty_evm_context(Box::new(env), db);
+        let precompile = address!("0000000000000000000000000000000000000100");
+        context.precompiles = ContextPrecompiles::from_precompiles(HashMap::from([(
+            precompile,
+            ContextPrecompile::Ordinary(Precompile::Standard(logging_precompile)),
+        )]));
+        let mut call_inputs = test_utils::create_mock_call_inputs(precompile);
+        call_inputs.gas_limit = 100;
+        let res = context.make_call_frame(&call_inputs);
+        let Ok(FrameOrResult::Result(result)) = res else {
+            panic!("Expected FrameOrResult::Result");
+        };
+        assert_eq!(
+            result.interpreter_result().result,
+            InstructionResult::Return
+        );
+        assert_eq!(context.journaled_state.logs.len(), 1);
+        assert_eq!(context.journaled_state.logs[0].address, precompile);
+    }
+
+    #[test]
+    fn test_make_call_frame_precompile_error() {
+        let env = Env::default();
+        let db = EmptyDB::default();
+        let mut context = test_utils::create_empty_evm_context(Box::new(env), db);
+        let precompile = address!("0000000000000000000000000000000000000009");
+        context.precompiles = ContextPrecompiles::from_precompiles(HashMap::from([(
+            precompile,
+            ContextPrecompile::Ordinary(revm_precompile::blake2::FUN.1),
+        )]));
+        let mut call_inputs = test_utils::create_mock_call_inputs(precompile);
+        call_inputs.gas_limit = 100;
+        let Ok(FrameOrResult::Result(result)) = context.make_call_frame(&call_inputs) else {
+            panic!("Expected FrameOrResult::Result");
+        };
+        assert_eq!(
+            result.interpreter_result().result,
+            InstructionResult::PrecompileError
+        );
+        assert_eq!(
+            result.interpreter_result().precompile_error,
+            Some(PrecompileError::Blake2WrongLength {
+                expected: 213,
+                actual: 0
+            })
+        );
+    }
+
+    #[test]
+    fn test_make_call_frame_precompile_disabled() {
+        fn disabled(_: &Bytes, _: u64) -> PrecompileResult {
+            Err(PrecompileError::Disabled.into())
+        }
+
+        let env = Env::default();
+        let db = EmptyDB::default();
+        let mut context = test_utils::create_empty_evm_context(Box::new(env), db);
+        let precompile = address!("0000000000000000000000000000000000000009");
+        context.precompiles = ContextPrecompiles::from_precompiles(HashMap::from([(
+            precompile,
+            ContextPrecompile::Ordinary(Precompile::Standard(disabled)),
+        )]));
+        let call_inputs = test_utils::create_mock_call_inputs(precompile);
+        let Ok(FrameOrResult::Result(result)) = context.make_call_frame(&call_inputs) else {
+            panic!("Expected FrameOrResult::Result");
+        };
+        assert_eq!(
+            result.interpreter_result().result,
+            InstructionResult::PrecompileDisabled
+        );
+        assert_eq!(
+            SuccessOrHalt::from(result.interpreter_result().result),
+            SuccessOrHalt::Halt(HaltReason::PrecompileDisabled)
+        );
+    }
 }
@@ -13,7 +13,7 @@ use crate::{
     },
     JournalCheckpoint,
 };
-use std::{boxed::Box, sync::Arc, vec::Vec};
+use std::{boxed::Box, sync::Arc};
 
 /// EVM contexts contains data that EVM needs for execution.
 #[derive(Debug)]
@@ -27,11 +27,12 @@ pub struct InnerEvmContext<DB: Database> {
     pub db: DB,
     /// Error that happened during execution.
     pub error: Result<(), EVMError<DB::Error>>,
-    /// EIP-7702 Authorization list of accounts that needs to be cleared.
-    pub valid_authorizations: Vec<Address>,
     /// Used as temporary value holder to store L1 block info.
     #[cfg(feature = "optimism")]
     pub l1_block_info: Option<crate::optimism::L1BlockInfo>,
+    /// Cache of analyzed bytecode shared between transactions.
+    #[cfg(feature = "std")]
+    pub analysis_cache: Option<crate::AnalysisCache>,
 }
 
 impl<DB: Database + Clone> Clone for InnerEvmContext<DB>
@@ -44,9 +45,10 @@ where
             journaled_state: self.journaled_state.clone(),
             db: self.db.clone(),
             error: self.error.clone(),
-            valid_authorizations: self.valid_authorizations.clone(),
             #[cfg(feature = "optimism")]
             l1_block_info: self.l1_block_info.clone(),
+            #[cfg(feature = "std")]
+            analysis_cache: self.analysis_cache.clone(),
         }
     }
 }
@@ -58,9 +60,10 @@ impl<DB: Database> InnerEvmContext<DB> {
             journaled_state: JournaledState::new(SpecId::LATEST, HashSet::new()),
             db,
             error: Ok(()),
-            valid_authorizations: Default::default(),
             #[cfg(feature = "optimism")]
             l1_block_info: None,
+            #[cfg(feature = "std")]
+            analysis_cache: None,
         }
     }
 
@@ -72,9 +75,10 @@ impl<DB: Database> InnerEvmContext<DB> {
             journaled_state: JournaledState::new(SpecId::LATEST, HashSet::new()),
             db,
             error: Ok(()),
-            valid_authorizations: Default::default(),
             #[cfg(feature = "optimism")]
             l1_block_info: None,
+            #[cfg(feature = "std")]
+            analysis_cache: None,
         }
     }
 
@@ -88,12 +92,23 @@ impl<DB: Database> InnerEvmContext<DB> {
             journaled_state: self.journaled_state,
             db,
             error: Ok(()),
-            valid_authorizations: Default::default(),
             #[cfg(feature = "optimism")]
             l1_block_info: self.l1_block_info,
+            #[cfg(feature = "std")]
+            analysis_cache: self.analysis_cache,
         }
     }
 
+    /// Sets the L1 block info used by the next transaction.
+    ///
+    /// Loading of the L1 block info from the database is skipped when it is set. It is
+    /// cleared after the transaction, so it needs to be set again for every transaction.
+    #[cfg(feature = "optimism")]
+    #[inline]
+    pub fn set_l1_block_info(&mut self, l1_block_info: crate::optimism::L1BlockInfo) {
+        self.l1_block_info = Some(l1_block_info);
+    }
+
     /// Returns the configured EVM spec ID.
     #[inline]
     pub const fn spec_id(&self) -> SpecId {
@@ -169,6 +184,28 @@ impl<DB: Database> InnerEvmContext<DB> {
             .load_account_exist(address, &mut self.db)
     }
 
+    /// Load account from database to JournaledState, together with its delegated account if
+    /// the account code is an EIP-7702 delegation designator.
+    #[inline]
+    pub fn load_account_delegated(
+        &mut self,
+        address: Address,
+    ) -> Result<LoadAccountResult, EVMError<DB::Error>> {
+        let mut result = self.load_account_exist(address)?;
+        if !self.journaled_state.spec.is_enabled_in(PRAGUE) {
+            return Ok(result);
+        }
+        let (account, _) = self.journaled_state.load_code(address, &mut self.db)?;
+        if let Some(Bytecode::Eip7702(code)) = &account.info.code {
+            let delegated_address = code.address();
+            let (_, is_cold) = self
+                .journaled_state
+                .load_account(delegated_address, &mut self.db)?;
+            result.is_delegate_account_cold = Some(is_cold);
+        }
+        Ok(result)
+    }
+
     /// Return account balance and is_cold flag.
     #[inline]
     pub fn balance(&mut self, address: Address) -> Result<(U256, bool), EVMError<DB::Error>> {
@@ -195,17 +232,35 @@ impl<DB: Database> InnerEvmContext<DB> {
             })
     }
 
+    /// Get code size of address without loading the code bytes.
+    ///
+    /// In case of EOF account it will return the size of `EOF_MAGIC_BYTES`.
+    #[inline]
+    pub fn code_size(&mut self, address: Address) -> Result<(usize, bool), EVMError<DB::Error>> {
+        self.journaled_state
+            .load_code_metadata(address, &mut self.db)
+            .map(|(_, metadata, is_cold)| {
+                if metadata.is_eof {
+                    (EOF_MAGIC_BYTES.len(), is_cold)
+                } else {
+                    (metadata.len, is_cold)
+                }
+            })
+    }
+
     /// Get code hash of address.
     ///
     /// In case of EOF account it will return `EOF_MAGIC_HASH`
     /// (the hash of `0xEF00`).
     #[inline]
     pub fn code_hash(&mut self, address: Address) -> Result<(B256, bool), EVMError<DB::Error>> {
-        let (acc, is_cold) = self.journaled_state.load_code(address, &mut self.db)?;
+        let (acc, metadata, is_cold) = self
+            .journaled_state
+            .load_code_metadata(address, &mut self.db)?;
         if acc.is_empty() {
             return Ok((B256::ZERO, is_cold));
         }
-        if let Some(true) = acc.info.code.as_ref().map(|code| code.is_eof()) {
+        if metadata.is_eof {
             return Ok((EOF_MAGIC_HASH, is_cold));
         }
         Ok((acc.info.code_hash, is_cold))
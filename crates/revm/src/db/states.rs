@@ -4,6 +4,7 @@ pub mod bundle_state;
 pub mod cache;
 pub mod cache_account;
 pub mod changes;
+pub mod execution_outcome;
 pub mod plain_account;
 pub mod reverts;
 pub mod state;
@@ -18,6 +19,7 @@ pub use bundle_state::{BundleBuilder, BundleState, OriginalValuesKnown};
 pub use cache::CacheState;
 pub use cache_account::CacheAccount;
 pub use changes::{PlainStateReverts, PlainStorageChangeset, PlainStorageRevert, StateChangeset};
+pub use execution_outcome::ExecutionOutcome;
 pub use plain_account::{PlainAccount, StorageSlot, StorageWithOriginalValues};
 pub use reverts::{AccountRevert, RevertToSlot};
 pub use state::{DBBox, State, StateDBBox};
@@ -0,0 +1,180 @@
+use super::BundleState;
+use core::ops::RangeInclusive;
+use revm_interpreter::primitives::Bytes;
+use std::vec::Vec;
+
+/// Outcome of the execution of a range of consecutive blocks.
+///
+/// Contains the [BundleState] with one revert per block and the receipts and requests
+/// of every block in the range. Receipt type is generic so chains can use their own receipts.
+///
+/// The bundle is expected to be created with
+/// [`BundleRetention::Reverts`](crate::db::states::bundle_state::BundleRetention::Reverts)
+/// and to have its transitions merged once per block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExecutionOutcome<R> {
+    /// Bundle state with reverts of all blocks in the range.
+    pub bundle: BundleState,
+    /// Receipts of every block, indexed by the offset of the block from `first_block`.
+    pub receipts: Vec<Vec<R>>,
+    /// Encoded EIP-7685 requests of every block, indexed by the offset of the block from
+    /// `first_block`.
+    pub requests: Vec<Vec<Bytes>>,
+    /// Number of the first block in the range.
+    pub first_block: u64,
+}
+
+impl<R> Default for ExecutionOutcome<R> {
+    fn default() -> Self {
+        Self {
+            bundle: BundleState::default(),
+            receipts: Vec::new(),
+            requests: Vec::new(),
+            first_block: 0,
+        }
+    }
+}
+
+impl<R> ExecutionOutcome<R> {
+    /// Creates a new execution outcome.
+    pub fn new(
+        bundle: BundleState,
+        receipts: Vec<Vec<R>>,
+        requests: Vec<Vec<Bytes>>,
+        first_block: u64,
+    ) -> Self {
+        Self {
+            bundle,
+            receipts,
+            requests,
+            first_block,
+        }
+    }
+
+    /// Returns the number of blocks in the outcome.
+    pub fn len(&self) -> usize {
+        self.receipts.len()
+    }
+
+    /// Returns `true` if the outcome contains no blocks.
+    pub fn is_empty(&self) -> bool {
+        self.receipts.is_empty()
+    }
+
+    /// Returns the number of the last block in the range.
+    ///
+    /// For empty outcome this is the block before `first_block`.
+    pub fn last_block(&self) -> u64 {
+        (self.first_block + self.len() as u64).saturating_sub(1)
+    }
+
+    /// Returns the range of block numbers contained in the outcome.
+    pub fn block_range(&self) -> RangeInclusive<u64> {
+        self.first_block..=self.last_block()
+    }
+
+    /// Returns `true` if the block is contained in the outcome.
+    pub fn contains_block(&self, block_number: u64) -> bool {
+        !self.is_empty() && self.block_range().contains(&block_number)
+    }
+
+    /// Returns the receipts of the given block.
+    pub fn receipts_by_block(&self, block_number: u64) -> Option<&[R]> {
+        self.block_index(block_number)
+            .map(|index| self.receipts[index].as_slice())
+    }
+
+    /// Returns the requests of the given block.
+    pub fn requests_by_block(&self, block_number: u64) -> Option<&[Bytes]> {
+        self.block_index(block_number)
+            .and_then(|index| self.requests.get(index))
+            .map(Vec::as_slice)
+    }
+
+    /// Adds the outcome of the next block.
+    ///
+    /// `bundle` must contain the changes of that block only.
+    pub fn push_block(&mut self, bundle: BundleState, receipts: Vec<R>, requests: Vec<Bytes>) {
+        self.bundle.extend(bundle);
+        self.receipts.push(receipts);
+        self.requests.push(requests);
+    }
+
+    /// Extends the outcome with the outcome of the following blocks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` does not start at the block after the last block of this outcome.
+    pub fn extend(&mut self, other: Self) {
+        if self.is_empty() {
+            *self = other;
+            return;
+        }
+        assert_eq!(
+            other.first_block,
+            self.last_block() + 1,
+            "execution outcomes are not consecutive"
+        );
+        self.bundle.extend(other.bundle);
+        self.receipts.extend(other.receipts);
+        self.requests.extend(other.requests);
+    }
+
+    /// Reverts the state, receipts and requests so that `block_number` is the last block.
+    ///
+    /// Returns `false` if the block is not contained in the outcome.
+    pub fn revert_to(&mut self, block_number: u64) -> bool {
+        let Some(index) = self.block_index(block_number) else {
+            return false;
+        };
+        let new_len = index + 1;
+        let num_blocks = self.len() - new_len;
+        self.bundle.revert(num_blocks);
+        self.receipts.truncate(new_len);
+        self.requests.truncate(new_len);
+        true
+    }
+
+    fn block_index(&self, block_number: u64) -> Option<usize> {
+        if !self.contains_block(block_number) {
+            return None;
+        }
+        Some((block_number - self.first_block) as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm_interpreter::primitives::{AccountInfo, Address, U256};
+
+    #[test]
+    fn revert_to_block() {
+        let address = Address::with_last_byte(1);
+        let info = |balance: u64| AccountInfo {
+            balance: U256::from(balance),
+            ..Default::default()
+        };
+        let bundle = BundleState::builder(1..=3)
+            .state_present_account_info(address, info(3))
+            .revert_account_info(1, address, Some(None))
+            .revert_account_info(2, address, Some(Some(info(1))))
+            .revert_account_info(3, address, Some(Some(info(2))))
+            .build();
+        let mut outcome =
+            ExecutionOutcome::new(bundle, vec![vec![1u64], vec![2], vec![3]], vec![], 1);
+
+        assert_eq!(outcome.block_range(), 1..=3);
+        assert_eq!(outcome.receipts_by_block(2), Some(&[2u64][..]));
+        assert!(!outcome.revert_to(4));
+
+        assert!(outcome.revert_to(2));
+        assert_eq!(outcome.last_block(), 2);
+        assert_eq!(outcome.receipts_by_block(3), None);
+        assert_eq!(
+            outcome.bundle.account(&address).unwrap().info,
+            Some(info(2))
+        );
+    }
+}
@@ -4,7 +4,7 @@ use super::{
 };
 use crate::db::EmptyDB;
 use revm_interpreter::primitives::{
-    db::{Database, DatabaseCommit},
+    db::{CommitSummary, Database, DatabaseCommit},
     hash_map, Account, AccountInfo, Address, Bytecode, HashMap, B256, BLOCK_HASH_HISTORY, U256,
 };
 use std::{
@@ -295,6 +295,27 @@ impl<DB: Database> DatabaseCommit for State<DB> {
         let transitions = self.cache.apply_evm_state(evm_state);
         self.apply_transition(transitions);
     }
+
+    /// Commits the changes and returns their summary.
+    ///
+    /// Created accounts that were destroyed by an earlier transaction are reported as
+    /// [`CommitSummary::recreated`].
+    fn commit_with_summary(&mut self, evm_state: HashMap<Address, Account>) -> CommitSummary {
+        let mut summary = CommitSummary::from_changes(&evm_state);
+        summary.recreated = summary
+            .created
+            .iter()
+            .filter(|address| {
+                self.cache
+                    .accounts
+                    .get(*address)
+                    .is_some_and(|account| account.status.was_destroyed())
+            })
+            .copied()
+            .collect();
+        self.commit(evm_state);
+        summary
+    }
 }
 
 #[cfg(test)]
@@ -304,7 +325,61 @@ mod tests {
         states::{reverts::AccountInfoRevert, StorageSlot},
         AccountRevert, AccountStatus, BundleAccount, RevertToSlot,
     };
-    use revm_interpreter::primitives::keccak256;
+    use revm_interpreter::primitives::{keccak256, EvmStorageSlot};
+
+    #[test]
+    fn commit_summary_of_recreated_account() {
+        let recreated = Address::with_last_byte(1);
+        let created = Address::with_last_byte(2);
+        let mut state = State::builder().with_bundle_update().build();
+        state.insert_account_with_storage(
+            recreated,
+            AccountInfo {
+                nonce: 1,
+                ..Default::default()
+            },
+            HashMap::from_iter([(U256::from(1), U256::from(5))]),
+        );
+        state.cache.insert_not_existing(created);
+
+        // First transaction destroys the account.
+        let mut destroyed = Account::default();
+        destroyed.mark_touch();
+        destroyed.mark_selfdestruct();
+        let summary = state.commit_with_summary(HashMap::from_iter([(recreated, destroyed)]));
+        assert_eq!(summary.destroyed, vec![recreated]);
+        assert!(summary.recreated.is_empty());
+
+        // Second transaction creates it again, along with a new account.
+        let new_account = || {
+            let mut account = Account::from(AccountInfo {
+                nonce: 1,
+                ..Default::default()
+            });
+            account.storage = HashMap::from_iter([(
+                U256::from(2),
+                EvmStorageSlot::new_changed(U256::ZERO, U256::from(7)),
+            )]);
+            account.mark_touch();
+            account.mark_created();
+            account
+        };
+        let mut summary = state.commit_with_summary(HashMap::from_iter([
+            (recreated, new_account()),
+            (created, new_account()),
+        ]));
+        summary.created.sort_unstable();
+        assert_eq!(summary.created, vec![recreated, created]);
+        assert_eq!(summary.recreated, vec![recreated]);
+
+        // Storage from before the selfdestruct is gone.
+        let account = &state.cache.accounts[&recreated];
+        assert_eq!(account.status, AccountStatus::DestroyedChanged);
+        assert_eq!(
+            account.account.as_ref().unwrap().storage,
+            HashMap::from_iter([(U256::from(2), U256::from(7))])
+        );
+    }
 
     #[test]
     fn block_hash_cache() {
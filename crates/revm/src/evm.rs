@@ -6,8 +6,8 @@ use crate::{
         CallInputs, CreateInputs, EOFCreateInputs, Host, InterpreterAction, SharedMemory,
     },
     primitives::{
-        specification::SpecId, BlockEnv, CfgEnv, EVMError, EVMResult, EnvWithHandlerCfg,
-        ExecutionResult, HandlerCfg, ResultAndState, TxEnv, TxKind, EOF_MAGIC_BYTES,
+        specification::SpecId, Address, BlockEnv, CfgEnv, EVMError, EVMResult, EnvWithHandlerCfg,
+        ExecutionResult, HandlerCfg, HashSet, ResultAndState, TxEnv, TxKind, EOF_MAGIC_BYTES,
     },
     Context, ContextWithHandlerCfg, Frame, FrameOrResult, FrameResult,
 };
@@ -63,6 +63,7 @@ impl<'a, EXT, DB: Database> Evm<'a, EXT, DB> {
         handler: Handler<'a, Context<EXT, DB>, EXT, DB>,
     ) -> Evm<'a, EXT, DB> {
         context.evm.journaled_state.set_spec_id(handler.cfg.spec_id);
+        context.evm.precompiles = handler.pre_execution().load_precompiles();
         Evm { context, handler }
     }
 
@@ -78,11 +79,9 @@ impl<'a, EXT, DB: Database> Evm<'a, EXT, DB> {
         let mut call_stack: Vec<Frame> = Vec::with_capacity(1025);
         call_stack.push(first_frame);
 
-        #[cfg(feature = "memory_limit")]
         let mut shared_memory =
-            SharedMemory::new_with_memory_limit(self.context.evm.env.cfg.memory_limit);
-        #[cfg(not(feature = "memory_limit"))]
-        let mut shared_memory = SharedMemory::new();
+            SharedMemory::new_with_memory_limit(self.context.evm.env.cfg.memory_limit)
+                .with_cumulative_memory_limit(self.context.evm.env.cfg.cumulative_memory_limit);
 
         shared_memory.new_context();
 
@@ -293,9 +292,28 @@ impl<EXT, DB: Database> Evm<'_, EXT, DB> {
         &mut self.context.evm.env.block
     }
 
+    /// Returns the addresses of the precompiles installed by the handler.
+    ///
+    /// This reflects the precompile set that the `load_precompiles` handle loaded when the EVM was
+    /// built or its spec id was last modified, including any precompiles added or removed by
+    /// handle registers.
+    #[inline]
+    pub fn precompile_addresses(&self) -> HashSet<Address> {
+        self.context.evm.precompiles.addresses_set()
+    }
+
+    /// Returns `true` if the given address is a precompile in the currently installed set.
+    ///
+    /// See [`Evm::precompile_addresses`] for more information.
+    #[inline]
+    pub fn is_precompile(&self, address: &Address) -> bool {
+        self.context.evm.precompiles.contains(address)
+    }
+
     /// Modify spec id, this will create new EVM that matches this spec id.
     pub fn modify_spec_id(&mut self, spec_id: SpecId) {
         self.handler.modify_spec_id(spec_id);
+        self.context.evm.precompiles = self.handler.pre_execution().load_precompiles();
     }
 
     /// Returns internal database and external struct.
@@ -387,3 +405,136 @@ impl<EXT, DB: Database> Evm<'_, EXT, DB> {
         post_exec.output(ctx, result)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::InMemoryDB,
+        interpreter::opcode,
+        primitives::{AccountInfo, Bytecode, Bytes, HaltReason, OutOfGasError, U256},
+    };
+
+    #[test]
+    fn memory_limit() {
+        let address = Address::with_last_byte(0x42);
+        // Stores a word at offset 0x010000.
+        let code = Bytecode::new_raw(Bytes::from(vec![
+            opcode::PUSH1,
+            0x01,
+            opcode::PUSH3,
+            0x01,
+            0x00,
+            0x00,
+            opcode::MSTORE,
+        ]));
+        let mut db = InMemoryDB::new(EmptyDB::default());
+        db.insert_account_info(
+            address,
+            AccountInfo::new(U256::ZERO, 1, code.hash_slow(), code),
+        );
+
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .modify_tx_env(|tx| {
+                tx.transact_to = TxKind::Call(address);
+                tx.gas_limit = 1_000_000;
+            })
+            .build();
+        assert!(evm.transact().unwrap().result.is_success());
+
+        let mut evm = evm
+            .modify()
+            .modify_cfg_env(|cfg| cfg.memory_limit = 1024)
+            .build();
+        assert_eq!(
+            evm.transact().unwrap().result,
+            ExecutionResult::Halt {
+                reason: HaltReason::OutOfGas(OutOfGasError::MemoryLimit),
+                gas_used: 1_000_000,
+            }
+        );
+    }
+    #[test]
+    fn staticcalled_precompile_is_loaded() {
+        let address = Address::with_last_byte(0x42);
+        let identity = Address::with_last_byte(0x04);
+        // Static calls the identity precompile with an empty input.
+        let code = Bytecode::new_raw(Bytes::from(vec![
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH1,
+            0x04,
+            opcode::GAS,
+            opcode::STATICCALL,
+        ]));
+        let mut db = InMemoryDB::new(EmptyDB::default());
+        db.insert_account_info(
+            address,
+            AccountInfo::new(U256::ZERO, 1, code.hash_slow(), code),
+        );
+
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .modify_tx_env(|tx| {
+                tx.transact_to = TxKind::Call(address);
+                tx.gas_limit = 1_000_000;
+            })
+            .build();
+        let result = evm.transact().unwrap();
+        assert!(result.result.is_success());
+        assert!(result.state.contains_key(&identity));
+    }
+
+    #[test]
+    fn precompile_error_halt() {
+        // Calls blake2 with an empty input.
+        let mut evm = Evm::builder()
+            .with_db(EmptyDB::default())
+            .modify_tx_env(|tx| {
+                tx.transact_to = TxKind::Call(Address::with_last_byte(0x09));
+                tx.gas_limit = 1_000_000;
+            })
+            .build();
+        assert_eq!(
+            evm.transact().unwrap().result,
+            ExecutionResult::Halt {
+                reason: HaltReason::PrecompileError,
+                gas_used: 1_000_000,
+            }
+        );
+    }
+
+    #[test]
+    fn installed_precompiles() {
+        let custom = Address::with_last_byte(0xff);
+        let ecrecover = Address::with_last_byte(0x01);
+        let modexp = Address::with_last_byte(0x05);
+
+        let mut evm = Evm::builder()
+            .with_empty_db()
+            .with_spec_id(SpecId::BERLIN)
+            .append_precompile(
+                custom,
+                crate::precompile::Precompile::Standard(|_, _| {
+                    Ok(crate::precompile::PrecompileOutput::new(10, Bytes::new()))
+                }),
+            )
+            .remove_precompile(ecrecover)
+            .build();
+        assert!(evm.is_precompile(&custom));
+        assert!(evm.is_precompile(&modexp));
+        assert!(!evm.is_precompile(&ecrecover));
+        let addresses = evm.precompile_addresses();
+        assert!(addresses.contains(&custom));
+        assert!(!addresses.contains(&ecrecover));
+
+        // The set follows the spec while keeping the registered changes.
+        evm.modify_spec_id(SpecId::HOMESTEAD);
+        assert!(evm.is_precompile(&custom));
+        assert!(!evm.is_precompile(&modexp));
+        assert!(!evm.is_precompile(&ecrecover));
+    }
+}
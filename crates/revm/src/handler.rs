@@ -1,6 +1,7 @@
 // Modules.
 mod handle_types;
 pub mod mainnet;
+pub mod operator_fee;
 pub mod register;
 
 // Exports.
@@ -212,11 +212,7 @@ mod tests {
         env.tx.gas_limit = 100;
 
         let mut first_frame = FrameResult::Call(CallOutcome::new(
-            InterpreterResult {
-                result: instruction_result,
-                output: Bytes::new(),
-                gas,
-            },
+            InterpreterResult::new(instruction_result, Bytes::new(), gas),
             0..0,
         ));
         frame_return_with_refund_flag::<CancunSpec>(&env, &mut first_frame, true);
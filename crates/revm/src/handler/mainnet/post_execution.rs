@@ -1,8 +1,8 @@
 use crate::{
     interpreter::{Gas, SuccessOrHalt},
     primitives::{
-        db::Database, Bytecode, EVMError, ExecutionResult, ResultAndState, Spec, SpecId::LONDON,
-        KECCAK_EMPTY, U256,
+        db::Database, BaseFeePolicy, EVMError, ExecutionResult, ResultAndState, Spec,
+        SpecId::LONDON, U256,
     },
     Context, FrameResult,
 };
@@ -22,9 +22,11 @@ pub fn clear<EXT, DB: Database>(context: &mut Context<EXT, DB>) {
     // clear error and journaled state.
     let _ = context.evm.take_error();
     context.evm.inner.journaled_state.clear();
-    // Clear valid authorizations after each transaction.
-    // If transaction is valid they are consumed in `output` handler.
-    context.evm.inner.valid_authorizations.clear();
+    // L1 block info is fetched again for the next transaction.
+    #[cfg(feature = "optimism")]
+    {
+        context.evm.inner.l1_block_info = None;
+    }
 }
 
 /// Reward beneficiary with gas fee.
@@ -36,13 +38,17 @@ pub fn reward_beneficiary<SPEC: Spec, EXT, DB: Database>(
     let beneficiary = context.evm.env.block.coinbase;
     let effective_gas_price = context.evm.env.effective_gas_price();
 
+    let base_fee_policy = context.evm.env.cfg.base_fee_policy;
+
     // transfer fee to coinbase/beneficiary.
-    // EIP-1559 discard basefee for coinbase transfer. Basefee amount of gas is discarded.
-    let coinbase_gas_price = if SPEC::enabled(LONDON) {
-        effective_gas_price.saturating_sub(context.evm.env.block.basefee)
-    } else {
-        effective_gas_price
-    };
+    // EIP-1559 discard basefee for coinbase transfer. Basefee amount of gas is discarded,
+    // unless the base fee policy sends it to the coinbase.
+    let coinbase_gas_price =
+        if SPEC::enabled(LONDON) && base_fee_policy != BaseFeePolicy::ToCoinbase {
+            effective_gas_price.saturating_sub(context.evm.env.block.basefee)
+        } else {
+            effective_gas_price
+        };
 
     let (coinbase_account, _) = context
         .evm
@@ -56,6 +62,24 @@ pub fn reward_beneficiary<SPEC: Spec, EXT, DB: Database>(
         .balance
         .saturating_add(coinbase_gas_price * U256::from(gas.spent() - gas.refunded() as u64));
 
+    // credit the discarded basefee to the fee vault if it is set.
+    let fee_vault = base_fee_policy
+        .fee_vault()
+        .filter(|_| SPEC::enabled(LONDON));
+    if let Some(fee_vault) = fee_vault {
+        let basefee = context.evm.env.block.basefee;
+        let (fee_vault_account, _) = context
+            .evm
+            .inner
+            .journaled_state
+            .load_account(fee_vault, &mut context.evm.inner.db)?;
+        fee_vault_account.mark_touch();
+        fee_vault_account.info.balance = fee_vault_account
+            .info
+            .balance
+            .saturating_add(basefee * U256::from(gas.spent() - gas.refunded() as u64));
+    }
+
     Ok(())
 }
 
@@ -96,16 +120,7 @@ pub fn output<EXT, DB: Database>(
     let instruction_result = result.into_interpreter_result();
 
     // reset journal and return present state.
-    let (mut state, logs) = context.evm.journaled_state.finalize();
-
-    // clear code of authorized accounts.
-    for authorized in core::mem::take(&mut context.evm.inner.valid_authorizations).into_iter() {
-        let account = state
-            .get_mut(&authorized)
-            .expect("Authorized account must exist");
-        account.info.code = Some(Bytecode::default());
-        account.info.code_hash = KECCAK_EMPTY;
-    }
+    let (state, logs) = context.evm.journaled_state.finalize();
 
     let result = match instruction_result.result.into() {
         SuccessOrHalt::Success(reason) => ExecutionResult::Success {
@@ -134,3 +149,71 @@ pub fn output<EXT, DB: Database>(
 
     Ok(ResultAndState { result, state })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::InMemoryDB,
+        primitives::{AccountInfo, Address, TxKind},
+        Evm,
+    };
+
+    /// Executes a transfer paying 15 wei per gas with a base fee of 10 wei and returns the
+    /// balances of the coinbase and of the vault.
+    fn fee_balances(base_fee_policy: BaseFeePolicy, vault: Address) -> (U256, U256) {
+        let caller = Address::with_last_byte(1);
+        let coinbase = Address::with_last_byte(2);
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            caller,
+            AccountInfo::from_balance(U256::from(10).pow(U256::from(18))),
+        );
+
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .modify_cfg_env(|cfg| cfg.base_fee_policy = base_fee_policy)
+            .modify_block_env(|block| {
+                block.coinbase = coinbase;
+                block.basefee = U256::from(10);
+            })
+            .modify_tx_env(|tx| {
+                tx.caller = caller;
+                tx.transact_to = TxKind::Call(Address::with_last_byte(3));
+                tx.gas_limit = 21_000;
+                tx.gas_price = U256::from(15);
+            })
+            .build();
+        let ResultAndState { result, state } = evm.transact().unwrap();
+        assert_eq!(result.gas_used(), 21_000);
+
+        let balance = |address| {
+            state
+                .get(&address)
+                .map(|account| account.info.balance)
+                .unwrap_or_default()
+        };
+        (balance(coinbase), balance(vault))
+    }
+
+    #[test]
+    fn base_fee_policy() {
+        let vault = Address::with_last_byte(4);
+        let gas_used = U256::from(21_000);
+        let priority_fee = U256::from(5) * gas_used;
+        let base_fee = U256::from(10) * gas_used;
+
+        assert_eq!(
+            fee_balances(BaseFeePolicy::Burn, vault),
+            (priority_fee, U256::ZERO)
+        );
+        assert_eq!(
+            fee_balances(BaseFeePolicy::ToCoinbase, vault),
+            (priority_fee + base_fee, U256::ZERO)
+        );
+        assert_eq!(
+            fee_balances(BaseFeePolicy::ToVault(vault), vault),
+            (priority_fee, base_fee)
+        );
+    }
+}
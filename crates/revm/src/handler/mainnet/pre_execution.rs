@@ -6,13 +6,12 @@ use crate::{
     precompile::PrecompileSpecId,
     primitives::{
         db::Database,
-        Account, EVMError, Env, Spec,
+        Account, Bytecode, EVMError, Env, Spec,
         SpecId::{CANCUN, PRAGUE, SHANGHAI},
-        TxKind, BLOCKHASH_STORAGE_ADDRESS, KECCAK_EMPTY, U256,
+        TxKind, BLOCKHASH_STORAGE_ADDRESS, U256,
     },
     Context, ContextPrecompiles,
 };
-use std::vec::Vec;
 
 /// Main precompile load
 #[inline]
@@ -49,10 +48,9 @@ pub fn load_accounts<SPEC: Spec, EXT, DB: Database>(
             .insert(BLOCKHASH_STORAGE_ADDRESS);
     }
 
-    // EIP-7702. Load bytecode to authorized accounts.
+    // EIP-7702. Set the delegation designators of the authorities.
     if SPEC::enabled(PRAGUE) {
         if let Some(authorization_list) = context.evm.inner.env.tx.authorization_list.as_ref() {
-            let mut valid_auths = Vec::with_capacity(authorization_list.len());
             for authorization in authorization_list.recovered_iter() {
                 // 1. recover authority and authorized addresses.
                 let Some(authority) = authorization.authority() else {
@@ -66,54 +64,40 @@ pub fn load_accounts<SPEC: Spec, EXT, DB: Database>(
                     continue;
                 }
 
-                // warm authority account and check nonce.
+                // 3. Add authority to accessed_addresses.
                 let (authority_acc, _) = context
                     .evm
                     .inner
                     .journaled_state
-                    .load_account(authority, &mut context.evm.inner.db)?;
+                    .load_code(authority, &mut context.evm.inner.db)?;
 
-                // 3. Verify that the code of authority is empty.
-                // In case of multiple same authorities this step will skip loading of
-                // authorized account.
-                if authority_acc.info.code_hash() != KECCAK_EMPTY {
-                    continue;
+                // 4. Verify the code of authority is either empty or already delegated.
+                if let Some(code) = &authority_acc.info.code {
+                    if !code.is_empty() && !code.is_eip7702() {
+                        continue;
+                    }
                 }
 
-                // 4. If nonce list item is length one, verify the nonce of authority is equal to nonce.
+                // 5. If nonce is set, verify the nonce of authority is equal to nonce.
                 if let Some(nonce) = authorization.nonce() {
                     if nonce != authority_acc.info.nonce {
                         continue;
                     }
                 }
 
-                // warm code account and get the code.
-                // 6. Add the authority account to accessed_addresses
-                let (account, _) = context
-                    .evm
-                    .inner
-                    .journaled_state
-                    .load_code(authorization.address, &mut context.evm.inner.db)?;
-                let code = account.info.code.clone();
-                let code_hash = account.info.code_hash;
-
-                // If code is empty no need to set code or add it to valid
-                // authorizations, as it is a noop operation.
-                if code_hash == KECCAK_EMPTY {
-                    continue;
-                }
-
-                // 5. Set the code of authority to code associated with address.
-                context.evm.inner.journaled_state.set_code_with_hash(
-                    authority,
-                    code.unwrap_or_default(),
-                    code_hash,
-                );
+                // 6. Set the code of authority to the delegation designator of the address.
+                // Delegation to the zero address clears the code.
+                let code = if authorization.address.is_zero() {
+                    Bytecode::default()
+                } else {
+                    Bytecode::new_eip7702(authorization.address)
+                };
+                let journaled_state = &mut context.evm.inner.journaled_state;
+                journaled_state.set_code(authority, code);
 
-                valid_auths.push(authority);
+                // 7. Increase the nonce of authority.
+                journaled_state.inc_nonce(authority);
             }
-
-            context.evm.inner.valid_authorizations = valid_auths;
         }
     }
 
@@ -164,3 +148,77 @@ pub fn deduct_caller<SPEC: Spec, EXT, DB: Database>(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        db::{EmptyDB, InMemoryDB},
+        interpreter::opcode,
+        primitives::{
+            AccountInfo, Address, Authorization, Bytecode, Bytes, ExecutionResult,
+            RecoveredAuthorization, SpecId, TxKind, U256,
+        },
+        Evm,
+    };
+
+    #[test]
+    fn eip7702_delegation() {
+        let authority = Address::with_last_byte(0xaa);
+        let delegate = Address::with_last_byte(0xdd);
+        let mut db = InMemoryDB::new(EmptyDB::default());
+        // Returns 32 bytes with value 42.
+        let code = Bytecode::new_raw(Bytes::from(vec![
+            opcode::PUSH1,
+            0x2a,
+            opcode::PUSH1,
+            0x00,
+            opcode::MSTORE,
+            opcode::PUSH1,
+            0x20,
+            opcode::PUSH1,
+            0x00,
+            opcode::RETURN,
+        ]));
+        db.insert_account_info(
+            delegate,
+            AccountInfo::new(U256::ZERO, 1, code.hash_slow(), code),
+        );
+
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_spec_id(SpecId::PRAGUE)
+            .modify_tx_env(|tx| {
+                tx.caller = Address::with_last_byte(1);
+                tx.gas_limit = 100_000;
+                tx.transact_to = TxKind::Call(authority);
+                tx.authorization_list = Some(
+                    vec![RecoveredAuthorization::new_unchecked(
+                        Authorization {
+                            chain_id: 0,
+                            address: delegate,
+                            nonce: None.into(),
+                        },
+                        Some(authority),
+                    )]
+                    .into(),
+                );
+            })
+            .build();
+
+        let ExecutionResult::Success { output, .. } = evm.transact_commit().unwrap() else {
+            panic!("Expected successful call of the delegated code");
+        };
+        assert_eq!(
+            output.into_data(),
+            Bytes::from(U256::from(42).to_be_bytes_vec())
+        );
+
+        // Delegation persists after the transaction.
+        let authority = &evm.db().accounts[&authority].info;
+        assert_eq!(
+            authority.code_hash,
+            Bytecode::new_eip7702(delegate).hash_slow()
+        );
+        assert_eq!(authority.nonce, 1);
+    }
+}
@@ -17,13 +17,13 @@ pub fn validate_env<SPEC: Spec, DB: Database>(env: &Env) -> Result<(), EVMError<
 pub fn validate_tx_against_state<SPEC: Spec, EXT, DB: Database>(
     context: &mut Context<EXT, DB>,
 ) -> Result<(), EVMError<DB::Error>> {
-    // load acc
+    // load acc, code is needed to allow EIP-7702 delegated callers.
     let tx_caller = context.evm.env.tx.caller;
     let (caller_account, _) = context
         .evm
         .inner
         .journaled_state
-        .load_account(tx_caller, &mut context.evm.inner.db)?;
+        .load_code(tx_caller, &mut context.evm.inner.db)?;
 
     context
         .evm
@@ -0,0 +1,205 @@
+//! Flat per-transaction operator fee.
+//!
+//! The fee is charged from the caller after the gas is deducted and is transferred to the
+//! configured recipient. It is not refunded, even if the transaction reverts.
+
+use crate::{
+    handler::register::{EvmHandler, HandleRegisterBox},
+    primitives::{db::Database, Address, EVMError, InvalidTransaction, U256},
+    Context,
+};
+use std::{boxed::Box, sync::Arc};
+
+/// Amount of the operator fee.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OperatorFeeAmount {
+    /// Fixed amount.
+    Fixed(U256),
+    /// Amount stored in the storage slot of a system contract.
+    ///
+    /// Slot is read directly from the database, so it is not warmed.
+    Storage {
+        /// Address of the system contract.
+        address: Address,
+        /// Storage slot containing the fee.
+        slot: U256,
+    },
+}
+
+/// Flat fee charged from the caller of every transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OperatorFee {
+    /// Recipient of the fee.
+    pub recipient: Address,
+    /// Amount of the fee.
+    pub amount: OperatorFeeAmount,
+}
+
+impl OperatorFee {
+    /// Creates a new operator fee with a fixed amount.
+    pub fn fixed(recipient: Address, amount: U256) -> Self {
+        Self {
+            recipient,
+            amount: OperatorFeeAmount::Fixed(amount),
+        }
+    }
+
+    /// Creates a new operator fee with the amount read from the storage slot.
+    pub fn from_storage(recipient: Address, address: Address, slot: U256) -> Self {
+        Self {
+            recipient,
+            amount: OperatorFeeAmount::Storage { address, slot },
+        }
+    }
+
+    /// Returns the handle register that charges the fee after the caller is deducted.
+    pub fn handle_register<'a, EXT: 'a, DB: Database + 'a>(self) -> HandleRegisterBox<'a, EXT, DB> {
+        Box::new(move |handler: &mut EvmHandler<'_, EXT, DB>| {
+            let prev_handle = handler.pre_execution.deduct_caller.clone();
+            handler.pre_execution.deduct_caller = Arc::new(move |context| {
+                prev_handle(context)?;
+                self.charge(context)
+            });
+        })
+    }
+
+    /// Transfers the fee from the caller to the recipient.
+    pub fn charge<EXT, DB: Database>(
+        &self,
+        context: &mut Context<EXT, DB>,
+    ) -> Result<(), EVMError<DB::Error>> {
+        let fee = match self.amount {
+            OperatorFeeAmount::Fixed(amount) => amount,
+            OperatorFeeAmount::Storage { address, slot } => context
+                .evm
+                .inner
+                .db
+                .storage(address, slot)
+                .map_err(EVMError::Database)?,
+        };
+        if fee.is_zero() {
+            return Ok(());
+        }
+
+        let inner = &mut context.evm.inner;
+        let (caller_account, _) = inner
+            .journaled_state
+            .load_account(inner.env.tx.caller, &mut inner.db)?;
+        if fee > caller_account.info.balance {
+            if inner.env.cfg.is_balance_check_disabled() {
+                // Add the fee to the balance, as it is done for the transaction cost.
+                caller_account.info.balance = fee;
+            } else {
+                return Err(EVMError::Transaction(
+                    InvalidTransaction::LackOfFundForOperatorFee {
+                        fee: fee.into(),
+                        balance: caller_account.info.balance.into(),
+                    },
+                ));
+            }
+        }
+        caller_account.info.balance -= fee;
+        caller_account.mark_touch();
+
+        let (recipient_account, _) = inner
+            .journaled_state
+            .load_account(self.recipient, &mut inner.db)?;
+        recipient_account.info.balance = recipient_account.info.balance.saturating_add(fee);
+        recipient_account.mark_touch();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::{EmptyDB, InMemoryDB},
+        primitives::{AccountInfo, TxKind},
+        Evm,
+    };
+
+    #[test]
+    fn charge_operator_fee() {
+        let caller = Address::with_last_byte(1);
+        let recipient = Address::with_last_byte(2);
+        let system_contract = Address::with_last_byte(3);
+
+        let mut db = InMemoryDB::new(EmptyDB::default());
+        db.insert_account_info(
+            caller,
+            AccountInfo {
+                balance: U256::from(1_000),
+                ..Default::default()
+            },
+        );
+        db.insert_account_storage(system_contract, U256::from(7), U256::from(300))
+            .unwrap();
+
+        for (fee, charged) in [
+            (OperatorFee::fixed(recipient, U256::from(100)), 100),
+            (
+                OperatorFee::from_storage(recipient, system_contract, U256::from(7)),
+                300,
+            ),
+        ] {
+            let mut evm = Evm::builder()
+                .with_db(db.clone())
+                .modify_tx_env(|tx| {
+                    tx.caller = caller;
+                    tx.transact_to = TxKind::Call(Address::with_last_byte(4));
+                    tx.gas_limit = 21_000;
+                })
+                .append_handler_register_box(fee.handle_register())
+                .build();
+
+            let state = evm.transact().unwrap().state;
+            assert_eq!(state[&caller].info.balance, U256::from(1_000 - charged));
+            assert_eq!(state[&recipient].info.balance, U256::from(charged));
+        }
+
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .modify_tx_env(|tx| {
+                tx.caller = caller;
+                tx.gas_limit = 21_000;
+            })
+            .append_handler_register_box(
+                OperatorFee::fixed(recipient, U256::from(2_000)).handle_register(),
+            )
+            .build();
+        assert!(matches!(
+            evm.transact(),
+            Err(EVMError::Transaction(
+                InvalidTransaction::LackOfFundForOperatorFee { .. }
+            ))
+        ));
+    }
+
+    #[cfg(feature = "optional_balance_check")]
+    #[test]
+    fn charge_operator_fee_without_balance_check() {
+        let caller = Address::with_last_byte(1);
+        let recipient = Address::with_last_byte(2);
+
+        let mut evm = Evm::builder()
+            .with_db(InMemoryDB::new(EmptyDB::default()))
+            .modify_cfg_env(|cfg| cfg.disable_balance_check = true)
+            .modify_tx_env(|tx| {
+                tx.caller = caller;
+                tx.transact_to = TxKind::Call(Address::with_last_byte(4));
+                tx.gas_limit = 21_000;
+            })
+            .append_handler_register_box(
+                OperatorFee::fixed(recipient, U256::from(2_000)).handle_register(),
+            )
+            .build();
+
+        let state = evm.transact().unwrap().state;
+        assert_eq!(state[&caller].info.balance, U256::ZERO);
+        assert_eq!(state[&recipient].info.balance, U256::from(2_000));
+    }
+}
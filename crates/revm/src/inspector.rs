@@ -1,10 +1,15 @@
+mod binary_trace;
+mod callgraph;
+mod circuit_usage;
 #[cfg(feature = "std")]
 mod customprinter;
 #[cfg(all(feature = "std", feature = "serde-json"))]
 mod eip3155;
 mod gas;
 mod handler_register;
+mod instruction_count;
 mod noop;
+mod watchpoint;
 
 pub use handler_register::{inspector_handle_register, GetInspector};
 
@@ -19,12 +24,25 @@ use auto_impl::auto_impl;
 
 /// [Inspector] implementations.
 pub mod inspectors {
+    pub use super::binary_trace::{
+        decode_binary_trace, BinaryTracer, TraceDecodeError, TraceRecord, BINARY_TRACE_MAGIC,
+        BINARY_TRACE_VERSION,
+    };
+    pub use super::callgraph::{CallEdge, CallEdgeKind, CallGraph, CallGraphInspector};
+    pub use super::circuit_usage::{
+        circuit_usage_handle_register, CircuitUsage, CircuitUsageInspector, GetCircuitUsage,
+        PrecompileUsage,
+    };
     #[cfg(feature = "std")]
     pub use super::customprinter::CustomPrintTracer;
     #[cfg(all(feature = "std", feature = "serde-json"))]
     pub use super::eip3155::TracerEip3155;
     pub use super::gas::GasInspector;
+    pub use super::instruction_count::InstructionCounter;
     pub use super::noop::NoOpInspector;
+    pub use super::watchpoint::{
+        StorageAccess, StorageWatchpoints, WatchpointCallback, WatchpointHit,
+    };
 }
 
 /// EVM [Interpreter] callbacks.
@@ -91,6 +109,8 @@ pub trait Inspector<DB: Database> {
     /// The returned [CallOutcome] is used as the result of the call.
     ///
     /// This allows the inspector to modify the given `result` before returning it.
+    ///
+    /// Gas used by the frame is available with [CallOutcome::frame_gas].
     #[inline]
     fn call_end(
         &mut self,
@@ -103,6 +123,24 @@ pub trait Inspector<DB: Database> {
         outcome
     }
 
+    /// Called when a precompile has been executed, before [Inspector::call_end].
+    ///
+    /// `outcome` contains the output and the gas used by the precompile. Errors returned by the
+    /// precompile are reported as [crate::interpreter::InstructionResult::PrecompileError] or
+    /// [crate::interpreter::InstructionResult::PrecompileOOG].
+    /// The returned error is available in `outcome.result.precompile_error`.
+    #[inline]
+    fn precompile(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &CallInputs,
+        outcome: &CallOutcome,
+    ) {
+        let _ = context;
+        let _ = inputs;
+        let _ = outcome;
+    }
+
     /// Called when a contract is about to be created.
     ///
     /// If this returns `Some` then the [CreateOutcome] is used to override the result of the creation.
@@ -123,6 +161,8 @@ pub trait Inspector<DB: Database> {
     ///
     /// InstructionResulting anything other than the values passed to this function (`(ret, remaining_gas,
     /// address, out)`) will alter the result of the create.
+    ///
+    /// Gas used by the frame is available with [CreateOutcome::frame_gas].
     #[inline]
     fn create_end(
         &mut self,
@@ -0,0 +1,416 @@
+//! Binary trace inspector. Emits a compact, versioned execution trace.
+
+use crate::{
+    interpreter::{opcode, InstructionResult, Interpreter},
+    primitives::{db::Database, Address, HashMap, B256, U256},
+    EvmContext, Inspector,
+};
+use core::fmt;
+use std::vec::Vec;
+
+/// Magic bytes at the start of the binary trace.
+pub const BINARY_TRACE_MAGIC: [u8; 4] = *b"RVMT";
+
+/// Version of the binary trace format.
+pub const BINARY_TRACE_VERSION: u8 = 1;
+
+const TAG_STEP: u8 = 0x00;
+const TAG_ACCOUNT_KEY: u8 = 0x01;
+const TAG_STORAGE_KEY: u8 = 0x02;
+const TAG_ACCOUNT_ACCESS: u8 = 0x03;
+const TAG_STORAGE_ACCESS: u8 = 0x04;
+
+/// Record of the binary trace.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TraceRecord {
+    /// Executed instruction.
+    Step {
+        /// Opcode.
+        opcode: u8,
+        /// Program counter.
+        pc: u32,
+        /// Gas remaining before the instruction.
+        gas_remaining: u64,
+        /// Call depth.
+        depth: u16,
+    },
+    /// Defines the account of the next account index.
+    AccountKey(Address),
+    /// Defines the storage slot of the next storage index.
+    StorageKey(Address, U256),
+    /// Access of the account with the given index.
+    AccountAccess(u32),
+    /// Access of the storage slot with the given index.
+    StorageAccess {
+        /// Index of the storage slot.
+        index: u32,
+        /// Value that was read or written.
+        value: U256,
+    },
+}
+
+impl TraceRecord {
+    /// Appends the encoded record to the buffer.
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            Self::Step {
+                opcode,
+                pc,
+                gas_remaining,
+                depth,
+            } => {
+                out.push(TAG_STEP);
+                out.push(*opcode);
+                out.extend_from_slice(&pc.to_be_bytes());
+                out.extend_from_slice(&gas_remaining.to_be_bytes());
+                out.extend_from_slice(&depth.to_be_bytes());
+            }
+            Self::AccountKey(address) => {
+                out.push(TAG_ACCOUNT_KEY);
+                out.extend_from_slice(address.as_slice());
+            }
+            Self::StorageKey(address, slot) => {
+                out.push(TAG_STORAGE_KEY);
+                out.extend_from_slice(address.as_slice());
+                out.extend_from_slice(&slot.to_be_bytes::<32>());
+            }
+            Self::AccountAccess(index) => {
+                out.push(TAG_ACCOUNT_ACCESS);
+                out.extend_from_slice(&index.to_be_bytes());
+            }
+            Self::StorageAccess { index, value } => {
+                out.push(TAG_STORAGE_ACCESS);
+                out.extend_from_slice(&index.to_be_bytes());
+                out.extend_from_slice(&value.to_be_bytes::<32>());
+            }
+        }
+    }
+}
+
+/// Error returned when decoding the binary trace.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TraceDecodeError {
+    /// Trace does not start with [`BINARY_TRACE_MAGIC`].
+    InvalidMagic,
+    /// Version of the trace is not supported.
+    UnsupportedVersion(u8),
+    /// Unknown record tag at the given offset.
+    UnknownTag(usize),
+    /// Trace ends in the middle of a record.
+    UnexpectedEnd,
+}
+
+impl fmt::Display for TraceDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidMagic => f.write_str("invalid binary trace magic"),
+            Self::UnsupportedVersion(v) => write!(f, "unsupported binary trace version {v}"),
+            Self::UnknownTag(offset) => write!(f, "unknown record tag at offset {offset}"),
+            Self::UnexpectedEnd => f.write_str("unexpected end of binary trace"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TraceDecodeError {}
+
+/// Decodes the binary trace into records.
+pub fn decode_binary_trace(bytes: &[u8]) -> Result<Vec<TraceRecord>, TraceDecodeError> {
+    let mut reader = Reader { bytes, offset: 0 };
+    if reader.take(4)? != BINARY_TRACE_MAGIC {
+        return Err(TraceDecodeError::InvalidMagic);
+    }
+    let version = reader.take(1)?[0];
+    if version != BINARY_TRACE_VERSION {
+        return Err(TraceDecodeError::UnsupportedVersion(version));
+    }
+
+    let mut records = Vec::new();
+    while reader.offset < bytes.len() {
+        let offset = reader.offset;
+        let record = match reader.take(1)?[0] {
+            TAG_STEP => TraceRecord::Step {
+                opcode: reader.take(1)?[0],
+                pc: u32::from_be_bytes(reader.array()?),
+                gas_remaining: u64::from_be_bytes(reader.array()?),
+                depth: u16::from_be_bytes(reader.array()?),
+            },
+            TAG_ACCOUNT_KEY => TraceRecord::AccountKey(Address::new(reader.array()?)),
+            TAG_STORAGE_KEY => TraceRecord::StorageKey(
+                Address::new(reader.array()?),
+                U256::from_be_bytes::<32>(reader.array()?),
+            ),
+            TAG_ACCOUNT_ACCESS => TraceRecord::AccountAccess(u32::from_be_bytes(reader.array()?)),
+            TAG_STORAGE_ACCESS => TraceRecord::StorageAccess {
+                index: u32::from_be_bytes(reader.array()?),
+                value: U256::from_be_bytes::<32>(reader.array()?),
+            },
+            _ => return Err(TraceDecodeError::UnknownTag(offset)),
+        };
+        records.push(record);
+    }
+    Ok(records)
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], TraceDecodeError> {
+        let slice = self
+            .bytes
+            .get(self.offset..self.offset + len)
+            .ok_or(TraceDecodeError::UnexpectedEnd)?;
+        self.offset += len;
+        Ok(slice)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], TraceDecodeError> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+}
+
+/// State access made by the current instruction.
+#[derive(Clone, Copy, Debug)]
+enum PendingAccess {
+    Account(Address),
+    Sload(Address, U256),
+    Sstore(Address, U256, U256),
+}
+
+/// Binary trace [Inspector].
+///
+/// Records every executed instruction and state access in the binary format described below.
+///
+/// # Format
+///
+/// The trace starts with the [`BINARY_TRACE_MAGIC`] bytes followed by the [`BINARY_TRACE_VERSION`]
+/// byte. It is followed by records, each starting with a one byte tag. All integers are big-endian.
+///
+/// | Tag    | Record          | Fields                                                     |
+/// |--------|-----------------|------------------------------------------------------------|
+/// | `0x00` | Step            | opcode `u8`, pc `u32`, gas remaining `u64`, depth `u16`    |
+/// | `0x01` | Account key     | address `[u8; 20]`                                         |
+/// | `0x02` | Storage key     | address `[u8; 20]`, slot `[u8; 32]`                        |
+/// | `0x03` | Account access  | account index `u32`                                        |
+/// | `0x04` | Storage access  | storage index `u32`, value `[u8; 32]`                      |
+///
+/// Accounts and storage slots are referenced by indices. Indices are assigned in order of the
+/// first access, separately for accounts and storage slots, and the key record is emitted right
+/// before the first access record that references it.
+///
+/// Access records follow the step record of the instruction that made them and are only emitted
+/// if the instruction succeeded. Storage value is the value read by `SLOAD` or written by `SSTORE`.
+#[derive(Clone, Debug)]
+pub struct BinaryTracer {
+    buffer: Vec<u8>,
+    accounts: HashMap<Address, u32>,
+    storage: HashMap<(Address, U256), u32>,
+    pending: Option<PendingAccess>,
+}
+
+impl Default for BinaryTracer {
+    fn default() -> Self {
+        let mut buffer = Vec::new();
+        buffer.extend_from_slice(&BINARY_TRACE_MAGIC);
+        buffer.push(BINARY_TRACE_VERSION);
+        Self {
+            buffer,
+            accounts: HashMap::default(),
+            storage: HashMap::default(),
+            pending: None,
+        }
+    }
+}
+
+impl BinaryTracer {
+    /// Creates a new tracer with an empty trace.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the encoded trace.
+    pub fn trace(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Consumes the tracer and returns the encoded trace.
+    pub fn into_trace(self) -> Vec<u8> {
+        self.buffer
+    }
+
+    fn push(&mut self, record: TraceRecord) {
+        record.encode(&mut self.buffer);
+    }
+
+    fn account_index(&mut self, address: Address) -> u32 {
+        if let Some(index) = self.accounts.get(&address) {
+            return *index;
+        }
+        let index = self.accounts.len() as u32;
+        self.accounts.insert(address, index);
+        self.push(TraceRecord::AccountKey(address));
+        index
+    }
+
+    fn storage_index(&mut self, address: Address, slot: U256) -> u32 {
+        if let Some(index) = self.storage.get(&(address, slot)) {
+            return *index;
+        }
+        let index = self.storage.len() as u32;
+        self.storage.insert((address, slot), index);
+        self.push(TraceRecord::StorageKey(address, slot));
+        index
+    }
+}
+
+/// Returns the address from the stack item at the given depth.
+fn stack_address(interp: &Interpreter, n: usize) -> Option<Address> {
+    interp
+        .stack
+        .peek(n)
+        .ok()
+        .map(|word| Address::from_word(B256::from(word)))
+}
+
+impl<DB: Database> Inspector<DB> for BinaryTracer {
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        let opcode = interp.current_opcode();
+        self.push(TraceRecord::Step {
+            opcode,
+            pc: interp.program_counter() as u32,
+            gas_remaining: interp.gas.remaining(),
+            depth: context.journaled_state.depth() as u16,
+        });
+
+        let address = interp.contract.target_address;
+        self.pending = match opcode {
+            opcode::SLOAD => interp
+                .stack
+                .peek(0)
+                .ok()
+                .map(|slot| PendingAccess::Sload(address, slot)),
+            opcode::SSTORE => match (interp.stack.peek(0), interp.stack.peek(1)) {
+                (Ok(slot), Ok(value)) => Some(PendingAccess::Sstore(address, slot, value)),
+                _ => None,
+            },
+            opcode::BALANCE
+            | opcode::EXTCODESIZE
+            | opcode::EXTCODECOPY
+            | opcode::EXTCODEHASH
+            | opcode::SELFDESTRUCT => stack_address(interp, 0).map(PendingAccess::Account),
+            opcode::CALL | opcode::CALLCODE | opcode::DELEGATECALL | opcode::STATICCALL => {
+                stack_address(interp, 1).map(PendingAccess::Account)
+            }
+            _ => None,
+        };
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        let Some(pending) = self.pending.take() else {
+            return;
+        };
+        // calls and selfdestruct stop the frame loop, everything else continues.
+        if !matches!(
+            interp.instruction_result,
+            InstructionResult::Continue
+                | InstructionResult::CallOrCreate
+                | InstructionResult::SelfDestruct
+        ) {
+            return;
+        }
+        match pending {
+            PendingAccess::Account(address) => {
+                let index = self.account_index(address);
+                self.push(TraceRecord::AccountAccess(index));
+            }
+            PendingAccess::Sload(address, slot) => {
+                let value = interp.stack.peek(0).unwrap_or_default();
+                let index = self.storage_index(address, slot);
+                self.push(TraceRecord::StorageAccess { index, value });
+            }
+            PendingAccess::Sstore(address, slot, value) => {
+                let index = self.storage_index(address, slot);
+                self.push(TraceRecord::StorageAccess { index, value });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::BenchmarkDB,
+        inspector_handle_register,
+        primitives::{address, Bytecode, Bytes, TxKind},
+        Evm,
+    };
+
+    #[test]
+    fn binary_trace_roundtrip() {
+        // SSTORE(1, 2), SLOAD(1), STOP
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x2,
+            opcode::PUSH1,
+            0x1,
+            opcode::SSTORE,
+            opcode::PUSH1,
+            0x1,
+            opcode::SLOAD,
+            opcode::STOP,
+        ]);
+        let target = address!("0000000000000000000000000000000000000000");
+
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)))
+            .with_external_context(BinaryTracer::new())
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to = TxKind::Call(target);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        evm.transact().unwrap();
+
+        let trace = evm.into_context().external.into_trace();
+        let records = decode_binary_trace(&trace).unwrap();
+        let steps = records
+            .iter()
+            .filter(|r| matches!(r, TraceRecord::Step { .. }))
+            .count();
+        assert_eq!(steps, 6);
+        let accesses: Vec<_> = records
+            .iter()
+            .filter(|r| !matches!(r, TraceRecord::Step { .. }))
+            .cloned()
+            .collect();
+        assert_eq!(
+            accesses,
+            vec![
+                TraceRecord::StorageKey(target, U256::from(1)),
+                TraceRecord::StorageAccess {
+                    index: 0,
+                    value: U256::from(2)
+                },
+                TraceRecord::StorageAccess {
+                    index: 0,
+                    value: U256::from(2)
+                },
+            ]
+        );
+
+        let mut encoded = Vec::from(BINARY_TRACE_MAGIC);
+        encoded.push(BINARY_TRACE_VERSION);
+        for record in &records {
+            record.encode(&mut encoded);
+        }
+        assert_eq!(encoded, trace);
+    }
+}
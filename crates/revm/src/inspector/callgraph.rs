@@ -0,0 +1,337 @@
+//! Call graph inspector. Builds the graph of calls made during transaction execution.
+
+use crate::{
+    interpreter::{
+        CallInputs, CallOutcome, CallScheme, CreateInputs, CreateOutcome, CreateScheme,
+        EOFCreateInputs,
+    },
+    primitives::{db::Database, Address, HashMap, U256},
+    EvmContext, Inspector,
+};
+use core::fmt::Write;
+use std::{string::String, vec::Vec};
+
+/// Kind of the call graph edge.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CallEdgeKind {
+    /// Call with the given scheme.
+    Call(CallScheme),
+    /// Create with the given scheme.
+    Create(CreateScheme),
+    /// EOF create.
+    EOFCreate,
+}
+
+impl CallEdgeKind {
+    /// Returns the name of the opcode that made the call.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Call(CallScheme::Call) => "CALL",
+            Self::Call(CallScheme::CallCode) => "CALLCODE",
+            Self::Call(CallScheme::DelegateCall) => "DELEGATECALL",
+            Self::Call(CallScheme::StaticCall) => "STATICCALL",
+            Self::Call(CallScheme::ExtCall) => "EXTCALL",
+            Self::Call(CallScheme::ExtStaticCall) => "EXTSTATICCALL",
+            Self::Call(CallScheme::ExtDelegateCall) => "EXTDELEGATECALL",
+            Self::Create(CreateScheme::Create) => "CREATE",
+            Self::Create(CreateScheme::Create2 { .. }) => "CREATE2",
+            Self::EOFCreate => "EOFCREATE",
+        }
+    }
+}
+
+/// Edge of the call graph. Represents a single call frame.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CallEdge {
+    /// Code address that made the call.
+    pub from: Address,
+    /// Code address that was called or created.
+    ///
+    /// For failed creates this is the zero address.
+    pub to: Address,
+    /// Kind of the call.
+    pub kind: CallEdgeKind,
+    /// Value of the call.
+    pub value: U256,
+    /// Gas limit of the call.
+    pub gas_limit: u64,
+    /// Gas used by the call.
+    pub gas_used: u64,
+    /// Whether the call was successful.
+    pub success: bool,
+    /// Depth of the call. Top level call has depth zero.
+    pub depth: usize,
+}
+
+/// Call graph of the transaction.
+///
+/// Nodes are code addresses and edges are calls between them, in execution order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CallGraph {
+    /// Unique code addresses in order of appearance.
+    pub nodes: Vec<Address>,
+    /// Calls in the order they were made.
+    pub edges: Vec<CallEdge>,
+}
+
+impl CallGraph {
+    /// Exports the graph in the Graphviz DOT format.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph calls {\n");
+        for node in &self.nodes {
+            let _ = writeln!(out, "    \"{node}\";");
+        }
+        for (i, edge) in self.edges.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "    \"{}\" -> \"{}\" [label=\"#{} {} value={} gas={}/{}\"{}];",
+                edge.from,
+                edge.to,
+                i,
+                edge.kind.name(),
+                edge.value,
+                edge.gas_used,
+                edge.gas_limit,
+                if edge.success { "" } else { ", color=red" },
+            );
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Exports the graph as JSON.
+    #[cfg(feature = "serde-json")]
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+}
+
+/// Call graph [Inspector].
+///
+/// Collects the [CallGraph] of the executed transaction.
+#[derive(Clone, Debug, Default)]
+pub struct CallGraphInspector {
+    graph: CallGraph,
+    /// Indices of the nodes.
+    node_indices: HashMap<Address, usize>,
+    /// Indices of the edges of the currently open frames.
+    open_edges: Vec<usize>,
+}
+
+impl CallGraphInspector {
+    /// Returns the collected call graph.
+    pub fn graph(&self) -> &CallGraph {
+        &self.graph
+    }
+
+    /// Consumes the inspector and returns the collected call graph.
+    pub fn into_graph(self) -> CallGraph {
+        self.graph
+    }
+
+    /// Clears the collected graph so the inspector can be reused.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    fn add_node(&mut self, address: Address) {
+        let nodes = &mut self.graph.nodes;
+        self.node_indices.entry(address).or_insert_with(|| {
+            nodes.push(address);
+            nodes.len() - 1
+        });
+    }
+
+    fn open_edge(
+        &mut self,
+        caller: Address,
+        to: Option<Address>,
+        kind: CallEdgeKind,
+        value: U256,
+        gas_limit: u64,
+    ) {
+        // frame that made the call executes code of the last opened edge.
+        let from = self
+            .open_edges
+            .last()
+            .map(|i| self.graph.edges[*i].to)
+            .unwrap_or(caller);
+        self.add_node(from);
+        if let Some(to) = to {
+            self.add_node(to);
+        }
+        self.graph.edges.push(CallEdge {
+            from,
+            to: to.unwrap_or_default(),
+            kind,
+            value,
+            gas_limit,
+            gas_used: 0,
+            success: false,
+            depth: self.open_edges.len(),
+        });
+        self.open_edges.push(self.graph.edges.len() - 1);
+    }
+
+    fn close_edge(&mut self, gas_used: u64, success: bool, created: Option<Address>) {
+        let Some(index) = self.open_edges.pop() else {
+            return;
+        };
+        if let Some(address) = created {
+            self.graph.edges[index].to = address;
+            self.add_node(address);
+        }
+        let edge = &mut self.graph.edges[index];
+        edge.gas_used = gas_used;
+        edge.success = success;
+    }
+}
+
+impl<DB: Database> Inspector<DB> for CallGraphInspector {
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.open_edge(
+            inputs.caller,
+            Some(inputs.bytecode_address),
+            CallEdgeKind::Call(inputs.scheme),
+            inputs.call_value(),
+            inputs.gas_limit,
+        );
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.close_edge(outcome.result.gas.spent(), outcome.result.is_ok(), None);
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.open_edge(
+            inputs.caller,
+            None,
+            CallEdgeKind::Create(inputs.scheme),
+            inputs.value,
+            inputs.gas_limit,
+        );
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.close_edge(
+            outcome.result.gas.spent(),
+            outcome.result.is_ok(),
+            outcome.address,
+        );
+        outcome
+    }
+
+    fn eofcreate(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut EOFCreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.open_edge(
+            inputs.caller,
+            inputs.kind.created_address().copied(),
+            CallEdgeKind::EOFCreate,
+            inputs.value,
+            inputs.gas_limit,
+        );
+        None
+    }
+
+    fn eofcreate_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &EOFCreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.close_edge(
+            outcome.result.gas.spent(),
+            outcome.result.is_ok(),
+            outcome.address,
+        );
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::BenchmarkDB,
+        inspector_handle_register,
+        interpreter::opcode,
+        primitives::{address, Bytecode, Bytes, TxKind},
+        Evm,
+    };
+
+    #[test]
+    fn call_graph_of_nested_call() {
+        // STATICCALL(gas, 0x1, 0, 0, 0, 0), STOP
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x0,
+            opcode::DUP1,
+            opcode::DUP1,
+            opcode::DUP1,
+            opcode::PUSH1,
+            0x1,
+            opcode::GAS,
+            opcode::STATICCALL,
+            opcode::STOP,
+        ]);
+        let caller = address!("1000000000000000000000000000000000000000");
+        let target = address!("0000000000000000000000000000000000000000");
+
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)))
+            .with_external_context(CallGraphInspector::default())
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = caller;
+                tx.transact_to = TxKind::Call(target);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        evm.transact().unwrap();
+
+        let graph = evm.into_context().external.into_graph();
+        assert_eq!(
+            graph.nodes,
+            vec![caller, target, Address::with_last_byte(1)]
+        );
+        assert_eq!(graph.edges.len(), 2);
+        assert_eq!(graph.edges[0].from, caller);
+        assert_eq!(graph.edges[0].depth, 0);
+        assert_eq!(
+            graph.edges[1].kind,
+            CallEdgeKind::Call(CallScheme::StaticCall)
+        );
+        assert_eq!(graph.edges[1].from, target);
+        assert_eq!(graph.edges[1].depth, 1);
+        assert!(graph.to_dot().contains("STATICCALL"));
+    }
+}
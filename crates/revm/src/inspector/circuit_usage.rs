@@ -0,0 +1,296 @@
+//! Circuit usage inspector. Tallies the operations that dominate the cost of proving a transaction.
+
+use super::instruction_count::InstructionCounter;
+use crate::{
+    handler::register::EvmHandler,
+    interpreter::{opcode, CallInputs, CallOutcome, InstructionResult, Interpreter},
+    primitives::{db::Database, Address, HashMap},
+    EvmContext, FrameOrResult, FrameResult, Inspector,
+};
+use std::sync::Arc;
+
+/// Precompile calls to a single precompile.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PrecompileUsage {
+    /// Number of calls.
+    pub calls: u64,
+    /// Total size of the call inputs in bytes.
+    pub input_bytes: u64,
+}
+
+/// Operations executed by a transaction that are expensive to prove.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CircuitUsage {
+    /// Number of executed instructions.
+    pub instructions: u64,
+    /// Number of executions of every executed opcode.
+    pub opcodes: HashMap<u8, u64>,
+    /// Number of `KECCAK256` instructions.
+    pub keccak_calls: u64,
+    /// Total size of the `KECCAK256` inputs in bytes.
+    pub keccak_bytes: u64,
+    /// Number of `EXP` instructions.
+    pub exp_calls: u64,
+    /// Total size of the `EXP` exponents in bytes.
+    pub exp_bytes: u64,
+    /// Executed precompiles.
+    pub precompiles: HashMap<Address, PrecompileUsage>,
+}
+
+/// Collects [CircuitUsage] of the executed transactions.
+///
+/// It can be used as an [Inspector] with [`inspector_handle_register`](crate::inspector_handle_register)
+/// or, without the overhead of the other inspector hooks, with [circuit_usage_handle_register].
+///
+/// Usage is accumulated over all transactions until it is taken with
+/// [CircuitUsageInspector::take_usage]. Instructions of reverted frames are counted as well,
+/// as they need to be proven.
+#[derive(Clone, Debug, Default)]
+pub struct CircuitUsageInspector {
+    counter: InstructionCounter,
+    usage: CircuitUsage,
+}
+
+impl CircuitUsageInspector {
+    /// Creates a new inspector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new inspector that estimates the cost of the instructions with the given counter.
+    pub fn with_counter(counter: InstructionCounter) -> Self {
+        Self {
+            counter,
+            usage: CircuitUsage::default(),
+        }
+    }
+
+    /// Returns the per-opcode counter of the executed instructions.
+    pub fn counter(&self) -> &InstructionCounter {
+        &self.counter
+    }
+
+    /// Returns the usage collected so far.
+    pub fn usage(&self) -> CircuitUsage {
+        CircuitUsage {
+            instructions: self.counter.total_instructions(),
+            opcodes: self.counter.counts(),
+            ..self.usage.clone()
+        }
+    }
+
+    /// Takes the usage collected so far and resets it.
+    pub fn take_usage(&mut self) -> CircuitUsage {
+        let usage = CircuitUsage {
+            instructions: self.counter.total_instructions(),
+            opcodes: self.counter.counts(),
+            ..core::mem::take(&mut self.usage)
+        };
+        self.counter.reset();
+        usage
+    }
+
+    /// Records the execution of the opcode. Called before the instruction is executed.
+    fn record_instruction(&mut self, opcode: u8, interp: &Interpreter) {
+        self.counter.record(opcode);
+        match opcode {
+            opcode::KECCAK256 => {
+                self.usage.keccak_calls += 1;
+                if let Ok(size) = interp.stack.peek(1) {
+                    self.usage.keccak_bytes =
+                        self.usage.keccak_bytes.saturating_add(size.saturating_to());
+                }
+            }
+            opcode::EXP => {
+                self.usage.exp_calls += 1;
+                if let Ok(exponent) = interp.stack.peek(1) {
+                    self.usage.exp_bytes = self
+                        .usage
+                        .exp_bytes
+                        .saturating_add(exponent.byte_len() as u64);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Records the execution of the precompile.
+    fn record_precompile(&mut self, address: Address, input_len: usize) {
+        let usage = self.usage.precompiles.entry(address).or_default();
+        usage.calls += 1;
+        usage.input_bytes = usage.input_bytes.saturating_add(input_len as u64);
+    }
+}
+
+impl<DB: Database> Inspector<DB> for CircuitUsageInspector {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        self.record_instruction(interp.current_opcode(), interp);
+    }
+
+    fn precompile(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &CallInputs,
+        _outcome: &CallOutcome,
+    ) {
+        self.record_precompile(inputs.bytecode_address, inputs.input.len());
+    }
+}
+
+/// Provides access to a [CircuitUsageInspector] instance.
+pub trait GetCircuitUsage {
+    /// Returns the associated [CircuitUsageInspector].
+    fn get_circuit_usage(&mut self) -> &mut CircuitUsageInspector;
+}
+
+impl GetCircuitUsage for CircuitUsageInspector {
+    #[inline]
+    fn get_circuit_usage(&mut self) -> &mut CircuitUsageInspector {
+        self
+    }
+}
+
+/// Register handles that collect [CircuitUsage] without running an [Inspector].
+///
+/// Every instruction is wrapped to record the opcode before it is executed, and the call handle is
+/// wrapped to record the executed precompiles. Existing handles are called, so the register can be
+/// combined with any other register.
+pub fn circuit_usage_handle_register<DB: Database, EXT: GetCircuitUsage>(
+    handler: &mut EvmHandler<'_, EXT, DB>,
+) {
+    let table = &mut handler.instruction_table;
+    for opcode in 0..=u8::MAX {
+        table.update_boxed(opcode, move |prev, interpreter, host| {
+            host.external
+                .get_circuit_usage()
+                .record_instruction(opcode, interpreter);
+            prev(interpreter, host);
+        });
+    }
+
+    let prev_handle = handler.execution.call.clone();
+    handler.execution.call = Arc::new(move |ctx, inputs| {
+        let precompile = ctx
+            .evm
+            .precompiles
+            .contains(&inputs.bytecode_address)
+            .then(|| (inputs.bytecode_address, inputs.input.len()));
+        let frame_or_result = prev_handle(ctx, inputs);
+        // call can fail before the precompile is executed.
+        if let (Some((address, input_len)), Ok(FrameOrResult::Result(FrameResult::Call(outcome)))) =
+            (precompile, &frame_or_result)
+        {
+            if !matches!(
+                outcome.result.result,
+                InstructionResult::CallTooDeep | InstructionResult::OutOfFunds
+            ) {
+                ctx.external
+                    .get_circuit_usage()
+                    .record_precompile(address, input_len);
+            }
+        }
+        frame_or_result
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::BenchmarkDB,
+        inspector_handle_register,
+        primitives::{address, Bytecode, Bytes, TxKind},
+        Evm,
+    };
+
+    fn contract() -> Bytecode {
+        Bytecode::new_raw(Bytes::from(vec![
+            // KECCAK256(0, 64)
+            opcode::PUSH1,
+            0x40,
+            opcode::PUSH1,
+            0x00,
+            opcode::KECCAK256,
+            opcode::POP,
+            // EXP(2, 0x0100)
+            opcode::PUSH2,
+            0x01,
+            0x00,
+            opcode::PUSH1,
+            0x02,
+            opcode::EXP,
+            opcode::POP,
+            // STATICCALL(gas, identity, 0, 32, 0, 0)
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x20,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x04,
+            opcode::GAS,
+            opcode::STATICCALL,
+            opcode::STOP,
+        ]))
+    }
+
+    fn assert_usage(usage: CircuitUsage) {
+        assert_eq!(usage.instructions, 16);
+        assert_eq!(usage.opcodes[&opcode::PUSH1], 8);
+        assert_eq!(usage.opcodes[&opcode::STATICCALL], 1);
+        assert_eq!(usage.opcodes.values().sum::<u64>(), 16);
+        assert_eq!((usage.keccak_calls, usage.keccak_bytes), (1, 64));
+        assert_eq!((usage.exp_calls, usage.exp_bytes), (1, 2));
+        assert_eq!(
+            usage.precompiles[&address!("0000000000000000000000000000000000000004")],
+            PrecompileUsage {
+                calls: 1,
+                input_bytes: 32
+            }
+        );
+    }
+
+    #[test]
+    fn count_keccak_exp_and_precompiles() {
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(contract()))
+            .with_external_context(CircuitUsageInspector::new())
+            .modify_tx_env(|tx| {
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to = TxKind::Call(address!("0000000000000000000000000000000000000000"));
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        evm.transact().unwrap();
+
+        assert_usage(evm.context.external.take_usage());
+        assert_eq!(evm.context.external.usage(), CircuitUsage::default());
+    }
+
+    #[test]
+    fn count_with_handler_register() {
+        let mut counter = InstructionCounter::new();
+        counter.set_cost(opcode::KECCAK256, 100);
+
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(contract()))
+            .with_external_context(CircuitUsageInspector::with_counter(counter))
+            .modify_tx_env(|tx| {
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to = TxKind::Call(address!("0000000000000000000000000000000000000000"));
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(circuit_usage_handle_register)
+            .build();
+        evm.transact().unwrap();
+
+        assert_eq!(evm.context.external.counter().estimated_cost(), 115);
+        assert_usage(evm.context.external.take_usage());
+    }
+}
@@ -120,11 +120,29 @@ pub fn inspector_handle_register<DB: Database, EXT: GetInspector<DB>>(
             return Ok(FrameOrResult::Result(FrameResult::Call(outcome)));
         }
 
+        let is_precompile = ctx.evm.precompiles.contains(&inputs.bytecode_address);
         let mut frame_or_result = prev_handle(ctx, inputs);
-        if let Ok(FrameOrResult::Frame(frame)) = &mut frame_or_result {
-            ctx.external
+        match &mut frame_or_result {
+            Ok(FrameOrResult::Frame(frame)) => ctx
+                .external
                 .get_inspector()
-                .initialize_interp(frame.interpreter_mut(), &mut ctx.evm)
+                .initialize_interp(frame.interpreter_mut(), &mut ctx.evm),
+            // call can fail before the precompile is executed.
+            Ok(FrameOrResult::Result(FrameResult::Call(outcome)))
+                if is_precompile
+                    && !matches!(
+                        outcome.result.result,
+                        InstructionResult::CallTooDeep | InstructionResult::OutOfFunds
+                    ) =>
+            {
+                let call_inputs = call_input_stack_inner.borrow();
+                ctx.external.get_inspector().precompile(
+                    &mut ctx.evm,
+                    call_inputs.last().unwrap(),
+                    outcome,
+                )
+            }
+            _ => {}
         }
         frame_or_result
     });
@@ -380,6 +398,62 @@ mod tests {
         assert!(inspector.call_end);
     }
 
+    #[test]
+    fn test_inspector_precompile() {
+        use crate::{
+            db::BenchmarkDB,
+            primitives::{address, Address, Bytecode, Bytes, TxKind},
+        };
+
+        #[derive(Default)]
+        struct PrecompileInspector {
+            calls: Vec<(Address, Bytes, InstructionResult, Bytes)>,
+        }
+
+        impl<DB: Database> Inspector<DB> for PrecompileInspector {
+            fn precompile(
+                &mut self,
+                _context: &mut EvmContext<DB>,
+                inputs: &CallInputs,
+                outcome: &CallOutcome,
+            ) {
+                self.calls.push((
+                    inputs.bytecode_address,
+                    inputs.input.clone(),
+                    outcome.result.result,
+                    outcome.result.output.clone(),
+                ));
+            }
+        }
+
+        let identity = address!("0000000000000000000000000000000000000004");
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+            .with_external_context(PrecompileInspector::default())
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to = TxKind::Call(identity);
+                tx.data = Bytes::from_static(&[1, 2]);
+                tx.gas_limit = 30_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        evm.transact().unwrap();
+
+        let inspector = evm.into_context().external;
+        assert_eq!(
+            inspector.calls,
+            vec![(
+                identity,
+                Bytes::from_static(&[1, 2]),
+                InstructionResult::Return,
+                Bytes::from_static(&[1, 2]),
+            )]
+        );
+    }
+
     #[test]
     fn test_inspector_reg() {
         let mut noop = NoOpInspector;
@@ -0,0 +1,139 @@
+//! Instruction counting inspector. Estimates the cost of the execution with a per-opcode cost model.
+
+use crate::{
+    interpreter::Interpreter,
+    primitives::{db::Database, HashMap},
+    EvmContext, Inspector,
+};
+
+/// Counts executed instructions per opcode and estimates their total cost.
+///
+/// Cost of every opcode is taken from the cost model, e.g. cycle estimates of a zkVM prover.
+/// Default cost model assigns cost of one to every opcode, so the estimated cost is equal to the
+/// number of executed instructions.
+#[derive(Clone, Debug)]
+pub struct InstructionCounter {
+    counts: [u64; 256],
+    cost_model: [u64; 256],
+}
+
+impl Default for InstructionCounter {
+    fn default() -> Self {
+        Self::with_cost_model([1; 256])
+    }
+}
+
+impl InstructionCounter {
+    /// Creates a new counter with the unit cost model.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new counter with the given cost of every opcode.
+    pub fn with_cost_model(cost_model: [u64; 256]) -> Self {
+        Self {
+            counts: [0; 256],
+            cost_model,
+        }
+    }
+
+    /// Sets the cost of the opcode.
+    pub fn set_cost(&mut self, opcode: u8, cost: u64) {
+        self.cost_model[opcode as usize] = cost;
+    }
+
+    /// Returns the cost of the opcode.
+    pub fn cost(&self, opcode: u8) -> u64 {
+        self.cost_model[opcode as usize]
+    }
+
+    /// Returns the number of executions of the opcode.
+    pub fn count(&self, opcode: u8) -> u64 {
+        self.counts[opcode as usize]
+    }
+
+    /// Returns the number of executions of every executed opcode.
+    pub fn counts(&self) -> HashMap<u8, u64> {
+        (0..=u8::MAX)
+            .filter(|&opcode| self.count(opcode) != 0)
+            .map(|opcode| (opcode, self.count(opcode)))
+            .collect()
+    }
+
+    /// Returns the total number of executed instructions.
+    pub fn total_instructions(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// Returns the estimated cost of all executed instructions.
+    pub fn estimated_cost(&self) -> u64 {
+        self.counts
+            .iter()
+            .zip(self.cost_model)
+            .fold(0u64, |total, (count, cost)| {
+                total.saturating_add(count.saturating_mul(cost))
+            })
+    }
+
+    /// Records one execution of the opcode.
+    #[inline]
+    pub fn record(&mut self, opcode: u8) {
+        self.counts[opcode as usize] += 1;
+    }
+
+    /// Clears the counts, the cost model is kept.
+    pub fn reset(&mut self) {
+        self.counts = [0; 256];
+    }
+}
+
+impl<DB: Database> Inspector<DB> for InstructionCounter {
+    #[inline]
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        self.record(interp.current_opcode());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::BenchmarkDB,
+        inspector_handle_register,
+        interpreter::opcode,
+        primitives::{address, Bytecode, Bytes, TxKind},
+        Evm,
+    };
+
+    #[test]
+    fn count_instructions() {
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x1,
+            opcode::PUSH1,
+            0x2,
+            opcode::ADD,
+            opcode::STOP,
+        ]);
+        let mut counter = InstructionCounter::new();
+        counter.set_cost(opcode::ADD, 10);
+
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)))
+            .with_external_context(counter)
+            .modify_tx_env(|tx| {
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to = TxKind::Call(address!("0000000000000000000000000000000000000000"));
+                tx.gas_limit = 21100;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        evm.transact().unwrap();
+
+        let counter = &evm.context.external;
+        assert_eq!(counter.count(opcode::PUSH1), 2);
+        assert_eq!(counter.count(opcode::ADD), 1);
+        assert_eq!(counter.total_instructions(), 4);
+        assert_eq!(counter.estimated_cost(), 13);
+    }
+}
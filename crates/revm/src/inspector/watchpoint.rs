@@ -0,0 +1,275 @@
+//! Storage watchpoint inspector. Reports every read and write of watched storage slots.
+
+use crate::{
+    interpreter::{opcode, InstructionResult, Interpreter},
+    primitives::{db::Database, Address, HashSet, U256},
+    EvmContext, Inspector,
+};
+use core::fmt;
+use std::{boxed::Box, vec::Vec};
+
+/// Kind of storage access that triggered a watchpoint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StorageAccess {
+    /// Slot was read with `SLOAD`.
+    Read,
+    /// Slot was written with `SSTORE`.
+    Write,
+}
+
+/// Information about a triggered watchpoint.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct WatchpointHit {
+    /// Address that owns the storage.
+    pub address: Address,
+    /// Address of the code that accessed the storage.
+    ///
+    /// It differs from `address` when the storage is accessed through `DELEGATECALL` or `CALLCODE`.
+    pub code_address: Address,
+    /// Storage slot.
+    pub slot: U256,
+    /// Kind of access.
+    pub access: StorageAccess,
+    /// Value that was read or written.
+    pub value: U256,
+    /// Program counter of the accessing instruction.
+    pub pc: usize,
+    /// Call depth at which the access happened.
+    pub depth: u64,
+}
+
+/// Callback that is called when a watchpoint is hit.
+///
+/// It receives the interpreter so execution can be halted by setting
+/// `interp.instruction_result`.
+pub type WatchpointCallback = Box<dyn FnMut(&WatchpointHit, &mut Interpreter)>;
+
+/// Storage watchpoint [Inspector].
+///
+/// Watchpoints are registered as `(address, slot)` pairs where `address` is the account that owns
+/// the storage. Accesses done through `DELEGATECALL` and `CALLCODE` are reported for the account
+/// whose storage is modified, with the executing code address stored in
+/// [`WatchpointHit::code_address`].
+#[derive(Default)]
+pub struct StorageWatchpoints {
+    watched: HashSet<(Address, U256)>,
+    callback: Option<WatchpointCallback>,
+    hits: Vec<WatchpointHit>,
+    pending: Option<WatchpointHit>,
+}
+
+impl fmt::Debug for StorageWatchpoints {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StorageWatchpoints")
+            .field("watched", &self.watched)
+            .field("hits", &self.hits)
+            .finish_non_exhaustive()
+    }
+}
+
+impl StorageWatchpoints {
+    /// Creates a new inspector without any watchpoints.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the callback that is called on every watchpoint hit.
+    pub fn with_callback(
+        mut self,
+        callback: impl FnMut(&WatchpointHit, &mut Interpreter) + 'static,
+    ) -> Self {
+        self.callback = Some(Box::new(callback));
+        self
+    }
+
+    /// Registers a watchpoint on the storage slot of the given address.
+    pub fn watch(&mut self, address: Address, slot: U256) {
+        self.watched.insert((address, slot));
+    }
+
+    /// Removes the watchpoint. Returns `true` if it was registered.
+    pub fn unwatch(&mut self, address: Address, slot: U256) -> bool {
+        self.watched.remove(&(address, slot))
+    }
+
+    /// Returns `true` if the slot is watched.
+    pub fn is_watched(&self, address: Address, slot: U256) -> bool {
+        self.watched.contains(&(address, slot))
+    }
+
+    /// Returns all watchpoint hits recorded so far.
+    pub fn hits(&self) -> &[WatchpointHit] {
+        &self.hits
+    }
+
+    /// Takes all recorded watchpoint hits.
+    pub fn take_hits(&mut self) -> Vec<WatchpointHit> {
+        core::mem::take(&mut self.hits)
+    }
+}
+
+impl<DB: Database> Inspector<DB> for StorageWatchpoints {
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        let access = match interp.current_opcode() {
+            opcode::SLOAD => StorageAccess::Read,
+            opcode::SSTORE => StorageAccess::Write,
+            _ => return,
+        };
+        let Ok(slot) = interp.stack.peek(0) else {
+            return;
+        };
+        let address = interp.contract.target_address;
+        if !self.is_watched(address, slot) {
+            return;
+        }
+        // written value is known before execution, read value is set in `step_end`.
+        let value = match access {
+            StorageAccess::Read => U256::ZERO,
+            StorageAccess::Write => interp.stack.peek(1).unwrap_or_default(),
+        };
+        self.pending = Some(WatchpointHit {
+            address,
+            code_address: interp.contract.bytecode_address.unwrap_or(address),
+            slot,
+            access,
+            value,
+            pc: interp.program_counter(),
+            depth: context.journaled_state.depth(),
+        });
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        let Some(mut hit) = self.pending.take() else {
+            return;
+        };
+        // instruction failed and the storage was not accessed.
+        if interp.instruction_result != InstructionResult::Continue {
+            return;
+        }
+        if hit.access == StorageAccess::Read {
+            hit.value = interp.stack.peek(0).unwrap_or_default();
+        }
+        if let Some(callback) = &mut self.callback {
+            callback(&hit, interp);
+        }
+        self.hits.push(hit);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::{BenchmarkDB, EmptyDB, InMemoryDB},
+        inspector_handle_register,
+        primitives::{address, AccountInfo, Bytecode, Bytes, TxKind},
+        Evm,
+    };
+
+    #[test]
+    fn watch_sload_and_sstore() {
+        // SSTORE(1, 2), SLOAD(1), SLOAD(3), STOP
+        let contract_data: Bytes = Bytes::from(vec![
+            opcode::PUSH1,
+            0x2,
+            opcode::PUSH1,
+            0x1,
+            opcode::SSTORE,
+            opcode::PUSH1,
+            0x1,
+            opcode::SLOAD,
+            opcode::PUSH1,
+            0x3,
+            opcode::SLOAD,
+            opcode::STOP,
+        ]);
+        let target = address!("0000000000000000000000000000000000000000");
+        let mut watchpoints = StorageWatchpoints::new();
+        watchpoints.watch(target, U256::from(1));
+
+        let mut evm = Evm::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(contract_data)))
+            .with_external_context(watchpoints)
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to = TxKind::Call(target);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        evm.transact().unwrap();
+
+        let hits = evm.into_context().external.take_hits();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].access, StorageAccess::Write);
+        assert_eq!(hits[0].value, U256::from(2));
+        assert_eq!(hits[1].access, StorageAccess::Read);
+        assert_eq!(hits[1].value, U256::from(2));
+        assert_eq!(hits[1].pc, 7);
+    }
+
+    #[test]
+    fn watch_sstore_in_delegatecall() {
+        let caller = address!("0000000000000000000000000000000000000100");
+        let library = address!("0000000000000000000000000000000000000200");
+        // DELEGATECALL(gas, library, 0, 0, 0, 0), STOP
+        let caller_code = Bytecode::new_raw(Bytes::from(vec![
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH1,
+            0x0,
+            opcode::PUSH2,
+            0x02,
+            0x00,
+            opcode::GAS,
+            opcode::DELEGATECALL,
+            opcode::STOP,
+        ]));
+        // SSTORE(1, 2), STOP
+        let library_code = Bytecode::new_raw(Bytes::from(vec![
+            opcode::PUSH1,
+            0x2,
+            opcode::PUSH1,
+            0x1,
+            opcode::SSTORE,
+            opcode::STOP,
+        ]));
+        let mut db = InMemoryDB::new(EmptyDB::default());
+        for (address, code) in [(caller, caller_code), (library, library_code)] {
+            db.insert_account_info(address, AccountInfo::from_bytecode(code));
+        }
+
+        let mut watchpoints = StorageWatchpoints::new();
+        watchpoints.watch(caller, U256::from(1));
+        watchpoints.watch(library, U256::from(1));
+
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_external_context(watchpoints)
+            .modify_tx_env(|tx| {
+                tx.clear();
+                tx.caller = address!("1000000000000000000000000000000000000000");
+                tx.transact_to = TxKind::Call(caller);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        evm.transact().unwrap();
+
+        // The write is reported against the storage of the caller only.
+        let hits = evm.into_context().external.take_hits();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].address, caller);
+        assert_eq!(hits[0].code_address, library);
+        assert_eq!(hits[0].access, StorageAccess::Write);
+        assert_eq!(hits[0].value, U256::from(2));
+        assert_eq!(hits[0].depth, 2);
+    }
+}
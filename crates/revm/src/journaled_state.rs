@@ -1,9 +1,12 @@
 use crate::{
     interpreter::{InstructionResult, LoadAccountResult, SStoreResult, SelfDestructResult},
     primitives::{
-        db::Database, hash_map::Entry, Account, Address, Bytecode, EVMError, EvmState,
-        EvmStorageSlot, HashMap, HashSet, Log, SpecId, SpecId::*, TransientStorage, B256,
-        KECCAK_EMPTY, PRECOMPILE3, U256,
+        db::{CodeMetadata, Database},
+        hash_map::Entry,
+        Account, Address, Bytecode, EVMError, EvmState, EvmStorageSlot, HashMap, HashSet, Log,
+        SpecId,
+        SpecId::*,
+        TransientStorage, B256, KECCAK_EMPTY, PRECOMPILE3, U256,
     },
 };
 use core::mem;
@@ -622,7 +625,11 @@ impl JournaledState {
             loaded_not_existing && is_not_touched
         };
 
-        Ok(LoadAccountResult { is_empty, is_cold })
+        Ok(LoadAccountResult {
+            is_empty,
+            is_cold,
+            is_delegate_account_cold: None,
+        })
     }
 
     /// Loads code.
@@ -647,6 +654,27 @@ impl JournaledState {
         Ok((acc, is_cold))
     }
 
+    /// Loads the account and returns it with the metadata of its code.
+    ///
+    /// Unlike [`JournaledState::load_code`], code bytes are not loaded if they are not
+    /// already present in the account.
+    #[inline]
+    pub fn load_code_metadata<DB: Database>(
+        &mut self,
+        address: Address,
+        db: &mut DB,
+    ) -> Result<(&mut Account, CodeMetadata, bool), EVMError<DB::Error>> {
+        let (acc, is_cold) = self.load_account(address, db)?;
+        let metadata = match &acc.info.code {
+            Some(code) => CodeMetadata::from_bytecode(code),
+            None if acc.info.code_hash == KECCAK_EMPTY => CodeMetadata::default(),
+            None => db
+                .code_metadata_by_hash(acc.info.code_hash)
+                .map_err(EVMError::Database)?,
+        };
+        Ok((acc, metadata, is_cold))
+    }
+
     /// Load storage slot
     ///
     /// # Panics
@@ -876,3 +904,30 @@ pub struct JournalCheckpoint {
     log_i: usize,
     journal_i: usize,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transient_storage_is_journaled() {
+        let address = Address::with_last_byte(1);
+        let key = U256::from(1);
+        let mut journal = JournaledState::new(SpecId::CANCUN, HashSet::new());
+
+        journal.tstore(address, key, U256::from(1));
+        let checkpoint = journal.checkpoint();
+        journal.tstore(address, key, U256::from(2));
+        journal.tstore(address, U256::from(2), U256::from(3));
+        assert_eq!(journal.tload(address, key), U256::from(2));
+
+        // Revert restores the value written before the checkpoint and removes the new slot.
+        journal.checkpoint_revert(checkpoint);
+        assert_eq!(journal.tload(address, key), U256::from(1));
+        assert_eq!(journal.tload(address, U256::from(2)), U256::ZERO);
+
+        // Transient storage is cleared at the end of the transaction.
+        journal.finalize();
+        assert_eq!(journal.tload(address, key), U256::ZERO);
+    }
+}
@@ -8,6 +8,8 @@ extern crate alloc as std;
 
 // Define modules.
 
+#[cfg(feature = "std")]
+mod analysis_cache;
 mod builder;
 mod context;
 
@@ -22,10 +24,16 @@ mod inspector;
 mod journaled_state;
 #[cfg(feature = "optimism")]
 pub mod optimism;
+pub mod simulate;
+mod system_call;
 
 // Export items.
 
+#[cfg(feature = "std")]
+pub use analysis_cache::{AnalysisCache, DEFAULT_ANALYSIS_CACHE_CAPACITY};
 pub use builder::EvmBuilder;
+#[cfg(feature = "optimism")]
+pub use builder::EvmBuilderError;
 pub use context::{
     Context, ContextPrecompile, ContextPrecompiles, ContextStatefulPrecompile,
     ContextStatefulPrecompileArc, ContextStatefulPrecompileBox, ContextStatefulPrecompileMut,
@@ -40,6 +48,7 @@ pub use frame::{CallFrame, CreateFrame, Frame, FrameData, FrameOrResult, FrameRe
 pub use handler::Handler;
 pub use inspector::{inspector_handle_register, inspectors, GetInspector, Inspector};
 pub use journaled_state::{JournalCheckpoint, JournalEntry, JournaledState};
+pub use system_call::PostBlockRequests;
 // export Optimism types, helpers, and constants
 #[cfg(feature = "optimism")]
 pub use optimism::{L1BlockInfo, BASE_FEE_RECIPIENT, L1_BLOCK_CONTRACT, L1_FEE_RECIPIENT};
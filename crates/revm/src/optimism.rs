@@ -2,11 +2,14 @@
 
 mod bn128;
 mod fast_lz;
+mod fee_quote;
 mod handler_register;
 mod l1block;
 
+pub use fee_quote::FeeQuote;
 pub use handler_register::{
     deduct_caller, end, last_frame_return, load_accounts, load_precompiles,
-    optimism_handle_register, output, reward_beneficiary, validate_env, validate_tx_against_state,
+    optimism_handle_register, optimism_precompiles, output, reward_beneficiary, validate_env,
+    validate_tx_against_state,
 };
 pub use l1block::{L1BlockInfo, BASE_FEE_RECIPIENT, L1_BLOCK_CONTRACT, L1_FEE_RECIPIENT};
@@ -12,7 +12,7 @@ pub(crate) mod pair {
 
     pub(crate) fn run_pair(input: &[u8], gas_limit: u64) -> PrecompileResult {
         if input.len() > GRANITE_MAX_INPUT_SIZE {
-            return Err(Error::Bn128PairLength.into());
+            return Err(Error::Bn128PairLength { len: input.len() }.into());
         }
         bn128::run_pair(
             input,
@@ -66,7 +66,7 @@ mod tests {
         let res = pair::run_pair(&input, 260_000);
         assert!(matches!(
             res,
-            Err(PrecompileErrors::Error(Error::Bn128PairLength))
+            Err(PrecompileErrors::Error(Error::Bn128PairLength { len: 79 }))
         ));
 
         // valid input length shorter than 112687
@@ -79,7 +79,9 @@ mod tests {
         let res = pair::run_pair(&input, 260_000);
         assert!(matches!(
             res,
-            Err(PrecompileErrors::Error(Error::Bn128PairLength))
+            Err(PrecompileErrors::Error(Error::Bn128PairLength {
+                len: 112704
+            }))
         ));
     }
 }
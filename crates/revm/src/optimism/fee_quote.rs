@@ -0,0 +1,157 @@
+use crate::{
+    optimism::L1BlockInfo,
+    primitives::{db::Database, EVMError, U256},
+    Evm,
+};
+
+/// Fees the transaction is expected to pay.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeeQuote {
+    /// Gas used by the execution of the transaction.
+    pub gas_used: u64,
+    /// Effective gas price of the transaction at the current base fee.
+    pub gas_price: U256,
+    /// L2 execution fee, `gas_used * gas_price`.
+    pub l2_fee: U256,
+    /// L1 data fee.
+    pub l1_fee: U256,
+}
+
+impl FeeQuote {
+    /// Returns the total fee, sum of L2 execution fee and L1 data fee.
+    pub fn total(&self) -> U256 {
+        self.l2_fee.saturating_add(self.l1_fee)
+    }
+}
+
+impl<EXT, DB: Database> Evm<'_, EXT, DB> {
+    /// Quotes the fees of the transaction set in the environment against the current state.
+    ///
+    /// Transaction is executed to get the gas used, state changes are not committed.
+    /// L1 data fee is calculated from `optimism.enveloped_tx` and is zero for deposit
+    /// transactions or if the enveloped transaction is not set.
+    ///
+    /// L1 block info set with `set_l1_block_info` is used for the quote and is kept for the next
+    /// transaction, otherwise it is loaded from the database.
+    pub fn quote_fees(&mut self) -> Result<FeeQuote, EVMError<DB::Error>> {
+        // Execution clears the L1 block info at the end of the transaction.
+        let l1_block_info = self.context.evm.inner.l1_block_info.clone();
+        let result = self.transact();
+        self.context.evm.inner.l1_block_info = l1_block_info;
+        let gas_used = result?.result.gas_used();
+        let spec_id = self.spec_id();
+
+        let env = &self.context.evm.env;
+        let gas_price = env.effective_gas_price();
+        let l1_fee = match &env.tx.optimism.enveloped_tx {
+            Some(enveloped_tx) if env.tx.optimism.source_hash.is_none() => {
+                match &self.context.evm.inner.l1_block_info {
+                    Some(l1_block_info) => {
+                        l1_block_info.calculate_tx_l1_cost(enveloped_tx, spec_id)
+                    }
+                    None => {
+                        let enveloped_tx = enveloped_tx.clone();
+                        L1BlockInfo::try_fetch(&mut self.context.evm.db, spec_id)
+                            .map_err(EVMError::Database)?
+                            .calculate_tx_l1_cost(&enveloped_tx, spec_id)
+                    }
+                }
+            }
+            _ => U256::ZERO,
+        };
+
+        Ok(FeeQuote {
+            gas_used,
+            gas_price,
+            l2_fee: gas_price.saturating_mul(U256::from(gas_used)),
+            l1_fee,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::InMemoryDB,
+        optimism::L1_BLOCK_CONTRACT,
+        primitives::{bytes, AccountInfo, Address, SpecId, TxKind},
+    };
+
+    fn quote_db() -> InMemoryDB {
+        let caller = Address::with_last_byte(1);
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            caller,
+            AccountInfo {
+                balance: U256::from(1_000_000_000),
+                ..Default::default()
+            },
+        );
+        // l1 base fee, overhead and scalar.
+        for slot in [1, 5, 6] {
+            db.insert_account_storage(L1_BLOCK_CONTRACT, U256::from(slot), U256::from(1_000))
+                .unwrap();
+        }
+        db
+    }
+
+    #[test]
+    fn quote_l2_and_l1_fee() {
+        let mut evm = Evm::builder()
+            .with_db(quote_db())
+            .with_spec_id(SpecId::REGOLITH)
+            .optimism()
+            .modify_block_env(|block| block.basefee = U256::from(10))
+            .modify_tx_env(|tx| {
+                tx.caller = Address::with_last_byte(1);
+                tx.transact_to = TxKind::Call(Address::with_last_byte(2));
+                tx.gas_limit = 21_000;
+                tx.gas_price = U256::from(10);
+                tx.optimism.enveloped_tx = Some(bytes!("FACADE"));
+            })
+            .build();
+
+        let quote = evm.quote_fees().unwrap();
+        assert_eq!(quote.gas_used, 21_000);
+        assert_eq!(quote.l2_fee, U256::from(210_000));
+        assert_eq!(quote.l1_fee, U256::from(1048));
+        assert_eq!(quote.total(), U256::from(211_048));
+    }
+
+    #[test]
+    fn quote_with_set_l1_block_info() {
+        // Differs from the database, the quote uses the set L1 block info.
+        let l1_block_info = L1BlockInfo {
+            l1_base_fee: U256::from(2_000),
+            l1_fee_overhead: Some(U256::from(1_000)),
+            l1_base_fee_scalar: U256::from(1_000),
+            ..Default::default()
+        };
+        let mut evm = Evm::builder()
+            .with_db(quote_db())
+            .with_spec_id(SpecId::REGOLITH)
+            .optimism()
+            .with_l1_block_info(l1_block_info.clone())
+            .modify_tx_env(|tx| {
+                tx.caller = Address::with_last_byte(1);
+                tx.transact_to = TxKind::Call(Address::with_last_byte(2));
+                tx.gas_limit = 21_000;
+                tx.optimism.enveloped_tx = Some(bytes!("FACADE"));
+            })
+            .build();
+
+        let quote = evm.quote_fees().unwrap();
+        assert_eq!(quote.l1_fee, U256::from(2096));
+        assert_eq!(evm.context.evm.inner.l1_block_info, Some(l1_block_info));
+
+        // Fee charged by the transaction matches the quote.
+        let caller = Address::with_last_byte(1);
+        let balance =
+            |evm: &mut Evm<'_, (), InMemoryDB>| evm.context.evm.db.accounts[&caller].info.balance;
+        let before = balance(&mut evm);
+        evm.transact_commit().unwrap();
+        assert_eq!(before - balance(&mut evm), quote.total());
+    }
+}
@@ -8,16 +8,17 @@ use crate::{
     interpreter::{return_ok, return_revert, Gas, InstructionResult},
     optimism,
     primitives::{
-        db::Database, spec_to_generic, Account, EVMError, Env, ExecutionResult, HaltReason,
-        HashMap, InvalidTransaction, OptimismInvalidTransaction, ResultAndState, Spec, SpecId,
-        SpecId::REGOLITH, U256,
+        db::Database, spec_to_generic, Account, BaseFeePolicy, EVMError, Env, ExecutionResult,
+        HaltReason, HashMap, InvalidTransaction, OptimismInvalidTransaction, ResultAndState, Spec,
+        SpecId, SpecId::REGOLITH, U256,
     },
     Context, ContextPrecompiles, FrameResult,
 };
 use core::ops::Mul;
-use revm_precompile::{secp256r1, PrecompileSpecId};
+use once_cell::race::OnceBox;
+use revm_precompile::{secp256r1, PrecompileSpecId, Precompiles};
 use std::string::ToString;
-use std::sync::Arc;
+use std::{boxed::Box, sync::Arc};
 
 pub fn optimism_handle_register<DB: Database, EXT>(handler: &mut EvmHandler<'_, EXT, DB>) {
     spec_to_generic!(handler.cfg.spec_id, {
@@ -147,23 +148,40 @@ pub fn last_frame_return<SPEC: Spec, EXT, DB: Database>(
 /// Load precompiles for Optimism chain.
 #[inline]
 pub fn load_precompiles<SPEC: Spec, EXT, DB: Database>() -> ContextPrecompiles<DB> {
-    let mut precompiles = ContextPrecompiles::new(PrecompileSpecId::from_spec_id(SPEC::SPEC_ID));
+    ContextPrecompiles::from_static_precompiles(optimism_precompiles(SPEC::SPEC_ID))
+}
 
-    if SPEC::enabled(SpecId::FJORD) {
-        precompiles.extend([
-            // EIP-7212: secp256r1 P256verify
-            secp256r1::P256VERIFY,
-        ])
-    }
+/// Returns the precompiles of the Optimism spec.
+///
+/// Every set is built once and shared by all EVMs, so loading precompiles does not allocate.
+pub fn optimism_precompiles(spec_id: SpecId) -> &'static Precompiles {
+    static FJORD: OnceBox<Precompiles> = OnceBox::new();
+    static GRANITE: OnceBox<Precompiles> = OnceBox::new();
+    static GRANITE_PRAGUE: OnceBox<Precompiles> = OnceBox::new();
+
+    let base = PrecompileSpecId::from_spec_id(spec_id);
+    let instance = if spec_id.is_enabled_in(SpecId::GRANITE) {
+        if base >= PrecompileSpecId::PRAGUE {
+            &GRANITE_PRAGUE
+        } else {
+            &GRANITE
+        }
+    } else if spec_id.is_enabled_in(SpecId::FJORD) {
+        &FJORD
+    } else {
+        return Precompiles::new(base);
+    };
 
-    if SPEC::enabled(SpecId::GRANITE) {
-        precompiles.extend([
+    instance.get_or_init(|| {
+        let mut precompiles = Precompiles::new(base).clone();
+        // EIP-7212: secp256r1 P256verify
+        precompiles.extend([secp256r1::P256VERIFY]);
+        if spec_id.is_enabled_in(SpecId::GRANITE) {
             // Restrict bn256Pairing input size
-            optimism::bn128::pair::GRANITE,
-        ])
-    }
-
-    precompiles
+            precompiles.extend([optimism::bn128::pair::GRANITE]);
+        }
+        Box::new(precompiles)
+    })
 }
 
 /// Load account (make them warm) and l1 data from database.
@@ -173,7 +191,10 @@ pub fn load_accounts<SPEC: Spec, EXT, DB: Database>(
 ) -> Result<(), EVMError<DB::Error>> {
     // the L1-cost fee is only computed for Optimism non-deposit transactions.
 
-    if context.evm.inner.env.tx.optimism.source_hash.is_none() {
+    // L1 block info set with `set_l1_block_info` is used as is.
+    if context.evm.inner.env.tx.optimism.source_hash.is_none()
+        && context.evm.inner.l1_block_info.is_none()
+    {
         let l1_block_info =
             crate::optimism::L1BlockInfo::try_fetch(&mut context.evm.inner.db, SPEC::SPEC_ID)
                 .map_err(EVMError::Database)?;
@@ -268,15 +289,23 @@ pub fn reward_beneficiary<SPEC: Spec, EXT, DB: Database>(
 
         let l1_cost = l1_block_info.calculate_tx_l1_cost(enveloped_tx, SPEC::SPEC_ID);
 
+        // If the base fee policy is not `Burn`, the base fee was already credited by the mainnet
+        // handler. If it is credited to the fee vault, the L1 cost goes to it as well.
+        let base_fee_policy = context.evm.inner.env.cfg.base_fee_policy;
+        let fee_vault = base_fee_policy.fee_vault();
+
         // Send the L1 cost of the transaction to the L1 Fee Vault.
-        let (l1_fee_vault_account, _) = context
-            .evm
-            .inner
-            .journaled_state
-            .load_account(optimism::L1_FEE_RECIPIENT, &mut context.evm.inner.db)?;
+        let (l1_fee_vault_account, _) = context.evm.inner.journaled_state.load_account(
+            fee_vault.unwrap_or(optimism::L1_FEE_RECIPIENT),
+            &mut context.evm.inner.db,
+        )?;
         l1_fee_vault_account.mark_touch();
         l1_fee_vault_account.info.balance += l1_cost;
 
+        if base_fee_policy != BaseFeePolicy::Burn {
+            return Ok(());
+        }
+
         // Send the base fee of the transaction to the Base Fee Vault.
         let (base_fee_vault_account, _) = context
             .evm
@@ -408,11 +437,7 @@ mod tests {
         let mut ctx = Context::new_empty();
         ctx.evm.inner.env = Box::new(env);
         let mut first_frame = FrameResult::Call(CallOutcome::new(
-            InterpreterResult {
-                result: instruction_result,
-                output: Bytes::new(),
-                gas,
-            },
+            InterpreterResult::new(instruction_result, Bytes::new(), gas),
             0..0,
         ));
         last_frame_return::<SPEC, _, _>(&mut ctx, &mut first_frame).unwrap();
@@ -513,6 +538,41 @@ mod tests {
         assert_eq!(account.info.balance, U256::from(1010));
     }
 
+    #[test]
+    fn test_optimism_precompiles_are_cached() {
+        let p256verify = secp256r1::P256VERIFY.0;
+        assert!(!optimism_precompiles(SpecId::ECOTONE).contains(&p256verify));
+        assert!(optimism_precompiles(SpecId::FJORD).contains(&p256verify));
+        assert!(optimism_precompiles(SpecId::GRANITE).contains(&p256verify));
+        assert!(optimism_precompiles(SpecId::PRAGUE).contains(&p256verify));
+        assert!(core::ptr::eq(
+            optimism_precompiles(SpecId::GRANITE),
+            optimism_precompiles(SpecId::GRANITE)
+        ));
+        assert!(!core::ptr::eq(
+            optimism_precompiles(SpecId::GRANITE),
+            optimism_precompiles(SpecId::PRAGUE)
+        ));
+    }
+
+    #[test]
+    fn test_load_accounts_uses_set_l1_block_info() {
+        let l1_block_info = L1BlockInfo {
+            l1_base_fee: U256::from(1_000),
+            l1_fee_overhead: Some(U256::from(1_000)),
+            l1_base_fee_scalar: U256::from(1_000),
+            ..Default::default()
+        };
+        let mut context: Context<(), EmptyDB> = Context::new_empty();
+        context.evm.set_l1_block_info(l1_block_info.clone());
+
+        load_accounts::<RegolithSpec, (), _>(&mut context).unwrap();
+        assert_eq!(context.evm.inner.l1_block_info, Some(l1_block_info));
+
+        mainnet::clear(&mut context);
+        assert_eq!(context.evm.inner.l1_block_info, None);
+    }
+
     #[test]
     fn test_remove_l1_cost_non_deposit() {
         let caller = Address::ZERO;
@@ -648,4 +708,67 @@ mod tests {
         // Nonce and balance checks should be skipped for deposit transactions.
         assert!(validate_env::<LatestSpec, EmptyDB>(&env).is_ok());
     }
+    /// Rewards a transaction that used 21 gas paying 15 wei per gas with a base fee of 10 wei.
+    /// Returns the L1 cost and the context.
+    fn rewarded_context(base_fee_policy: BaseFeePolicy) -> (U256, Context<(), EmptyDB>) {
+        let mut context: Context<(), EmptyDB> = Context::new_with_db(EmptyDB::default());
+        let l1_block_info = L1BlockInfo {
+            l1_base_fee: U256::from(1_000),
+            l1_fee_overhead: Some(U256::from(1_000)),
+            l1_base_fee_scalar: U256::from(1_000),
+            ..Default::default()
+        };
+        let enveloped_tx = bytes!("FACADE");
+        let l1_cost = l1_block_info.calculate_tx_l1_cost(&enveloped_tx, SpecId::REGOLITH);
+        context.evm.inner.l1_block_info = Some(l1_block_info);
+        context.evm.inner.env.tx.optimism.enveloped_tx = Some(enveloped_tx);
+        context.evm.inner.env.tx.gas_price = U256::from(15);
+        context.evm.inner.env.block.basefee = U256::from(10);
+        context.evm.inner.env.block.coinbase = Address::with_last_byte(1);
+        context.evm.inner.env.cfg.base_fee_policy = base_fee_policy;
+
+        let mut gas = Gas::new(100);
+        assert!(gas.record_cost(21));
+        reward_beneficiary::<RegolithSpec, (), _>(&mut context, &gas).unwrap();
+        (l1_cost, context)
+    }
+
+    fn balance(context: &Context<(), EmptyDB>, address: Address) -> U256 {
+        context
+            .evm
+            .inner
+            .journaled_state
+            .state
+            .get(&address)
+            .map(|account| account.info.balance)
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_reward_beneficiary_base_fee_policy() {
+        let coinbase = Address::with_last_byte(1);
+        let vault = Address::with_last_byte(2);
+        let priority_fee = U256::from(5 * 21);
+        let base_fee = U256::from(10 * 21);
+
+        // Base fee goes to the base fee vault.
+        let (l1_cost, context) = rewarded_context(BaseFeePolicy::Burn);
+        assert!(l1_cost > U256::ZERO);
+        assert_eq!(balance(&context, coinbase), priority_fee);
+        assert_eq!(balance(&context, optimism::L1_FEE_RECIPIENT), l1_cost);
+        assert_eq!(balance(&context, optimism::BASE_FEE_RECIPIENT), base_fee);
+
+        // Base fee goes to the coinbase.
+        let (l1_cost, context) = rewarded_context(BaseFeePolicy::ToCoinbase);
+        assert_eq!(balance(&context, coinbase), priority_fee + base_fee);
+        assert_eq!(balance(&context, optimism::L1_FEE_RECIPIENT), l1_cost);
+        assert_eq!(balance(&context, optimism::BASE_FEE_RECIPIENT), U256::ZERO);
+
+        // Base fee and L1 cost go to the vault.
+        let (l1_cost, context) = rewarded_context(BaseFeePolicy::ToVault(vault));
+        assert_eq!(balance(&context, coinbase), priority_fee);
+        assert_eq!(balance(&context, vault), base_fee + l1_cost);
+        assert_eq!(balance(&context, optimism::L1_FEE_RECIPIENT), U256::ZERO);
+        assert_eq!(balance(&context, optimism::BASE_FEE_RECIPIENT), U256::ZERO);
+    }
 }
@@ -46,7 +46,7 @@ pub const L1_BLOCK_CONTRACT: Address = address!("4200000000000000000000000000000
 /// uint64 _sequenceNumber, bytes32 _batcherHash, uint256 _l1FeeOverhead, uint256 _l1FeeScalar)
 ///
 /// For now, we only care about the fields necessary for L1 cost calculation.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct L1BlockInfo {
     /// The base fee of the L1 origin block.
     pub l1_base_fee: U256,
@@ -71,11 +71,9 @@ impl L1BlockInfo {
             let _ = db.basic(L1_BLOCK_CONTRACT)?;
         }
 
-        let l1_base_fee = db.storage(L1_BLOCK_CONTRACT, L1_BASE_FEE_SLOT)?;
-
         if !spec_id.is_enabled_in(SpecId::ECOTONE) {
-            let l1_fee_overhead = db.storage(L1_BLOCK_CONTRACT, L1_OVERHEAD_SLOT)?;
-            let l1_fee_scalar = db.storage(L1_BLOCK_CONTRACT, L1_SCALAR_SLOT)?;
+            let [l1_base_fee, l1_fee_overhead, l1_fee_scalar] =
+                fetch_slots(db, [L1_BASE_FEE_SLOT, L1_OVERHEAD_SLOT, L1_SCALAR_SLOT])?;
 
             Ok(L1BlockInfo {
                 l1_base_fee,
@@ -84,10 +82,15 @@ impl L1BlockInfo {
                 ..Default::default()
             })
         } else {
-            let l1_blob_base_fee = db.storage(L1_BLOCK_CONTRACT, ECOTONE_L1_BLOB_BASE_FEE_SLOT)?;
-            let l1_fee_scalars = db
-                .storage(L1_BLOCK_CONTRACT, ECOTONE_L1_FEE_SCALARS_SLOT)?
-                .to_be_bytes::<32>();
+            let [l1_base_fee, l1_blob_base_fee, l1_fee_scalars] = fetch_slots(
+                db,
+                [
+                    L1_BASE_FEE_SLOT,
+                    ECOTONE_L1_BLOB_BASE_FEE_SLOT,
+                    ECOTONE_L1_FEE_SCALARS_SLOT,
+                ],
+            )?;
+            let l1_fee_scalars = l1_fee_scalars.to_be_bytes::<32>();
 
             let l1_base_fee_scalar = U256::from_be_slice(
                 l1_fee_scalars[BASE_FEE_SCALAR_OFFSET..BASE_FEE_SCALAR_OFFSET + 4].as_ref(),
@@ -241,10 +244,83 @@ impl L1BlockInfo {
     }
 }
 
+/// Fetches the slots of the L1 block contract with [Database::storage_multi].
+///
+/// Slots missing from the returned values are fetched one by one.
+fn fetch_slots<DB: Database, const N: usize>(
+    db: &mut DB,
+    slots: [U256; N],
+) -> Result<[U256; N], DB::Error> {
+    let values = db.storage_multi(L1_BLOCK_CONTRACT, &slots)?;
+    let mut fetched = [U256::ZERO; N];
+    for (i, slot) in slots.into_iter().enumerate() {
+        fetched[i] = match values.get(i) {
+            Some(value) => *value,
+            None => db.storage(L1_BLOCK_CONTRACT, slot)?,
+        };
+    }
+    Ok(fetched)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::primitives::bytes;
+    use crate::{
+        db::InMemoryDB,
+        primitives::{bytes, AccountInfo, Address, Bytecode, B256},
+    };
+    use std::vec::Vec;
+
+    #[test]
+    fn test_try_fetch_with_incomplete_storage_multi() {
+        /// Database returning no values from `storage_multi`.
+        struct IncompleteDB(InMemoryDB);
+
+        impl Database for IncompleteDB {
+            type Error = <InMemoryDB as Database>::Error;
+
+            fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+                self.0.basic(address)
+            }
+
+            fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+                self.0.code_by_hash(code_hash)
+            }
+
+            fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+                self.0.storage(address, index)
+            }
+
+            fn storage_multi(
+                &mut self,
+                _address: Address,
+                _indices: &[U256],
+            ) -> Result<Vec<U256>, Self::Error> {
+                Ok(Vec::new())
+            }
+
+            fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+                self.0.block_hash(number)
+            }
+        }
+
+        let mut db = InMemoryDB::default();
+        for (slot, value) in [(1, 10), (5, 20), (6, 30)] {
+            db.insert_account_storage(L1_BLOCK_CONTRACT, U256::from(slot), U256::from(value))
+                .unwrap();
+        }
+
+        let l1_block_info = L1BlockInfo::try_fetch(&mut IncompleteDB(db), SpecId::BEDROCK).unwrap();
+        assert_eq!(
+            l1_block_info,
+            L1BlockInfo {
+                l1_base_fee: U256::from(10),
+                l1_fee_overhead: Some(U256::from(20)),
+                l1_base_fee_scalar: U256::from(30),
+                ..Default::default()
+            }
+        );
+    }
 
     #[test]
     fn test_data_gas_non_zero_bytes() {
@@ -0,0 +1,221 @@
+//! Simulation of multiple blocks of calls, in the style of `eth_simulateV1`.
+//!
+//! Calls are executed sequentially on top of an ephemeral [CacheDB], so every call sees the
+//! changes of the calls before it and the wrapped database is never modified.
+
+use crate::{
+    db::{AccountState, CacheDB, DatabaseRef},
+    primitives::{
+        Address, BlockEnv, Bytecode, EVMError, Env, ExecutionResult, HashMap, SpecId, TxEnv, B256,
+        U256,
+    },
+    Evm,
+};
+use std::{boxed::Box, vec::Vec};
+
+/// Overrides of the account state applied before the calls of a block are executed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccountOverride {
+    /// Overrides the balance.
+    pub balance: Option<U256>,
+    /// Overrides the nonce.
+    pub nonce: Option<u64>,
+    /// Overrides the code.
+    pub code: Option<Bytecode>,
+    /// Replaces the whole storage of the account.
+    pub state: Option<HashMap<U256, U256>>,
+    /// Overrides the given storage slots, other slots are left intact.
+    pub state_diff: Option<HashMap<U256, U256>>,
+}
+
+/// Overrides of the block environment.
+///
+/// Fields that are not set are taken from the previous block.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlockOverrides {
+    /// Overrides the block number.
+    pub number: Option<U256>,
+    /// Overrides the block timestamp.
+    pub timestamp: Option<U256>,
+    /// Overrides the block gas limit.
+    pub gas_limit: Option<U256>,
+    /// Overrides the block beneficiary.
+    pub coinbase: Option<Address>,
+    /// Overrides the base fee.
+    pub basefee: Option<U256>,
+    /// Overrides the prevrandao.
+    pub prevrandao: Option<B256>,
+    /// Overrides the difficulty.
+    pub difficulty: Option<U256>,
+}
+
+impl BlockOverrides {
+    /// Applies the overrides to the block environment.
+    pub fn apply(&self, block: &mut BlockEnv) {
+        if let Some(number) = self.number {
+            block.number = number;
+        }
+        if let Some(timestamp) = self.timestamp {
+            block.timestamp = timestamp;
+        }
+        if let Some(gas_limit) = self.gas_limit {
+            block.gas_limit = gas_limit;
+        }
+        if let Some(coinbase) = self.coinbase {
+            block.coinbase = coinbase;
+        }
+        if let Some(basefee) = self.basefee {
+            block.basefee = basefee;
+        }
+        if let Some(prevrandao) = self.prevrandao {
+            block.prevrandao = Some(prevrandao);
+        }
+        if let Some(difficulty) = self.difficulty {
+            block.difficulty = difficulty;
+        }
+    }
+}
+
+/// Batch of calls executed in a single simulated block.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimulatedBlock {
+    /// Overrides of the block environment.
+    pub block_overrides: BlockOverrides,
+    /// Overrides of the account state, applied before the calls are executed.
+    pub state_overrides: HashMap<Address, AccountOverride>,
+    /// Calls executed in order.
+    pub calls: Vec<TxEnv>,
+}
+
+/// Default time between simulated blocks, in seconds.
+pub const SIMULATED_BLOCK_TIME: u64 = 12;
+
+/// Executes the blocks of calls sequentially on top of the database.
+///
+/// First block uses the block environment of `env`, every following block has the number
+/// incremented by one and timestamp incremented by [SIMULATED_BLOCK_TIME], unless overridden.
+///
+/// Returns the results of every call, grouped by block. Execution stops at the first call that
+/// fails validation.
+pub fn simulate<DB: DatabaseRef>(
+    db: DB,
+    env: Box<Env>,
+    spec_id: SpecId,
+    blocks: Vec<SimulatedBlock>,
+) -> Result<Vec<Vec<ExecutionResult>>, EVMError<DB::Error>> {
+    let mut evm = Evm::builder()
+        .with_db(CacheDB::new(db))
+        .with_env(env)
+        .with_spec_id(spec_id)
+        .build();
+
+    let mut results = Vec::with_capacity(blocks.len());
+    for (index, block) in blocks.into_iter().enumerate() {
+        let block_env = evm.block_mut();
+        if index > 0 {
+            block_env.number += U256::from(1);
+            block_env.timestamp += U256::from(SIMULATED_BLOCK_TIME);
+        }
+        block.block_overrides.apply(block_env);
+
+        apply_state_overrides(evm.db_mut(), block.state_overrides).map_err(EVMError::Database)?;
+
+        let mut block_results = Vec::with_capacity(block.calls.len());
+        for tx in block.calls {
+            *evm.tx_mut() = tx;
+            block_results.push(evm.transact_commit()?);
+        }
+        results.push(block_results);
+    }
+    Ok(results)
+}
+
+fn apply_state_overrides<DB: DatabaseRef>(
+    db: &mut CacheDB<DB>,
+    overrides: HashMap<Address, AccountOverride>,
+) -> Result<(), DB::Error> {
+    for (address, account_override) in overrides {
+        let mut info = db.load_account(address)?.info.clone();
+        if let Some(balance) = account_override.balance {
+            info.balance = balance;
+        }
+        if let Some(nonce) = account_override.nonce {
+            info.nonce = nonce;
+        }
+        if let Some(code) = account_override.code {
+            info.code_hash = code.hash_slow();
+            info.code = Some(code);
+        }
+        db.insert_contract(&mut info);
+        let account = db.load_account(address)?;
+        account.info = info;
+        // overridden account exists even if it was not in the database.
+        if account.account_state == AccountState::NotExisting {
+            account.account_state = AccountState::Touched;
+        }
+
+        if let Some(state) = account_override.state {
+            db.replace_account_storage(address, state)?;
+        }
+        for (slot, value) in account_override.state_diff.into_iter().flatten() {
+            db.insert_account_storage(address, slot, value)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{db::EmptyDB, primitives::TxKind};
+
+    #[test]
+    fn blocks_share_state() {
+        let contract = Address::with_last_byte(0x10);
+        // NUMBER, PUSH1 0, MSTORE, PUSH1 32, PUSH1 0, RETURN
+        let code = Bytecode::new_raw([0x43, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3].into());
+        let call = TxEnv {
+            transact_to: TxKind::Call(contract),
+            ..Default::default()
+        };
+
+        let blocks = vec![
+            SimulatedBlock {
+                block_overrides: BlockOverrides {
+                    number: Some(U256::from(10)),
+                    ..Default::default()
+                },
+                state_overrides: HashMap::from([(
+                    contract,
+                    AccountOverride {
+                        code: Some(code),
+                        ..Default::default()
+                    },
+                )]),
+                calls: vec![call.clone()],
+            },
+            SimulatedBlock {
+                calls: vec![call.clone(), call],
+                ..Default::default()
+            },
+        ];
+        let results = simulate(EmptyDB::default(), Box::default(), SpecId::CANCUN, blocks).unwrap();
+
+        let numbers: Vec<Vec<U256>> = results
+            .iter()
+            .map(|block| {
+                block
+                    .iter()
+                    .map(|result| U256::from_be_slice(result.output().unwrap()))
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            numbers,
+            vec![vec![U256::from(10)], vec![U256::from(11), U256::from(11)]]
+        );
+    }
+}
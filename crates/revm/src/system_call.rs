@@ -0,0 +1,184 @@
+//! System calls, calls made by the protocol outside of the block transactions.
+//!
+//! System call is executed from [SYSTEM_ADDRESS] with [SYSTEM_CALL_GAS_LIMIT] gas, it is not
+//! validated, does not pay for the gas and does not reward the beneficiary.
+
+use crate::{
+    db::{Database, DatabaseCommit},
+    interpreter::CallInputs,
+    primitives::{
+        Address, Bytes, EVMError, EVMResult, ExecutionResult, ResultAndState, SpecId, TxEnv,
+        TxKind, CONSOLIDATION_REQUEST_PREDEPLOY_ADDRESS, SYSTEM_ADDRESS, SYSTEM_CALL_GAS_LIMIT,
+        WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS,
+    },
+    Evm, FrameOrResult,
+};
+use std::format;
+
+/// Requests collected by the post-block system calls of Prague.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PostBlockRequests {
+    /// EIP-7002 withdrawal requests returned by the withdrawal request predeploy.
+    pub withdrawal_requests: Bytes,
+    /// EIP-7251 consolidation requests returned by the consolidation request predeploy.
+    pub consolidation_requests: Bytes,
+}
+
+impl<EXT, DB: Database> Evm<'_, EXT, DB> {
+    /// Executes the system call of the contract at `address` with the given input.
+    ///
+    /// Transaction environment is restored after the call and the system address is removed
+    /// from the returned state, so it is not touched when the state is committed.
+    pub fn transact_system_call(&mut self, address: Address, data: Bytes) -> EVMResult<DB::Error> {
+        let tx = core::mem::replace(
+            &mut self.context.evm.env.tx,
+            TxEnv {
+                caller: SYSTEM_ADDRESS,
+                gas_limit: SYSTEM_CALL_GAS_LIMIT,
+                transact_to: TxKind::Call(address),
+                data,
+                ..Default::default()
+            },
+        );
+        let output = self.transact_system_call_inner();
+        let output = self.handler.post_execution().end(&mut self.context, output);
+        self.handler.post_execution().clear(&mut self.context);
+        self.context.evm.env.tx = tx;
+
+        output.map(|mut result| {
+            result.state.remove(&SYSTEM_ADDRESS);
+            result
+        })
+    }
+
+    fn transact_system_call_inner(&mut self) -> EVMResult<DB::Error> {
+        let ctx = &mut self.context;
+        let pre_exec = self.handler.pre_execution();
+        pre_exec.load_accounts(ctx)?;
+        let precompiles = pre_exec.load_precompiles();
+        ctx.evm.set_precompiles(precompiles);
+
+        let inputs = CallInputs::new_boxed(&ctx.evm.env.tx, SYSTEM_CALL_GAS_LIMIT).unwrap();
+        let mut result = match self.handler.execution().call(ctx, inputs)? {
+            FrameOrResult::Frame(first_frame) => self.run_the_loop(first_frame)?,
+            FrameOrResult::Result(result) => result,
+        };
+
+        let ctx = &mut self.context;
+        self.handler
+            .execution()
+            .last_frame_return(ctx, &mut result)?;
+        self.handler.post_execution().output(ctx, result)
+    }
+}
+
+impl<EXT, DB: Database + DatabaseCommit> Evm<'_, EXT, DB> {
+    /// Executes and commits the post-block system calls.
+    ///
+    /// From Prague, EIP-7002 withdrawal request and EIP-7251 consolidation request predeploys
+    /// are called after the last transaction of the block to dequeue the requests. Block is
+    /// invalid if any of the calls fails, which is returned as [EVMError::Custom].
+    ///
+    /// Returns empty requests before Prague.
+    pub fn transact_post_block_system_calls(
+        &mut self,
+    ) -> Result<PostBlockRequests, EVMError<DB::Error>> {
+        if !self.spec_id().is_enabled_in(SpecId::PRAGUE) {
+            return Ok(PostBlockRequests::default());
+        }
+        Ok(PostBlockRequests {
+            withdrawal_requests: self
+                .commit_system_call(WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS, "withdrawal request")?,
+            consolidation_requests: self.commit_system_call(
+                CONSOLIDATION_REQUEST_PREDEPLOY_ADDRESS,
+                "consolidation request",
+            )?,
+        })
+    }
+
+    /// Executes and commits the system call, returning the output of the successful call.
+    fn commit_system_call(
+        &mut self,
+        address: Address,
+        name: &str,
+    ) -> Result<Bytes, EVMError<DB::Error>> {
+        let ResultAndState { result, state } = self.transact_system_call(address, Bytes::new())?;
+        let ExecutionResult::Success { output, .. } = result else {
+            return Err(EVMError::Custom(format!(
+                "{name} system call failed: {result:?}"
+            )));
+        };
+        let has_code = state
+            .get(&address)
+            .is_some_and(|account| !account.info.is_empty_code_hash());
+        if !has_code {
+            return Err(EVMError::Custom(format!("{name} predeploy has no code")));
+        }
+        self.context.evm.db.commit(state);
+        Ok(output.into_data())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::{EmptyDB, InMemoryDB},
+        interpreter::opcode,
+        primitives::{AccountInfo, Bytecode, U256},
+    };
+
+    #[test]
+    fn post_block_system_calls() {
+        let caller = Address::with_last_byte(1);
+        let mut db = InMemoryDB::new(EmptyDB::default());
+        // Returns 32 bytes with value 42.
+        let withdrawal = Bytecode::new_raw(Bytes::from(vec![
+            opcode::PUSH1,
+            0x2a,
+            opcode::PUSH1,
+            0x00,
+            opcode::MSTORE,
+            opcode::PUSH1,
+            0x20,
+            opcode::PUSH1,
+            0x00,
+            opcode::RETURN,
+        ]));
+        // Returns nothing.
+        let consolidation = Bytecode::new_raw(Bytes::from(vec![opcode::STOP]));
+        for (address, code) in [
+            (WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS, withdrawal),
+            (CONSOLIDATION_REQUEST_PREDEPLOY_ADDRESS, consolidation),
+        ] {
+            db.insert_account_info(
+                address,
+                AccountInfo::new(U256::ZERO, 1, code.hash_slow(), code),
+            );
+        }
+
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_spec_id(SpecId::PRAGUE)
+            .modify_tx_env(|tx| tx.caller = caller)
+            .build();
+        let requests = evm.transact_post_block_system_calls().unwrap();
+        assert_eq!(
+            requests.withdrawal_requests,
+            Bytes::from(U256::from(42).to_be_bytes_vec())
+        );
+        assert!(requests.consolidation_requests.is_empty());
+        assert_eq!(evm.tx().caller, caller);
+        assert!(!evm.db().accounts.contains_key(&SYSTEM_ADDRESS));
+
+        // Predeploys are missing.
+        let mut evm = Evm::builder()
+            .with_db(InMemoryDB::new(EmptyDB::default()))
+            .with_spec_id(SpecId::PRAGUE)
+            .build();
+        assert!(matches!(
+            evm.transact_post_block_system_calls(),
+            Err(EVMError::Custom(_))
+        ));
+    }
+}